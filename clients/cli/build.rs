@@ -14,6 +14,17 @@ fn main() -> Result<(), Box<dyn Error>> {
         .to_string();
     println!("cargo:rustc-env=BUILD_TIMESTAMP={}", build_timestamp);
 
+    // Pin the expected hash of the embedded guest program, checked at startup
+    let elf_hash_path = "assets/fib_input_initial.sha3-256";
+    println!("cargo:rerun-if-changed={}", elf_hash_path);
+    let elf_hash = fs::read_to_string(elf_hash_path)?
+        .trim()
+        .to_ascii_lowercase();
+    if elf_hash.len() != 64 || !elf_hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("{} must contain a hex SHA3-256 hash", elf_hash_path).into());
+    }
+    println!("cargo:rustc-env=FIB_ELF_EXPECTED_SHA3_256={}", elf_hash);
+
     // Skip proto compilation unless build_proto feature is enabled.
     if !cfg!(feature = "build_proto") {
         println!(
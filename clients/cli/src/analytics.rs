@@ -6,7 +6,7 @@ use chrono::Datelike;
 use chrono::Timelike;
 use reqwest::header::ACCEPT;
 use serde_json::{Value, json};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::{Mutex, OnceLock};
 use std::time::{Duration, Instant};
 use std::{
@@ -52,6 +52,63 @@ pub fn analytics_api_key(environment: &Environment) -> String {
     }
 }
 
+/// Analytics event names that may be sent; unset means all events are enabled
+static ENABLED_EVENTS: OnceLock<HashSet<String>> = OnceLock::new();
+
+/// Restrict analytics to the given event names. `None` keeps all events enabled.
+/// Only the first call takes effect.
+pub fn set_enabled_analytics_events(event_names: Option<Vec<String>>) {
+    if let Some(names) = event_names {
+        let _ = ENABLED_EVENTS.set(names.into_iter().collect());
+    }
+}
+
+/// Maximum length of a node label, in characters
+const MAX_NODE_LABEL_LEN: usize = 64;
+
+/// Label attached to every analytics event, set with `--node-label`
+static NODE_LABEL: OnceLock<String> = OnceLock::new();
+
+/// Attach `label` to all analytics events after sanitizing it. Only the first call takes
+/// effect; labels that are empty after sanitizing are ignored.
+pub fn set_node_label(label: Option<&str>) {
+    if let Some(label) = label.and_then(sanitize_node_label) {
+        let _ = NODE_LABEL.set(label);
+    }
+}
+
+/// Truncate a label to [`MAX_NODE_LABEL_LEN`] characters and replace anything other than
+/// ASCII letters, digits, `-`, `_`, `.` and `:` with `_`.
+fn sanitize_node_label(label: &str) -> Option<String> {
+    let sanitized: String = label
+        .trim()
+        .chars()
+        .take(MAX_NODE_LABEL_LEN)
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    (!sanitized.is_empty()).then_some(sanitized)
+}
+
+/// Keep only the event names allowed by `enabled` (all of them when `None`).
+fn filter_enabled_events(
+    event_names: Vec<String>,
+    enabled: Option<&HashSet<String>>,
+) -> Vec<String> {
+    match enabled {
+        Some(enabled) => event_names
+            .into_iter()
+            .filter(|name| enabled.contains(name))
+            .collect(),
+        None => event_names,
+    }
+}
+
 /// Track an event with the Firebase Measurement Protocol
 ///
 /// # Arguments
@@ -70,6 +127,10 @@ pub async fn track(
     if analytics_id.is_empty() {
         return Ok(());
     }
+    let event_names = filter_enabled_events(event_names, ENABLED_EVENTS.get());
+    if event_names.is_empty() {
+        return Ok(());
+    }
     let local_now = chrono::offset::Local::now();
 
     // For tracking events, we use the Firebase Measurement Protocol
@@ -101,6 +162,9 @@ pub async fn track(
         "num_cores": num_cores(),
         "peak_flops": estimate_peak_gflops(num_cores()),
     });
+    if let Some(label) = NODE_LABEL.get() {
+        properties["node_label"] = json!(label);
+    }
 
     // Add event properties to the properties JSON
     // This is done by iterating over the key-value pairs in the event_properties JSON object
@@ -124,7 +188,7 @@ pub async fn track(
         }).collect::<Vec<_>>(),
     });
 
-    let client = reqwest::Client::new();
+    let client = crate::network::proxy::client_builder().build()?;
     let url = format!(
         "https://www.google-analytics.com/mp/collect?measurement_id={}&api_secret={}",
         analytics_id, analytics_api_key
@@ -156,38 +220,63 @@ const CLI_USER_AGENT: &str = concat!("nexus-cli/", env!("CARGO_PKG_VERSION"));
 
 /// Global, per-address, in-process rate limiter for reportProving calls
 static LAST_REPORT_BY_ADDRESS: OnceLock<Mutex<HashMap<String, Instant>>> = OnceLock::new();
-/// Global wallet address for reporting; set once during session setup
-static REPORT_WALLET_ADDRESS: OnceLock<String> = OnceLock::new();
+/// Wallet address to report for each node; set during session setup
+static REPORT_WALLET_BY_NODE: OnceLock<Mutex<HashMap<u64, String>>> = OnceLock::new();
+
+/// Minimum time between reports for the same wallet address
+const REPORT_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Set the wallet address used for reporting proving activity of a node
+pub fn set_wallet_address_for_reporting(node_id: u64, address: String) {
+    let map = REPORT_WALLET_BY_NODE.get_or_init(|| Mutex::new(HashMap::new()));
+    let mut guard = match map.lock() {
+        Ok(g) => g,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    guard.insert(node_id, address);
+}
 
-/// Set the wallet address used for reporting proving activity
-pub fn set_wallet_address_for_reporting(address: String) {
-    let _ = REPORT_WALLET_ADDRESS.set(address);
+/// Wallet address registered for a node, if any
+fn wallet_address_for_node(node_id: u64) -> Option<String> {
+    let map = REPORT_WALLET_BY_NODE.get()?;
+    let guard = match map.lock() {
+        Ok(g) => g,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    guard.get(&node_id).cloned()
 }
 
-/// Report proving activity to our Cloud Function at most once per hour per wallet address
-pub async fn report_proving_if_needed() {
-    let Some(wallet_address) = REPORT_WALLET_ADDRESS.get() else {
+/// Record a report for `wallet_address` at `now` unless one was sent within the interval.
+/// Returns whether a report should be sent.
+fn should_report(
+    last_reports: &mut HashMap<String, Instant>,
+    wallet_address: &str,
+    now: Instant,
+) -> bool {
+    match last_reports.get(wallet_address) {
+        Some(&last) if now.duration_since(last) < REPORT_INTERVAL => false,
+        _ => {
+            last_reports.insert(wallet_address.to_string(), now);
+            true
+        }
+    }
+}
+
+/// Report proving activity of a node to our Cloud Function at most once per hour per wallet address
+pub async fn report_proving_if_needed(node_id: u64) {
+    let Some(wallet_address) = wallet_address_for_node(node_id) else {
         return;
     };
     // Initialize map
     let map = LAST_REPORT_BY_ADDRESS.get_or_init(|| Mutex::new(HashMap::new()));
 
-    let now = Instant::now();
-
     // Check and update last report time with a small critical section
     let should_send = {
         let mut guard = match map.lock() {
             Ok(g) => g,
             Err(poisoned) => poisoned.into_inner(),
         };
-
-        match guard.get(wallet_address) {
-            Some(&last) if now.duration_since(last) < Duration::from_secs(3600) => false,
-            _ => {
-                guard.insert(wallet_address.to_string(), now);
-                true
-            }
-        }
+        should_report(&mut guard, &wallet_address, Instant::now())
     };
 
     if !should_send {
@@ -195,7 +284,9 @@ pub async fn report_proving_if_needed() {
     }
 
     // Fire-and-forget POST; ignore errors
-    let client = reqwest::Client::new();
+    let Ok(client) = crate::network::proxy::client_builder().build() else {
+        return;
+    };
     let body = json!({
         "data": { "address": wallet_address }
     });
@@ -251,6 +342,34 @@ pub async fn track_verification_failed(
     // TODO: Catch errors and log them
 }
 
+/// Track analytics for a task that repeatedly failed to prove (non-blocking)
+pub async fn track_task_unprovable(
+    task: Task,
+    attempts: u32,
+    error: String,
+    environment: Environment,
+    client_id: String,
+) {
+    let analytics_data = json!({
+        "program_name": task.program_id,
+        "task_id": task.task_id,
+        "attempts": attempts,
+        "error": error,
+    });
+
+    let _ = track(
+        vec![
+            "cli_task_unprovable".to_string(),
+            "task_unprovable".to_string(),
+        ],
+        analytics_data,
+        &environment,
+        client_id,
+    )
+    .await;
+    // TODO: Catch errors and log them
+}
+
 /// Track analytics for proof submission error (non-blocking)
 pub async fn track_proof_submission_error(
     task: crate::task::Task,
@@ -285,6 +404,7 @@ pub async fn track_proof_submission_error(
 /// Track analytics for proof acceptance (non-blocking)
 pub async fn track_proof_accepted(
     task: crate::task::Task,
+    node_id: u64,
     environment: Environment,
     client_id: String,
 ) {
@@ -307,13 +427,14 @@ pub async fn track_proof_accepted(
 
     // Rate-limited cloud ping
     tokio::spawn(async move {
-        report_proving_if_needed().await;
+        report_proving_if_needed(node_id).await;
     });
 }
 
 /// Track analytics for proof submission success (non-blocking)
 pub async fn track_proof_submission_success(
     task: crate::task::Task,
+    node_id: u64,
     environment: Environment,
     client_id: String,
 ) {
@@ -336,7 +457,7 @@ pub async fn track_proof_submission_success(
 
     // Rate-limited cloud ping
     tokio::spawn(async move {
-        report_proving_if_needed().await;
+        report_proving_if_needed(node_id).await;
     });
 }
 
@@ -426,3 +547,65 @@ pub async fn track_likely_oom_error(task: Task, environment: Environment, client
     .await;
     // TODO: Catch errors and log them
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_enabled_events() {
+        let names = vec!["cli_proof_node_v4".to_string(), "proof_node".to_string()];
+
+        // No filter configured: everything is sent
+        assert_eq!(filter_enabled_events(names.clone(), None), names);
+
+        let enabled: HashSet<String> = ["proof_node".to_string()].into_iter().collect();
+        assert_eq!(
+            filter_enabled_events(names.clone(), Some(&enabled)),
+            vec!["proof_node".to_string()]
+        );
+
+        let none_enabled = HashSet::new();
+        assert!(filter_enabled_events(names, Some(&none_enabled)).is_empty());
+    }
+
+    #[test]
+    fn test_sanitize_node_label() {
+        assert_eq!(
+            sanitize_node_label(" dc-1/rack 7 "),
+            Some("dc-1_rack_7".to_string())
+        );
+        assert_eq!(sanitize_node_label("   "), None);
+        let long = "a".repeat(MAX_NODE_LABEL_LEN + 10);
+        assert_eq!(
+            sanitize_node_label(&long).unwrap().len(),
+            MAX_NODE_LABEL_LEN
+        );
+    }
+
+    #[test]
+    fn test_should_report_rate_limits_per_wallet() {
+        let mut last_reports = HashMap::new();
+        let now = Instant::now();
+
+        assert!(should_report(&mut last_reports, "0xaaa", now));
+        assert!(!should_report(&mut last_reports, "0xaaa", now));
+        // A different wallet is tracked independently
+        assert!(should_report(&mut last_reports, "0xbbb", now));
+        // After the interval the same wallet may report again
+        assert!(should_report(
+            &mut last_reports,
+            "0xaaa",
+            now + REPORT_INTERVAL
+        ));
+    }
+
+    #[test]
+    fn test_wallet_address_is_per_node() {
+        set_wallet_address_for_reporting(1, "0xaaa".to_string());
+        set_wallet_address_for_reporting(2, "0xbbb".to_string());
+        assert_eq!(wallet_address_for_node(1).as_deref(), Some("0xaaa"));
+        assert_eq!(wallet_address_for_node(2).as_deref(), Some("0xbbb"));
+        assert_eq!(wallet_address_for_node(3), None);
+    }
+}
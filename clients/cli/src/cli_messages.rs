@@ -3,42 +3,55 @@
 //! This module provides consistent messaging for CLI commands like registration,
 //! logout, and other command-line operations.
 
-/// Print CLI command info message (for registration, logout, etc.)
-pub fn print_info(title: &str, details: &str) {
-    print!("\x1b[1;33m[INFO]\x1b[0m {}", title);
-    if !details.is_empty() {
-        println!("\t {}", details);
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Whether messages go to stderr, leaving stdout to `--output json` events
+static MESSAGES_TO_STDERR: AtomicBool = AtomicBool::new(false);
+
+/// Print messages to stderr for the rest of the process, so stdout carries only events
+pub fn send_messages_to_stderr() {
+    MESSAGES_TO_STDERR.store(true, Ordering::Relaxed);
+}
+
+/// Print a line of a message to stdout, or to stderr after `send_messages_to_stderr`
+fn emit(line: &str) {
+    if MESSAGES_TO_STDERR.load(Ordering::Relaxed) {
+        eprintln!("{}", line);
     } else {
-        println!();
+        println!("{}", line);
     }
 }
 
-/// Print CLI command warn message
-pub fn print_warn(title: &str, details: &str) {
-    print!("\x1b[1;91m[WARN]\x1b[0m {}", title);
-    if !details.is_empty() {
-        println!("\t {}", details);
+/// A tagged message line, with the details after a tab if there are any
+fn tagged(tag: &str, title: &str, details: &str) -> String {
+    if details.is_empty() {
+        format!("{} {}", tag, title)
     } else {
-        println!();
+        format!("{} {}\t {}", tag, title, details)
     }
 }
 
+/// Print CLI command info message (for registration, logout, etc.)
+pub fn print_info(title: &str, details: &str) {
+    emit(&tagged("\x1b[1;33m[INFO]\x1b[0m", title, details));
+}
+
+/// Print CLI command warn message
+pub fn print_warn(title: &str, details: &str) {
+    emit(&tagged("\x1b[1;91m[WARN]\x1b[0m", title, details));
+}
+
 /// Print CLI command error
 pub fn print_error(title: &str, details: Option<&str>) {
-    println!("\x1b[1;31m[ERROR]\x1b[0m {}", title);
+    emit(&format!("\x1b[1;31m[ERROR]\x1b[0m {}", title));
     if let Some(details) = details {
-        println!("\x1b[1;31m[ERROR]\x1b[0m Details: {}", details);
+        emit(&format!("\x1b[1;31m[ERROR]\x1b[0m Details: {}", details));
     }
 }
 
 /// Print CLI command success
 pub fn print_success(title: &str, details: &str) {
-    print!("\x1b[1;32m[SUCCESS]\x1b[0m {}", title);
-    if !details.is_empty() {
-        println!("\t {}", details);
-    } else {
-        println!();
-    }
+    emit(&tagged("\x1b[1;32m[SUCCESS]\x1b[0m", title, details));
 }
 
 /// Macro for backward compatibility with existing print_cmd_info! usage
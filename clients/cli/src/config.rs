@@ -2,20 +2,138 @@
 
 use crate::cli_messages::{print_error, print_info, print_success};
 use crate::environment::Environment;
+use crate::keys;
 use crate::orchestrator::Orchestrator;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::error::Error;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+/// File names the config may have, in order of preference. The first is used for new configs.
+const CONFIG_FILE_NAMES: [&str; 2] = ["config.json", "config.toml"];
+
 /// Get the path to the Nexus config file, typically located at ~/.nexus/config.json.
+/// An existing ~/.nexus/config.toml is used when there is no config.json.
 pub fn get_config_path() -> Result<PathBuf, std::io::Error> {
     let home_path = home::home_dir().ok_or(std::io::Error::new(
         std::io::ErrorKind::NotFound,
         "Home directory not found",
     ))?;
-    let config_path = home_path.join(".nexus").join("config.json");
-    Ok(config_path)
+    Ok(config_path_in(&home_path.join(".nexus")))
+}
+
+/// The config file in `dir`: the first of [`CONFIG_FILE_NAMES`] that exists, or config.json
+fn config_path_in(dir: &Path) -> PathBuf {
+    CONFIG_FILE_NAMES
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.exists())
+        .unwrap_or_else(|| dir.join(CONFIG_FILE_NAMES[0]))
+}
+
+/// Whether the config at `path` is TOML, judged by its extension. Anything else is JSON.
+fn is_toml(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("toml"))
+}
+
+/// Environment variables that override config values, taking precedence over both
+/// `--node-id` and the config file.
+pub const USER_ID_ENV: &str = "NEXUS_USER_ID";
+pub const WALLET_ADDRESS_ENV: &str = "NEXUS_WALLET_ADDRESS";
+pub const NODE_ID_ENV: &str = "NEXUS_NODE_ID";
+pub const ENVIRONMENT_ENV: &str = "NEXUS_ENVIRONMENT";
+
+/// Config values set through environment variables, validated
+#[derive(Debug, Default, PartialEq, Eq)]
+struct ConfigOverrides {
+    user_id: Option<String>,
+    wallet_address: Option<String>,
+    node_id: Option<u64>,
+    environment: Option<String>,
+}
+
+impl ConfigOverrides {
+    /// Read the overrides from the process environment
+    fn from_env() -> Result<Self, Box<dyn Error>> {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    /// Read the overrides with `lookup`, ignoring empty values. The node ID must be a
+    /// number and the wallet address a valid Ethereum address.
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Self, Box<dyn Error>> {
+        let var = |name: &str| {
+            lookup(name)
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+
+        let node_id = match var(NODE_ID_ENV) {
+            Some(value) => Some(value.parse::<u64>().map_err(|_| {
+                format!("{} must be a numeric node ID, got '{}'", NODE_ID_ENV, value)
+            })?),
+            None => None,
+        };
+        let wallet_address = var(WALLET_ADDRESS_ENV);
+        if let Some(address) = &wallet_address {
+            if !keys::is_valid_eth_address(address) {
+                return Err(format!(
+                    "{} must be a valid Ethereum address, got '{}'",
+                    WALLET_ADDRESS_ENV, address
+                )
+                .into());
+            }
+        }
+
+        Ok(Self {
+            user_id: var(USER_ID_ENV),
+            wallet_address,
+            node_id,
+            environment: var(ENVIRONMENT_ENV),
+        })
+    }
+
+    /// Replace the values of `config` that have an override, except the node ID, which
+    /// `Config::resolve` handles like `--node-id`
+    fn apply(&self, config: &mut Config) {
+        if let Some(user_id) = &self.user_id {
+            config.user_id = user_id.clone();
+        }
+        if let Some(wallet_address) = &self.wallet_address {
+            config.wallet_address = wallet_address.clone();
+        }
+        if let Some(environment) = &self.environment {
+            config.environment = environment.clone();
+        }
+    }
+}
+
+/// Name of the profile used when `--profile` is not given.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// A named set of node credentials, selected with `--profile <NAME>`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct Profile {
+    /// Environment the credentials belong to
+    #[serde(default)]
+    pub environment: String,
+
+    /// Orchestrator URL for custom environments
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub orchestrator_url: Option<String>,
+
+    /// User ID registered in this environment
+    #[serde(default)]
+    pub user_id: String,
+
+    /// Wallet address linked to the user
+    #[serde(default)]
+    pub wallet_address: String,
+
+    /// Node ID registered in this environment
+    #[serde(default)]
+    pub node_id: String,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
@@ -35,6 +153,14 @@ pub struct Config {
     /// Node ID, resolved to a valid u64 during `Config::resolve`
     #[serde(default)]
     pub node_id: String,
+
+    /// Named profiles. When empty, the flat fields above act as the default profile.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, Profile>,
+
+    /// Analytics event names to send (e.g. `cli_proof_node_v4`). When absent, all events are sent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub analytics_events: Option<Vec<String>>,
 }
 
 impl Config {
@@ -50,67 +176,180 @@ impl Config {
             wallet_address,
             node_id,
             environment: environment.to_string(),
+            profiles: BTreeMap::new(),
+            analytics_events: None,
+        }
+    }
+
+    /// Returns a copy of this config with the flat fields taken from the named profile.
+    ///
+    /// The `default` profile falls back to the flat (legacy) fields when it is not
+    /// defined explicitly; any other missing profile is an error.
+    pub fn select_profile(&self, name: &str) -> Result<Self, std::io::Error> {
+        match self.profiles.get(name) {
+            Some(profile) => Ok(Config {
+                environment: profile.environment.clone(),
+                user_id: profile.user_id.clone(),
+                wallet_address: profile.wallet_address.clone(),
+                node_id: profile.node_id.clone(),
+                profiles: self.profiles.clone(),
+                analytics_events: self.analytics_events.clone(),
+            }),
+            None if name == DEFAULT_PROFILE => Ok(self.clone()),
+            None => Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("Profile '{}' not found in config", name),
+            )),
+        }
+    }
+
+    /// Returns this config with `view`'s user, wallet, node and environment stored under
+    /// the named profile, the inverse of [`Config::select_profile`].
+    pub fn store_profile(&self, name: &str, view: &Config) -> Self {
+        let mut config = self.clone();
+        if name == DEFAULT_PROFILE && !self.profiles.contains_key(name) {
+            config.environment = view.environment.clone();
+            config.user_id = view.user_id.clone();
+            config.wallet_address = view.wallet_address.clone();
+            config.node_id = view.node_id.clone();
+        } else {
+            let profile = config.profiles.entry(name.to_string()).or_default();
+            profile.environment = view.environment.clone();
+            profile.user_id = view.user_id.clone();
+            profile.wallet_address = view.wallet_address.clone();
+            profile.node_id = view.node_id.clone();
+        }
+        config
+    }
+
+    /// Returns the environment the named profile connects to: its orchestrator URL, else
+    /// its `environment`. `None` if there is no config file or profile, or the profile
+    /// names neither.
+    pub fn profile_environment(
+        config_path: &Path,
+        name: &str,
+    ) -> Result<Option<Environment>, std::io::Error> {
+        if !config_path.exists() {
+            return Ok(None);
+        }
+        let config = Config::load_from_file(config_path)?;
+        let Some(profile) = config.profiles.get(name) else {
+            return Ok(None);
+        };
+        if let Some(url) = &profile.orchestrator_url {
+            return Ok(Some(Environment::Custom {
+                orchestrator_url: url.clone(),
+            }));
         }
+        if profile.environment.is_empty() {
+            return Ok(None);
+        }
+        profile.environment.parse().map(Some).map_err(|()| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!(
+                    "Unknown environment '{}' in profile '{}'",
+                    profile.environment, name
+                ),
+            )
+        })
     }
 
-    /// Loads configuration from a JSON file at the given path.
+    /// Loads configuration from the file at the given path: TOML for a `.toml` file,
+    /// JSON otherwise.
     pub fn load_from_file(path: &Path) -> Result<Self, std::io::Error> {
         let buf = fs::read(path)?;
-        let config: Config = serde_json::from_slice(&buf)
-            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        let config: Config = if is_toml(path) {
+            let text = std::str::from_utf8(&buf)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+            toml::from_str(text)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?
+        } else {
+            serde_json::from_slice(&buf)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?
+        };
         Ok(config)
     }
 
-    /// Saves the configuration to a JSON file at the given path.
+    /// Saves the configuration to the file at the given path, in the format its extension
+    /// names (see [`Config::load_from_file`]).
     pub fn save(&self, path: &Path) -> Result<(), std::io::Error> {
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent)?;
         }
-        let json = serde_json::to_string_pretty(self).map_err(|e| {
+        let serialized = if is_toml(path) {
+            toml::to_string_pretty(self).map_err(|e| e.to_string())
+        } else {
+            serde_json::to_string_pretty(self).map_err(|e| e.to_string())
+        };
+        let contents = serialized.map_err(|e| {
             std::io::Error::new(
                 std::io::ErrorKind::InvalidData,
                 format!("Serialization failed: {}", e),
             )
         })?;
-        fs::write(path, json)?;
+        fs::write(path, contents)?;
         Ok(())
     }
 
-    /// Clear the node ID configuration file.
+    /// Clear the node ID configuration. Removes every config file next to `path`, so a
+    /// config.toml left behind doesn't take over once config.json is gone.
     pub fn clear_node_config(path: &Path) -> std::io::Result<()> {
-        if !path.exists() {
-            println!("No config file found at {}", path.display());
-            return Ok(());
-        }
-        if !path.ends_with("config.json") {
+        if !CONFIG_FILE_NAMES.iter().any(|name| path.ends_with(name)) {
             return Err(std::io::Error::new(
                 std::io::ErrorKind::InvalidInput,
-                "Path must end with config.json",
+                "Path must end with config.json or config.toml",
             ));
         }
-        fs::remove_file(path)
+        let dir = path.parent().unwrap_or(Path::new(""));
+        let existing: Vec<PathBuf> = CONFIG_FILE_NAMES
+            .iter()
+            .map(|name| dir.join(name))
+            .filter(|path| path.exists())
+            .collect();
+        if existing.is_empty() {
+            println!("No config file found at {}", path.display());
+            return Ok(());
+        }
+        for path in existing {
+            fs::remove_file(path)?;
+        }
+        Ok(())
     }
 
     /// Resolves configuration and ensures node_id is available
     pub async fn resolve(
         node_id_arg: Option<u64>,
         config_path: &Path,
+        profile: &str,
         orchestrator: &impl Orchestrator,
     ) -> Result<Self, Box<dyn Error>> {
-        // Special case: if --node-id is provided, allow running without config file
-        if let Some(node_id) = node_id_arg {
+        // Environment variables win over --node-id and the config file
+        let overrides = ConfigOverrides::from_env().inspect_err(|e| {
+            print_error("Invalid environment variable", Some(&e.to_string()));
+        })?;
+
+        // Special case: if --node-id (or NEXUS_NODE_ID) is provided, allow running without
+        // config file
+        if let Some(node_id) = overrides.node_id.or(node_id_arg) {
             print_success("Using provided Node ID", &format!("Node ID: {}", node_id));
 
             // Get the wallet address for analytics
             let wallet_address = orchestrator.get_node(&node_id.to_string()).await?;
 
             // Create a minimal config with the provided node_id
-            let config = Config {
+            let mut config = Config {
                 user_id: "anonymous".to_string(), // Use anonymous for --node-id shortcut
                 wallet_address,
                 node_id: node_id.to_string(),
                 environment: "".to_string(),
+                profiles: BTreeMap::new(),
+                // Honour the analytics filter from the config file, if there is one
+                analytics_events: Config::load_from_file(config_path)
+                    .ok()
+                    .and_then(|config| config.analytics_events),
             };
+            overrides.apply(&mut config);
 
             return Ok(config);
         }
@@ -124,8 +363,13 @@ impl Config {
             return Err("Configuration file not found. Please register first.".into());
         }
 
-        // Load the config file
-        let mut config = Config::load_from_file(config_path)?;
+        // Load the config file and select the requested profile
+        let mut config = Config::load_from_file(config_path)?
+            .select_profile(profile)
+            .inspect_err(|e| {
+                print_error("Failed to select config profile", Some(&e.to_string()));
+            })?;
+        overrides.apply(&mut config);
 
         // Resolve node_id from config file
         let resolved_node_id = match config.resolve_node_id_from_config() {
@@ -151,7 +395,7 @@ impl Config {
 
         // Populate the config struct with the resolved values
         config.node_id = resolved_node_id.to_string();
-        config.wallet_address = wallet_address;
+        config.wallet_address = overrides.wallet_address.unwrap_or(wallet_address);
 
         Ok(config)
     }
@@ -202,6 +446,8 @@ mod tests {
             user_id: "test_user_id".to_string(),
             wallet_address: "0x1234567890abcdef1234567890abcdef12345678".to_string(),
             node_id: "test_node_id".to_string(),
+            profiles: BTreeMap::new(),
+            analytics_events: None,
         }
     }
 
@@ -280,6 +526,109 @@ mod tests {
         assert!(!path.exists(), "Config file was not removed");
     }
 
+    #[test]
+    // Clearing should remove both config files when a JSON and a TOML config exist.
+    fn test_clear_node_config_removes_every_config_file() {
+        let dir = tempdir().unwrap();
+        let json_path = dir.path().join("config.json");
+        let toml_path = dir.path().join("config.toml");
+        let config = get_config();
+        config.save(&json_path).unwrap();
+        config.save(&toml_path).unwrap();
+
+        Config::clear_node_config(&json_path).unwrap();
+        assert!(!json_path.exists(), "JSON config was not removed");
+        assert!(!toml_path.exists(), "TOML config was not removed");
+    }
+
+    #[test]
+    // A profile's environment should be used, and unreadable configs reported.
+    fn test_profile_environment() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        assert_eq!(Config::profile_environment(&path, "dev").unwrap(), None);
+
+        let mut config = get_config();
+        config.profiles.insert(
+            "prod".to_string(),
+            Profile {
+                environment: "production".to_string(),
+                ..Profile::default()
+            },
+        );
+        config.profiles.insert(
+            "typo".to_string(),
+            Profile {
+                environment: "prodution".to_string(),
+                ..Profile::default()
+            },
+        );
+        config.save(&path).unwrap();
+        assert_eq!(
+            Config::profile_environment(&path, "prod").unwrap(),
+            Some(Environment::Production)
+        );
+        assert!(Config::profile_environment(&path, "typo").is_err());
+
+        fs::write(&path, "invalid json").unwrap();
+        assert!(Config::profile_environment(&path, "prod").is_err());
+    }
+
+    #[test]
+    // A TOML config should round-trip, and clearing it should remove it.
+    fn test_toml_config_round_trip_and_clear() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        let mut config = get_config();
+        config.profiles.insert(
+            "dev".to_string(),
+            Profile {
+                environment: "devnet".to_string(),
+                node_id: "42".to_string(),
+                ..Profile::default()
+            },
+        );
+        config.analytics_events = Some(vec!["cli_proof_node_v4".to_string()]);
+        config.save(&path).unwrap();
+
+        let contents = fs::read_to_string(&path).unwrap();
+        assert!(contents.contains("user_id = \"test_user_id\""));
+        assert_eq!(Config::load_from_file(&path).unwrap(), config);
+
+        Config::clear_node_config(&path).unwrap();
+        assert!(!path.exists(), "Config file was not removed");
+    }
+
+    #[test]
+    // Should load a hand-written TOML config with a profile table.
+    fn test_load_handwritten_toml_config() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(
+            &path,
+            "node_id = \"123\"\n\n[profiles.dev]\nenvironment = \"devnet\"\nnode_id = \"456\"\n",
+        )
+        .unwrap();
+
+        let config = Config::load_from_file(&path).unwrap();
+        assert_eq!(config.node_id, "123");
+        assert_eq!(config.user_id, "");
+        assert_eq!(config.select_profile("dev").unwrap().node_id, "456");
+    }
+
+    #[test]
+    // config.json is preferred; config.toml is only used when it is the only one present.
+    fn test_config_path_prefers_json() {
+        let dir = tempdir().unwrap();
+        assert_eq!(config_path_in(dir.path()), dir.path().join("config.json"));
+
+        fs::write(dir.path().join("config.toml"), "").unwrap();
+        assert_eq!(config_path_in(dir.path()), dir.path().join("config.toml"));
+
+        fs::write(dir.path().join("config.json"), "{}").unwrap();
+        assert_eq!(config_path_in(dir.path()), dir.path().join("config.json"));
+    }
+
     #[test]
     // Should load JSON containing a user_id and empty strings for other fields.
     fn test_load_config_with_user_id_and_empty_fields() {
@@ -341,6 +690,8 @@ mod tests {
             user_id: "".to_string(),
             wallet_address: "".to_string(),
             node_id: "12345".to_string(),
+            profiles: BTreeMap::new(),
+            analytics_events: None,
         };
         config.save(&path).unwrap();
 
@@ -383,4 +734,106 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    // A legacy flat config should be saved without a profiles key.
+    fn test_save_omits_empty_profiles() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        get_config().save(&path).unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(!contents.contains("profiles"));
+    }
+
+    #[test]
+    // The default profile falls back to the flat fields when no profiles are defined.
+    fn test_select_default_profile_uses_flat_fields() {
+        let config = get_config();
+        let selected = config.select_profile(DEFAULT_PROFILE).unwrap();
+        assert_eq!(selected, config);
+    }
+
+    #[test]
+    // Selecting a named profile should replace the flat fields with the profile's values.
+    fn test_select_named_profile() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.json");
+
+        let mut file = File::create(&path).unwrap();
+        writeln!(
+            file,
+            r#"{{
+                "node_id": "1",
+                "user_id": "prod_user",
+                "profiles": {{
+                    "dev": {{
+                        "environment": "Custom",
+                        "orchestrator_url": "http://localhost:8080",
+                        "user_id": "dev_user",
+                        "node_id": "2"
+                    }}
+                }}
+            }}"#
+        )
+        .unwrap();
+
+        let config = Config::load_from_file(&path).unwrap();
+        let dev = config.select_profile("dev").unwrap();
+        assert_eq!(dev.user_id, "dev_user");
+        assert_eq!(dev.node_id, "2");
+        assert_eq!(
+            Config::profile_orchestrator_url(&path, "dev").as_deref(),
+            Some("http://localhost:8080")
+        );
+
+        let default = config.select_profile(DEFAULT_PROFILE).unwrap();
+        assert_eq!(default.user_id, "prod_user");
+        assert_eq!(default.node_id, "1");
+        assert_eq!(
+            Config::profile_orchestrator_url(&path, DEFAULT_PROFILE),
+            None
+        );
+    }
+
+    #[test]
+    // Selecting an unknown, non-default profile should fail.
+    fn test_select_missing_profile_fails() {
+        let result = get_config().select_profile("staging");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    // Environment overrides should be validated and replace the config file values.
+    fn test_config_overrides_from_env() {
+        let wallet = "0xabcdefabcdefabcdefabcdefabcdefabcdefabcd";
+        let overrides = ConfigOverrides::from_lookup(|name| match name {
+            NODE_ID_ENV => Some(" 123 ".to_string()),
+            WALLET_ADDRESS_ENV => Some(wallet.to_string()),
+            USER_ID_ENV => Some(String::new()),
+            _ => None,
+        })
+        .unwrap();
+        assert_eq!(overrides.node_id, Some(123));
+        assert_eq!(overrides.user_id, None);
+
+        let mut config = get_config();
+        overrides.apply(&mut config);
+        assert_eq!(config.wallet_address, wallet);
+        assert_eq!(config.user_id, "test_user_id");
+        assert_eq!(config.environment, "test");
+
+        let bad_node_id = ConfigOverrides::from_lookup(|name| {
+            (name == NODE_ID_ENV).then(|| "node-1".to_string())
+        });
+        assert!(bad_node_id.is_err());
+        let bad_wallet = ConfigOverrides::from_lookup(|name| {
+            (name == WALLET_ADDRESS_ENV).then(|| "0x1234".to_string())
+        });
+        assert!(bad_wallet.is_err());
+        assert_eq!(
+            ConfigOverrides::from_lookup(|_| None).unwrap(),
+            ConfigOverrides::default()
+        );
+    }
 }
@@ -16,6 +16,9 @@ pub mod cli_consts {
     /// Maximum number of event buffer size for worker threads
     pub const EVENT_QUEUE_SIZE: usize = 100;
 
+    /// Default window (seconds) within which identical events are coalesced
+    pub const DEFAULT_EVENT_DEDUPE_WINDOW_SECS: u64 = 30;
+
     // =============================================================================
     // PROVING CONFIGURATIONS
     // =============================================================================
@@ -26,9 +29,64 @@ pub mod cli_consts {
     /// Subprocess error code indicating an internal failure of the proving
     pub const SUBPROCESS_INTERNAL_ERROR_CODE: i32 = 3;
 
+    /// Exit code when `--fail-fast` stops the session on a proving failure
+    pub const FAIL_FAST_PROVING_EXIT_CODE: i32 = 4;
+
+    /// Exit code when `--fail-fast` stops the session on a submission failure
+    pub const FAIL_FAST_SUBMISSION_EXIT_CODE: i32 = 5;
+
+    /// Exit code when a blocking version constraint forbids this CLI version
+    pub const VERSION_BLOCKED_EXIT_CODE: i32 = 6;
+
+    /// How often a running session re-checks the published version requirements (seconds)
+    pub const VERSION_RECHECK_INTERVAL_SECS: u64 = 60 * 60;
+
+    /// Fraction by which each process shifts the re-check interval, picked once at
+    /// startup, so nodes started together don't re-check together
+    pub const VERSION_RECHECK_JITTER: f64 = 0.1;
+
     /// "Reasonable" generic projection task memory requirement.
     pub const PROJECTED_MEMORY_REQUIREMENT: u64 = 4294967296; // 4gb
 
+    /// Default number of failed proving attempts for the same task before it is
+    /// reported as unprovable
+    pub const DEFAULT_MAX_PROOF_ATTEMPTS: u32 = 3;
+
+    /// Default number of tasks held at once, counting the one being proved: the current
+    /// task plus one fetched ahead while prefetching
+    pub const DEFAULT_TASK_QUEUE_SIZE: u32 = 2;
+
+    /// Default memory for proofs kept by the proof cache (MB)
+    pub const DEFAULT_PROOF_CACHE_MB: u64 = 256;
+
+    /// Default time allowed to connect to the orchestrator (seconds)
+    pub const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 10;
+
+    /// Default time allowed for a whole orchestrator request (seconds)
+    pub const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 10;
+
+    /// Default cap on orchestrator response bodies (MB). Task responses are a few KB; this
+    /// leaves plenty of headroom while keeping a misbehaving endpoint from exhausting memory.
+    pub const DEFAULT_MAX_RESPONSE_MB: u64 = 16;
+
+    /// Maximum number of tasks whose failed proving attempts are remembered
+    pub const MAX_TRACKED_PROOF_ATTEMPTS: usize = 256;
+
+    /// How long to stop writing a local file (e.g. an event recording) after a write fails
+    pub const FAILED_WRITE_RETRY_SECS: u64 = 30;
+
+    /// Maximum number of a task's proofs verified at the same time
+    pub const MAX_PARALLEL_VERIFICATIONS: usize = 4;
+
+    /// Consecutive tasks on which the proving workers died before `--on-worker-death` applies
+    pub const WORKER_DEATH_THRESHOLD: u32 = 3;
+
+    /// Times the proving workers are restarted before the session shuts down
+    pub const MAX_WORKER_RESTARTS: u32 = 3;
+
+    /// Backoff before each worker restart, multiplied by the restart number
+    pub const WORKER_RESTART_BACKOFF_SECS: u64 = 30;
+
     // =============================================================================
     // DIFFICULTY CONFIGURATION
     // =============================================================================
@@ -38,6 +96,18 @@ pub mod cli_consts {
         /// Time threshold for auto-promotion (seconds)
         /// Tasks completing faster than this will promote to next difficulty level
         pub const PROMOTION_THRESHOLD_SECS: u64 = 7 * 60; // 7 minutes
+
+        /// `--promotion-threshold` values below this are accepted with a warning: even
+        /// the smallest tasks rarely finish this fast, so difficulty would never promote
+        pub const MIN_SENSIBLE_PROMOTION_THRESHOLD_SECS: u64 = 30;
+
+        /// Number of recent task durations averaged for the promotion decision, so a single
+        /// lucky fast task doesn't promote on its own
+        pub const PROMOTION_WINDOW: usize = 5;
+
+        /// Consecutive tasks at or over the promotion threshold after which adaptive
+        /// difficulty steps down one level
+        pub const DEMOTION_SLOW_STREAK: u32 = 3;
     }
 
     // =============================================================================
@@ -67,6 +137,9 @@ pub mod cli_consts {
         pub const fn rate_limit_interval() -> Duration {
             Duration::from_millis(RATE_LIMIT_INTERVAL_MS)
         }
+
+        /// Default longest wait between fetches while the server has no tasks (seconds)
+        pub const DEFAULT_IDLE_INTERVAL_SECS: u64 = 960;
     }
 
     /// Proof submission backoff configuration
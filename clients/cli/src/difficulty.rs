@@ -0,0 +1,41 @@
+//! Task difficulty levels
+//!
+//! The levels the CLI knows about, ordered from easiest to hardest. Adaptive difficulty,
+//! submission ordering and metrics all rank difficulties by their position here.
+
+use crate::nexus_orchestrator::TaskDifficulty;
+
+/// All available difficulty levels as (name, enum_value) pairs, easiest first
+pub const DIFFICULTY_LEVELS: &[(&str, TaskDifficulty)] = &[
+    ("SMALL", TaskDifficulty::Small),
+    ("SMALL_MEDIUM", TaskDifficulty::SmallMedium),
+    ("MEDIUM", TaskDifficulty::Medium),
+    ("LARGE", TaskDifficulty::Large),
+    ("EXTRA_LARGE", TaskDifficulty::ExtraLarge),
+    ("EXTRA_LARGE_2", TaskDifficulty::ExtraLarge2),
+    ("EXTRA_LARGE_3", TaskDifficulty::ExtraLarge3),
+    ("EXTRA_LARGE_4", TaskDifficulty::ExtraLarge4),
+    ("EXTRA_LARGE_5", TaskDifficulty::ExtraLarge5),
+];
+
+/// Position of `difficulty` in `DIFFICULTY_LEVELS`, lowest first
+pub fn difficulty_rank(difficulty: TaskDifficulty) -> Option<usize> {
+    DIFFICULTY_LEVELS
+        .iter()
+        .position(|(_, level)| *level == difficulty)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_difficulty_rank_orders_easiest_first() {
+        assert_eq!(difficulty_rank(TaskDifficulty::Small), Some(0));
+        assert!(difficulty_rank(TaskDifficulty::Medium) < difficulty_rank(TaskDifficulty::Large));
+        assert_eq!(
+            difficulty_rank(TaskDifficulty::ExtraLarge5),
+            Some(DIFFICULTY_LEVELS.len() - 1)
+        );
+    }
+}
@@ -24,7 +24,16 @@ impl Environment {
 impl FromStr for Environment {
     type Err = ();
 
+    /// Parses `production`, or `Custom(<url>)` as written to config files by `Display`
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(url) = s
+            .strip_prefix("Custom(")
+            .and_then(|rest| rest.strip_suffix(')'))
+        {
+            return Ok(Environment::Custom {
+                orchestrator_url: url.to_string(),
+            });
+        }
         match s.to_lowercase().as_str() {
             "production" => Ok(Environment::Production),
             _ => Err(()),
@@ -3,10 +3,17 @@
 //! Types and implementations for worker events and logging
 
 use crate::logging::{LogLevel, should_log_with_env};
-use chrono::Local;
+use crate::network::{RateLimitUsage, WaitReason};
+use chrono::{Local, NaiveDateTime, TimeZone};
+use serde::{Deserialize, Serialize};
 use std::fmt::Display;
+use std::time::{Duration, SystemTime};
+use tokio::sync::mpsc;
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// Format of [`Event::timestamp`], in local time
+pub const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub enum Worker {
     /// Worker that fetches tasks from the orchestrator and processes them.
     TaskFetcher,
@@ -16,25 +23,42 @@ pub enum Worker {
     ProofSubmitter,
 }
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq, strum::Display)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, strum::Display, Serialize, Deserialize)]
 pub enum EventType {
     Success,
     Error,
     Refresh,
+    /// Pacing requests to respect rate limits
     Waiting,
+    /// Backing off after failed requests
+    Backoff,
     StateChange,
+    /// One-time progress marker, e.g. the first proof submitted in a session
+    Milestone,
+    /// A fetched task passed over by the task type filter
+    Skipped,
 }
 
 /// Represents the current state in the proof pipeline
-#[derive(Debug, Copy, Clone, Eq, PartialEq, strum::Display)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, strum::Display, Serialize, Deserialize)]
 pub enum ProverState {
     /// Computing the proof
     Proving,
     /// Waiting before fetching next task (idle state)
     Waiting,
+    /// Outside the proving schedule; not fetching new tasks
+    Paused,
+}
+
+/// A wait before the next task fetch, so the dashboard can count it down
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FetchWait {
+    pub reason: WaitReason,
+    /// Seconds until the next fetch
+    pub secs: u64,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Event {
     pub worker: Worker,
     pub msg: String,
@@ -42,7 +66,29 @@ pub struct Event {
     pub event_type: EventType,
     pub log_level: LogLevel,
     /// Optional state information for state change events
+    #[serde(default)]
     pub prover_state: Option<ProverState>,
+    /// Fetch-to-acceptance latency in milliseconds, set on successful submissions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+    /// Usage of the server's request rate limit, set on task fetcher events
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit: Option<RateLimitUsage>,
+    /// Wait before the next fetch, set on task fetcher waiting events
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fetch_wait: Option<FetchWait>,
+    /// Difficulty of the task, set on the task fetcher event reporting a fetched task
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub difficulty: Option<String>,
+    /// Node the event belongs to, set when one process proves for several nodes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node_id: Option<u64>,
+    /// Identical events this one summarizes, set on "(repeated N times)" summaries
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repeated: Option<u32>,
+    /// Task the event is about, set by the workers on task, proof and submission events
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task_id: Option<String>,
 }
 
 impl PartialEq for Event {
@@ -53,6 +99,13 @@ impl PartialEq for Event {
             && self.event_type == other.event_type
             && self.log_level == other.log_level
             && self.prover_state == other.prover_state
+            && self.latency_ms == other.latency_ms
+            && self.rate_limit == other.rate_limit
+            && self.fetch_wait == other.fetch_wait
+            && self.difficulty == other.difficulty
+            && self.node_id == other.node_id
+            && self.repeated == other.repeated
+            && self.task_id == other.task_id
         // Note: We don't compare state_start_time since Instant doesn't implement Eq
     }
 }
@@ -64,10 +117,17 @@ impl Event {
         Self {
             worker,
             msg,
-            timestamp: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            timestamp: Local::now().format(TIMESTAMP_FORMAT).to_string(),
             event_type,
             log_level,
             prover_state: None,
+            latency_ms: None,
+            rate_limit: None,
+            fetch_wait: None,
+            difficulty: None,
+            node_id: None,
+            repeated: None,
+            task_id: None,
         }
     }
 
@@ -75,10 +135,17 @@ impl Event {
         Self {
             worker: Worker::TaskFetcher,
             msg,
-            timestamp: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            timestamp: Local::now().format(TIMESTAMP_FORMAT).to_string(),
             event_type: EventType::StateChange,
             log_level: LogLevel::Info,
             prover_state: Some(state),
+            latency_ms: None,
+            rate_limit: None,
+            fetch_wait: None,
+            difficulty: None,
+            node_id: None,
+            repeated: None,
+            task_id: None,
         }
     }
 
@@ -107,6 +174,57 @@ impl Event {
         Self::new(Worker::Prover(thread_id), msg, event_type, log_level)
     }
 
+    /// Attach the end-to-end latency of the task this event reports on
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency_ms = Some(latency.as_millis() as u64);
+        self
+    }
+
+    /// Attribute the event to a node
+    pub fn with_node_id(mut self, node_id: u64) -> Self {
+        self.node_id = Some(node_id);
+        self
+    }
+
+    /// Attribute the event to a task
+    pub fn with_task_id(mut self, task_id: &str) -> Self {
+        self.task_id = Some(task_id.to_string());
+        self
+    }
+
+    /// Attach the current usage of the server's request rate limit
+    pub fn with_rate_limit(mut self, usage: RateLimitUsage) -> Self {
+        self.rate_limit = Some(usage);
+        self
+    }
+
+    /// Attach the wait before the next task fetch
+    pub fn with_fetch_wait(mut self, reason: WaitReason, wait: Duration) -> Self {
+        self.fetch_wait = Some(FetchWait {
+            reason,
+            secs: wait.as_secs(),
+        });
+        self
+    }
+
+    /// Attach the difficulty of the task the event reports on
+    pub fn with_difficulty(
+        mut self,
+        difficulty: crate::nexus_orchestrator::TaskDifficulty,
+    ) -> Self {
+        self.difficulty = Some(difficulty.as_str_name().to_string());
+        self
+    }
+
+    /// When the event was created, parsed from its timestamp
+    pub fn time(&self) -> Option<SystemTime> {
+        let naive = NaiveDateTime::parse_from_str(&self.timestamp, TIMESTAMP_FORMAT).ok()?;
+        Local
+            .from_local_datetime(&naive)
+            .earliest()
+            .map(SystemTime::from)
+    }
+
     pub fn should_display(&self) -> bool {
         // Always show success events and info level events
         if self.event_type == EventType::Success || self.log_level >= LogLevel::Info {
@@ -122,6 +240,95 @@ impl Event {
 
 impl Display for Event {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{} [{}] {}", self.event_type, self.timestamp, self.msg)
+        match self.node_id {
+            Some(node_id) => write!(
+                f,
+                "{} [{}] [node {}] {}",
+                self.event_type, self.timestamp, node_id, self.msg
+            ),
+            None => write!(f, "{} [{}] {}", self.event_type, self.timestamp, self.msg),
+        }
+    }
+}
+
+/// Run `observe` on every event from `receiver` in a task of its own, forwarding each
+/// event unchanged. An event returned by `observe`, such as a warning that a write failed,
+/// is forwarded ahead of the one observed.
+///
+/// Returns the receiver that downstream consumers (TUI or headless) should read from.
+pub fn tap_events(
+    mut receiver: mpsc::Receiver<Event>,
+    mut observe: impl FnMut(&Event) -> Option<Event> + Send + 'static,
+) -> mpsc::Receiver<Event> {
+    let (sender, forwarded) = mpsc::channel::<Event>(crate::consts::cli_consts::EVENT_QUEUE_SIZE);
+    tokio::spawn(async move {
+        while let Some(event) = receiver.recv().await {
+            if let Some(extra) = observe(&event) {
+                if sender.send(extra).await.is_err() {
+                    break;
+                }
+            }
+            if sender.send(event).await.is_err() {
+                break;
+            }
+        }
+    });
+    forwarded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(msg: &str) -> Event {
+        Event::task_fetcher_with_level(msg.to_string(), EventType::Success, LogLevel::Info)
+    }
+
+    #[test]
+    fn test_task_id_round_trips() {
+        let got = event("Step 1 of 4: Got task abc-123").with_task_id("abc-123");
+        let json = serde_json::to_string(&got).unwrap();
+        assert!(json.contains(r#""task_id":"abc-123""#));
+        let parsed: Event = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.task_id.as_deref(), Some("abc-123"));
+
+        // Events that aren't about a task leave it out
+        let plain = serde_json::to_string(&event("Step 1 of 4: Fetching task...")).unwrap();
+        assert!(!plain.contains("task_id"));
+    }
+
+    #[test]
+    fn test_time_parses_timestamp() {
+        let mut recorded = event("Step 4 of 4: Proof submitted successfully");
+        recorded.timestamp = "2024-03-01 12:30:45".to_string();
+        let expected = Local
+            .with_ymd_and_hms(2024, 3, 1, 12, 30, 45)
+            .unwrap()
+            .into();
+        assert_eq!(recorded.time(), Some(expected));
+
+        recorded.timestamp = "not a time".to_string();
+        assert_eq!(recorded.time(), None);
+    }
+
+    #[test]
+    fn test_fetch_wait_round_trips() {
+        let waiting = event("Step 1 of 4: Rate limited by server - retrying in 90 seconds")
+            .with_fetch_wait(WaitReason::RateLimited, Duration::from_secs(90));
+        let json = serde_json::to_string(&waiting).unwrap();
+        let parsed: Event = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            parsed.fetch_wait,
+            Some(FetchWait {
+                reason: WaitReason::RateLimited,
+                secs: 90
+            })
+        );
+
+        // Recordings from before waits were attached still load
+        let plain = serde_json::to_string(&event("Step 1 of 4: Fetching task...")).unwrap();
+        assert!(!plain.contains("fetch_wait"));
+        let parsed: Event = serde_json::from_str(&plain).unwrap();
+        assert_eq!(parsed.fetch_wait, None);
     }
 }
@@ -1,7 +1,15 @@
 use log::LevelFilter;
+use serde::{Deserialize, Serialize};
 use std::env;
+use std::sync::atomic::{AtomicU8, Ordering};
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+/// Sentinel meaning "no runtime override, use RUST_LOG"
+const NO_OVERRIDE: u8 = u8::MAX;
+
+/// Log level threshold set at runtime (e.g. via SIGUSR1), taking precedence over RUST_LOG
+static LOG_LEVEL_OVERRIDE: AtomicU8 = AtomicU8::new(NO_OVERRIDE);
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum LogLevel {
     Trace = 0,
     Debug = 1,
@@ -22,7 +30,23 @@ impl From<LogLevel> for LevelFilter {
     }
 }
 
+impl LogLevel {
+    fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(LogLevel::Trace),
+            1 => Some(LogLevel::Debug),
+            2 => Some(LogLevel::Info),
+            3 => Some(LogLevel::Warn),
+            4 => Some(LogLevel::Error),
+            _ => None,
+        }
+    }
+}
+
 pub fn get_rust_log_level() -> LogLevel {
+    if let Some(level) = log_level_override() {
+        return level;
+    }
     let rust_log = env::var("RUST_LOG").unwrap_or_else(|_| "info".to_string());
     parse_rust_log_level(&rust_log)
 }
@@ -48,6 +72,49 @@ pub fn parse_rust_log_level(rust_log: &str) -> LogLevel {
     }
 }
 
+/// Log level threshold set at runtime with `-v` or SIGUSR1, if any
+pub fn log_level_override() -> Option<LogLevel> {
+    LogLevel::from_u8(LOG_LEVEL_OVERRIDE.load(Ordering::Relaxed))
+}
+
+/// Override the log level threshold for the rest of the process lifetime.
+pub fn set_log_level_override(level: LogLevel) {
+    LOG_LEVEL_OVERRIDE.store(level as u8, Ordering::Relaxed);
+}
+
+/// Toggle the active threshold between Info and Debug, returning the new level.
+pub fn cycle_log_level() -> LogLevel {
+    let next = next_log_level(get_rust_log_level());
+    set_log_level_override(next);
+    next
+}
+
+/// Threshold after toggling from `current`: Debug from Info or less verbose, else Info
+fn next_log_level(current: LogLevel) -> LogLevel {
+    match current {
+        LogLevel::Trace | LogLevel::Debug => LogLevel::Info,
+        _ => LogLevel::Debug,
+    }
+}
+
+/// Display threshold for `-v` repeated `count` times, or `None` to keep the default.
+pub fn verbosity_log_level(count: u8) -> Option<LogLevel> {
+    match count {
+        0 => None,
+        1 => Some(LogLevel::Debug),
+        _ => Some(LogLevel::Trace),
+    }
+}
+
+/// Lower the display threshold for `-v`/`-vv`, unless RUST_LOG is already more verbose.
+pub fn apply_verbosity(count: u8) {
+    if let Some(level) = verbosity_log_level(count) {
+        if level < get_rust_log_level() {
+            set_log_level_override(level);
+        }
+    }
+}
+
 pub fn should_log(event_level: LogLevel, threshold: LogLevel) -> bool {
     event_level >= threshold
 }
@@ -80,6 +147,14 @@ mod tests {
         assert_eq!(parse_rust_log_level("invalid"), LogLevel::Info);
     }
 
+    #[test]
+    fn test_verbosity_log_level() {
+        assert_eq!(verbosity_log_level(0), None);
+        assert_eq!(verbosity_log_level(1), Some(LogLevel::Debug));
+        assert_eq!(verbosity_log_level(2), Some(LogLevel::Trace));
+        assert_eq!(verbosity_log_level(5), Some(LogLevel::Trace));
+    }
+
     #[test]
     fn test_should_log() {
         assert!(should_log(LogLevel::Error, LogLevel::Debug));
@@ -87,4 +162,12 @@ mod tests {
         assert!(!should_log(LogLevel::Debug, LogLevel::Error));
         assert!(!should_log(LogLevel::Info, LogLevel::Error));
     }
+
+    #[test]
+    fn test_next_log_level() {
+        assert_eq!(next_log_level(LogLevel::Info), LogLevel::Debug);
+        assert_eq!(next_log_level(LogLevel::Debug), LogLevel::Info);
+        assert_eq!(next_log_level(LogLevel::Trace), LogLevel::Info);
+        assert_eq!(next_log_level(LogLevel::Error), LogLevel::Debug);
+    }
 }
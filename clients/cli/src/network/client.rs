@@ -86,6 +86,11 @@ impl NetworkClient {
                     self.request_timer.record_success();
                     return Ok(proof_task_result);
                 }
+                Err(e) if e.is_no_tasks_available() => {
+                    // Not a failure: slow down until work appears instead of backing off
+                    self.request_timer.record_empty();
+                    return Err(e);
+                }
                 Err(e) => {
                     attempts += 1;
 
@@ -41,6 +41,10 @@ impl ErrorHandler {
             // Retry on network/connection errors
             OrchestratorError::Reqwest(_) => true,
             OrchestratorError::Decode(_) => true,
+            // Skip tasks we can't handle and fetch another
+            OrchestratorError::UnsupportedTask(_) => true,
+            // An oversized response won't shrink on retry
+            OrchestratorError::ResponseTooLarge { .. } => false,
 
             // HTTP errors - check status code
             OrchestratorError::Http { status, .. } => {
@@ -1,6 +1,7 @@
 pub mod client;
 pub mod error_handler;
+pub mod proxy;
 pub mod request_timer;
 
 pub use client::{NetworkClient, ProofSubmission};
-pub use request_timer::{RequestTimer, RequestTimerConfig};
+pub use request_timer::{RateLimitUsage, RequestTimer, RequestTimerConfig, WaitReason};
@@ -2,8 +2,30 @@
 //!
 //! This module replaces the separate backoff and rate limiter components with a
 //! unified approach that prioritizes server-provided retry delays over local timing strategies.
-
-use std::time::{Duration, Instant};
+//!
+//! Timing state is kept in memory unless a state file is configured, in which case recent
+//! request times are saved after every request and reloaded on startup, so a quickly
+//! restarted process still respects the current rate-limit window. State is kept per node,
+//! so processes for different nodes on one host don't share or overwrite a window.
+
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Get the directory for a node's persisted request timer state, typically
+/// ~/.nexus/rate_limits/<node_id>.
+pub fn get_request_timer_state_dir(node_id: u64) -> Result<PathBuf, std::io::Error> {
+    let home_path = home::home_dir().ok_or(std::io::Error::new(
+        std::io::ErrorKind::NotFound,
+        "Home directory not found",
+    ))?;
+    Ok(home_path
+        .join(".nexus")
+        .join("rate_limits")
+        .join(node_id.to_string()))
+}
 
 /// Configuration for request timing behavior
 #[derive(Debug, Clone)]
@@ -16,6 +38,11 @@ pub struct RequestTimerConfig {
     pub time_window: Option<Duration>,
     /// Default retry delay when server doesn't provide one
     pub default_retry_delay: Duration,
+    /// File to persist request times to across restarts; in-memory only when `None`
+    pub state_path: Option<PathBuf>,
+    /// Longest delay after consecutive empty responses; without it, empty responses wait
+    /// `default_retry_delay` without growing
+    pub idle_max_delay: Option<Duration>,
 }
 
 impl RequestTimerConfig {
@@ -26,6 +53,8 @@ impl RequestTimerConfig {
             max_requests: None,
             time_window: None,
             default_retry_delay: Duration::from_secs(1),
+            state_path: None,
+            idle_max_delay: None,
         }
     }
 
@@ -36,6 +65,8 @@ impl RequestTimerConfig {
             max_requests: Some(max_requests),
             time_window: Some(time_window),
             default_retry_delay: Duration::from_secs(1),
+            state_path: None,
+            idle_max_delay: None,
         }
     }
 
@@ -51,10 +82,121 @@ impl RequestTimerConfig {
             max_requests: Some(max_requests),
             time_window: Some(time_window),
             default_retry_delay,
+            state_path: None,
+            idle_max_delay: None,
+        }
+    }
+
+    /// Persist request times to `path`, if given, so they survive a restart
+    pub fn with_state_path(mut self, path: Option<PathBuf>) -> Self {
+        self.state_path = path;
+        self
+    }
+
+    /// Double the delay after each consecutive empty response, up to `max_delay`
+    pub fn with_idle_backoff(mut self, max_delay: Duration) -> Self {
+        self.idle_max_delay = Some(max_delay);
+        self
+    }
+}
+
+/// How much of the request window is used, to explain why requests are paced
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateLimitUsage {
+    /// Requests made within the current window
+    pub used: u32,
+    /// Requests allowed per window
+    pub max: u32,
+    /// Seconds until the oldest request in the window expires
+    pub resets_in_secs: u64,
+}
+
+impl RateLimitUsage {
+    /// Fraction of the window used, from 0.0 to 1.0
+    pub fn ratio(&self) -> f64 {
+        if self.max == 0 {
+            return 1.0;
         }
+        (self.used as f64 / self.max as f64).min(1.0)
     }
 }
 
+impl Display for RateLimitUsage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{} requests this window", self.used, self.max)?;
+        if self.used > 0 {
+            write!(f, ", resets in {}s", self.resets_in_secs)?;
+        }
+        Ok(())
+    }
+}
+
+/// Request timer state as saved to disk, with times in milliseconds since the Unix epoch
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+struct PersistedTimerState {
+    last_request_ms: Option<u64>,
+    request_times_ms: Vec<u64>,
+    server_retry_until_ms: Option<u64>,
+}
+
+impl PersistedTimerState {
+    fn load(path: &Path) -> Result<Self, std::io::Error> {
+        let buf = fs::read(path)?;
+        serde_json::from_slice(&buf)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+
+    fn save(&self, path: &Path) -> Result<(), std::io::Error> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string(self)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        fs::write(path, json)
+    }
+}
+
+/// Convert an `Instant` to milliseconds since the Unix epoch
+fn instant_to_unix_ms(instant: Instant) -> u64 {
+    let now = Instant::now();
+    let now_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64;
+    if instant <= now {
+        now_ms.saturating_sub(now.duration_since(instant).as_millis() as u64)
+    } else {
+        now_ms + instant.duration_since(now).as_millis() as u64
+    }
+}
+
+/// Convert milliseconds since the Unix epoch to an `Instant`, if it can be represented
+fn unix_ms_to_instant(ms: u64) -> Option<Instant> {
+    let now = Instant::now();
+    let now_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .ok()?
+        .as_millis() as u64;
+    if ms <= now_ms {
+        now.checked_sub(Duration::from_millis(now_ms - ms))
+    } else {
+        now.checked_add(Duration::from_millis(ms - now_ms))
+    }
+}
+
+/// Why the next request has to wait
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WaitReason {
+    /// Normal pacing to stay within the rate limits
+    Pacing,
+    /// Backing off after a failed request
+    Backoff,
+    /// Waiting as long as the server asked in `Retry-After`
+    RateLimited,
+    /// Slowing down because the server had no tasks
+    Idle,
+}
+
 /// Unified request timer that handles both rate limiting and retry timing
 /// Server-provided retry delays always override local timing strategies
 #[derive(Debug)]
@@ -63,16 +205,74 @@ pub struct RequestTimer {
     last_request_time: Option<Instant>,
     request_times: Vec<Instant>,
     server_retry_until: Option<Instant>,
+    /// What set the pending retry delay: a success, a failure or an empty response
+    retry_reason: WaitReason,
+    /// Consecutive empty responses, for the idle backoff
+    empty_streak: u32,
 }
 
 impl RequestTimer {
     pub fn new(config: RequestTimerConfig) -> Self {
-        Self {
+        let mut timer = Self {
             config,
             last_request_time: None,
             request_times: Vec::new(),
             server_retry_until: None,
-        }
+            retry_reason: WaitReason::Pacing,
+            empty_streak: 0,
+        };
+        timer.load_state();
+        timer
+    }
+
+    /// Restore request times saved by a previous process, if a state file is configured.
+    /// A missing or unreadable file starts with a clean slate.
+    fn load_state(&mut self) {
+        let Some(state) = self
+            .config
+            .state_path
+            .as_deref()
+            .and_then(|path| PersistedTimerState::load(path).ok())
+        else {
+            return;
+        };
+
+        // Clock changes can put saved times in the future; never trust those
+        let now = Instant::now();
+        self.last_request_time = state
+            .last_request_ms
+            .and_then(unix_ms_to_instant)
+            .filter(|&time| time <= now);
+        self.request_times = state
+            .request_times_ms
+            .into_iter()
+            .filter_map(unix_ms_to_instant)
+            .filter(|&time| time <= now)
+            .collect();
+        self.request_times.sort();
+        self.server_retry_until = state
+            .server_retry_until_ms
+            .and_then(unix_ms_to_instant)
+            .filter(|&until| until > now);
+    }
+
+    /// Save the current request times, if a state file is configured. Saving is best
+    /// effort: a failure only means a restart won't remember this window.
+    fn save_state(&self) {
+        let Some(path) = &self.config.state_path else {
+            return;
+        };
+        let state = PersistedTimerState {
+            last_request_ms: self.last_request_time.map(instant_to_unix_ms),
+            request_times_ms: self
+                .request_times
+                .iter()
+                .copied()
+                .map(instant_to_unix_ms)
+                .collect(),
+            server_retry_until_ms: self.server_retry_until.map(instant_to_unix_ms),
+        };
+        let _ = state.save(path);
     }
 
     /// Check if a new request can proceed
@@ -116,6 +316,8 @@ impl RequestTimer {
     pub fn record_success(&mut self) {
         let now = Instant::now();
         self.last_request_time = Some(now);
+        self.retry_reason = WaitReason::Pacing;
+        self.empty_streak = 0;
         if self.config.max_requests.is_some() {
             self.request_times.push(now);
         }
@@ -125,6 +327,7 @@ impl RequestTimer {
         if self.server_retry_until.is_none() || self.server_retry_until.unwrap() <= now {
             self.server_retry_until = Some(now + self.config.default_retry_delay);
         }
+        self.save_state();
     }
 
     /// Record a failed request with optional server-provided retry delay
@@ -132,6 +335,11 @@ impl RequestTimer {
     pub fn record_failure(&mut self, server_retry_delay: Option<Duration>) {
         let now = Instant::now();
         self.last_request_time = Some(now);
+        self.retry_reason = if server_retry_delay.is_some() {
+            WaitReason::RateLimited
+        } else {
+            WaitReason::Backoff
+        };
 
         if self.config.max_requests.is_some() {
             self.request_times.push(now);
@@ -144,6 +352,81 @@ impl RequestTimer {
             // Use default retry delay if no server delay provided
             self.server_retry_until = Some(now + self.config.default_retry_delay);
         }
+        self.save_state();
+    }
+
+    /// Record a request the server answered without work, e.g. no tasks available.
+    /// Consecutive empty responses double the retry delay, up to the idle maximum; the
+    /// next successful request resets it.
+    pub fn record_empty(&mut self) {
+        let now = Instant::now();
+        self.last_request_time = Some(now);
+        if self.config.max_requests.is_some() {
+            self.request_times.push(now);
+        }
+        self.retry_reason = WaitReason::Idle;
+        self.empty_streak = self.empty_streak.saturating_add(1);
+
+        let delay = self.idle_delay();
+        // Never shorten a delay the server asked for
+        let until = now + delay;
+        if self
+            .server_retry_until
+            .is_none_or(|current| current < until)
+        {
+            self.server_retry_until = Some(until);
+        }
+        self.save_state();
+    }
+
+    /// Delay after the current run of empty responses
+    fn idle_delay(&self) -> Duration {
+        let base = self.config.default_retry_delay;
+        let Some(max_delay) = self.config.idle_max_delay else {
+            return base;
+        };
+        let doublings = self.empty_streak.saturating_sub(1).min(16);
+        base.saturating_mul(1 << doublings).min(max_delay.max(base))
+    }
+
+    /// Why the next request has to wait, or `None` if it can proceed now. Waiting out the
+    /// retry delay of a failed request is a backoff, and of an empty response is idling;
+    /// anything else is normal pacing.
+    pub fn wait_reason(&mut self) -> Option<WaitReason> {
+        if self.can_proceed() {
+            return None;
+        }
+        let retry_pending = self
+            .server_retry_until
+            .is_some_and(|until| Instant::now() < until);
+        if retry_pending {
+            Some(self.retry_reason)
+        } else {
+            Some(WaitReason::Pacing)
+        }
+    }
+
+    /// Requests made within the current window, if this timer limits requests per window
+    pub fn usage(&mut self) -> Option<RateLimitUsage> {
+        let (Some(max_requests), Some(time_window)) =
+            (self.config.max_requests, self.config.time_window)
+        else {
+            return None;
+        };
+        let now = Instant::now();
+        self.request_times
+            .retain(|&time| now.duration_since(time) <= time_window);
+
+        let resets_in = self
+            .request_times
+            .first()
+            .map(|&oldest| time_window.saturating_sub(now.duration_since(oldest)))
+            .unwrap_or_default();
+        Some(RateLimitUsage {
+            used: self.request_times.len() as u32,
+            max: max_requests,
+            resets_in_secs: resets_in.as_secs_f64().ceil() as u64,
+        })
     }
 
     /// Get time until next request is allowed
@@ -250,6 +533,45 @@ mod tests {
         timer.record_success();
     }
 
+    #[test]
+    fn test_wait_reason_tells_backoff_from_pacing() {
+        let config = RequestTimerConfig::_interval(Duration::from_millis(100));
+        let mut timer = RequestTimer::new(config);
+        assert_eq!(timer.wait_reason(), None);
+
+        timer.record_success();
+        assert_eq!(timer.wait_reason(), Some(WaitReason::Pacing));
+
+        timer.record_failure(None);
+        assert_eq!(timer.wait_reason(), Some(WaitReason::Backoff));
+
+        // A delay from the server is reported as rate limiting
+        timer.record_failure(Some(Duration::from_secs(10)));
+        assert_eq!(timer.wait_reason(), Some(WaitReason::RateLimited));
+    }
+
+    #[test]
+    fn test_idle_backoff_grows_and_resets() {
+        let mut config = RequestTimerConfig::_interval(Duration::ZERO);
+        config.default_retry_delay = Duration::from_secs(10);
+        let mut timer = RequestTimer::new(config.with_idle_backoff(Duration::from_secs(35)));
+
+        timer.record_empty();
+        assert_eq!(timer.wait_reason(), Some(WaitReason::Idle));
+        assert_eq!(timer.idle_delay(), Duration::from_secs(10));
+        timer.record_empty();
+        assert_eq!(timer.idle_delay(), Duration::from_secs(20));
+        timer.record_empty();
+        // Capped at the idle maximum
+        assert_eq!(timer.idle_delay(), Duration::from_secs(35));
+        assert!(timer.time_until_next() > Duration::from_secs(34));
+
+        // Work appearing speeds fetching back up
+        timer.record_success();
+        assert_eq!(timer.wait_reason(), Some(WaitReason::Pacing));
+        assert_eq!(timer.idle_delay(), Duration::from_secs(10));
+    }
+
     #[test]
     fn test_min_interval_without_server_delay() {
         let config = RequestTimerConfig::_interval(Duration::from_millis(100));
@@ -262,4 +584,68 @@ mod tests {
         // Immediate second request should be blocked by min interval
         assert!(!timer.can_proceed());
     }
+
+    #[test]
+    fn test_persisted_window_survives_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("fetch.json");
+        let config = RequestTimerConfig::_requests_per_window(2, Duration::from_secs(60))
+            .with_state_path(Some(path.clone()));
+
+        let mut timer = RequestTimer::new(config.clone());
+        timer.record_success();
+        timer.record_success();
+
+        // A restarted timer still sees the full window
+        let mut restarted = RequestTimer::new(config);
+        assert_eq!(restarted.request_times.len(), 2);
+        restarted.server_retry_until = None;
+        assert!(!restarted.can_proceed());
+        assert!(restarted.time_until_next() > Duration::from_secs(55));
+    }
+
+    #[test]
+    fn test_state_dir_is_per_node() {
+        assert_ne!(
+            get_request_timer_state_dir(1).unwrap(),
+            get_request_timer_state_dir(2).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_usage_counts_requests_in_window() {
+        let config = RequestTimerConfig::_requests_per_window(6, Duration::from_secs(60));
+        let mut timer = RequestTimer::new(config);
+        assert_eq!(
+            timer.usage().unwrap().to_string(),
+            "0/6 requests this window"
+        );
+
+        timer.record_success();
+        timer.record_failure(None);
+        let usage = timer.usage().unwrap();
+        assert_eq!(usage.used, 2);
+        assert_eq!(usage.max, 6);
+        assert!(usage.resets_in_secs > 55 && usage.resets_in_secs <= 60);
+        assert!(
+            usage
+                .to_string()
+                .starts_with("2/6 requests this window, resets in")
+        );
+
+        // Interval-only timers have no window to report
+        let mut interval = RequestTimer::new(RequestTimerConfig::_interval(Duration::from_secs(1)));
+        assert!(interval.usage().is_none());
+    }
+
+    #[test]
+    fn test_in_memory_timer_ignores_state() {
+        let config = RequestTimerConfig::_requests_per_window(1, Duration::from_secs(60));
+        let mut timer = RequestTimer::new(config.clone());
+        timer.record_success();
+
+        let restarted = RequestTimer::new(config);
+        assert!(restarted.request_times.is_empty());
+        assert!(restarted.server_retry_until.is_none());
+    }
 }
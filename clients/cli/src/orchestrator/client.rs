@@ -2,18 +2,24 @@
 //!
 //! A client for the Nexus Orchestrator, allowing for proof task retrieval and submission.
 
+use crate::consts::cli_consts::{
+    DEFAULT_CONNECT_TIMEOUT_SECS, DEFAULT_MAX_RESPONSE_MB, DEFAULT_REQUEST_TIMEOUT_SECS,
+};
 use crate::environment::Environment;
+use crate::network::proxy;
 use crate::nexus_orchestrator::{
-    GetProofTaskRequest, GetProofTaskResponse, NodeType, RegisterNodeRequest, RegisterNodeResponse,
-    RegisterUserRequest, SubmitProofRequest, UserResponse,
+    GetProofTaskRequest, GetProofTaskResponse, NodeTelemetry, NodeType, RegisterNodeRequest,
+    RegisterNodeResponse, RegisterUserRequest, SubmitProofRequest, UserResponse,
 };
 use crate::orchestrator::Orchestrator;
 use crate::orchestrator::error::OrchestratorError;
+use crate::orchestrator::geo;
 use crate::system::{estimate_peak_gflops, get_memory_info};
 use crate::task::Task;
 use ed25519_dalek::{Signer, SigningKey, VerifyingKey};
 use prost::Message;
-use reqwest::{Client, ClientBuilder, Response};
+use reqwest::{Client, Response};
+use sha3::{Digest, Keccak256};
 use std::sync::OnceLock;
 use std::time::Duration;
 
@@ -32,6 +38,8 @@ pub(crate) type ProofPayload = (Vec<u8>, Vec<Vec<u8>>, Vec<String>);
 pub struct ProofTaskResult {
     pub task: Task,
     pub actual_difficulty: crate::nexus_orchestrator::TaskDifficulty,
+    /// Server limit on tasks in flight for this node, if it reported one
+    pub max_concurrent_tasks: Option<u32>,
 }
 
 impl std::fmt::Display for ProofTaskResult {
@@ -50,6 +58,9 @@ static BUILD_TIMESTAMP: &str = match option_env!("BUILD_TIMESTAMP") {
     None => "Build timestamp not available",
 };
 
+/// Default cap on orchestrator response bodies, in bytes
+pub const DEFAULT_MAX_RESPONSE_BYTES: usize = DEFAULT_MAX_RESPONSE_MB as usize * 1024 * 1024;
+
 // User-Agent string with CLI version
 const USER_AGENT: &str = concat!("nexus-cli/", env!("CARGO_PKG_VERSION"));
 
@@ -59,36 +70,199 @@ const USER_AGENT: &str = concat!("nexus-cli/", env!("CARGO_PKG_VERSION"));
 // No precise location, IP addresses, or personal data is collected or stored.
 pub(crate) static COUNTRY_CODE: OnceLock<String> = OnceLock::new();
 
+/// Join an orchestrator base URL and an endpoint path.
+///
+/// Exactly one slash separates the two, an empty endpoint yields the base URL, an endpoint
+/// that is only a query string is appended directly, and an absolute endpoint URL is
+/// returned unchanged. The endpoint is otherwise used verbatim, so percent-encoded
+/// segments are preserved.
+pub(crate) fn join_url(base: &str, endpoint: &str) -> String {
+    if endpoint.starts_with("http://") || endpoint.starts_with("https://") {
+        return endpoint.to_string();
+    }
+
+    let base = base.trim_end_matches('/');
+    let endpoint = endpoint.trim_start_matches('/');
+    if endpoint.is_empty() {
+        base.to_string()
+    } else if endpoint.starts_with('?') {
+        format!("{}{}", base, endpoint)
+    } else {
+        format!("{}/{}", base, endpoint)
+    }
+}
+
+/// Idempotency key for a proof submission.
+///
+/// Derived only from the task ID and proof hash, so every retry of the same submission
+/// carries the same key and the server can drop duplicates.
+pub(crate) fn submission_idempotency_key(task_id: &str, proof_hash: &str) -> String {
+    let mut hasher = Keccak256::new();
+    hasher.update(task_id.as_bytes());
+    hasher.update(b":");
+    hasher.update(proof_hash.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// Append a streamed chunk to `body`, failing if the total would exceed `limit` bytes.
+fn append_capped(body: &mut Vec<u8>, chunk: &[u8], limit: usize) -> Result<(), OrchestratorError> {
+    if body.len() + chunk.len() > limit {
+        return Err(OrchestratorError::ResponseTooLarge { limit });
+    }
+    body.extend_from_slice(chunk);
+    Ok(())
+}
+
+/// Read a response body, failing early if `Content-Length` or the streamed body exceeds
+/// `limit` bytes.
+pub(crate) async fn read_capped_body(
+    mut response: Response,
+    limit: usize,
+) -> Result<Vec<u8>, OrchestratorError> {
+    if response
+        .content_length()
+        .is_some_and(|length| length > limit as u64)
+    {
+        return Err(OrchestratorError::ResponseTooLarge { limit });
+    }
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        append_capped(&mut body, &chunk, limit)?;
+    }
+    Ok(body)
+}
+
+/// HTTP protocol version used for orchestrator requests
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum HttpVersion {
+    /// Negotiate HTTP/2 via ALPN, falling back to HTTP/1.1
+    #[default]
+    Auto,
+    /// Force HTTP/1.1, e.g. for proxies that break HTTP/2
+    Http1,
+    /// Force HTTP/2 without negotiation
+    Http2,
+}
+
+/// Build the HTTP client used for orchestrator requests.
+///
+/// Responses compressed with gzip, deflate or brotli (e.g. by a proxy) are decompressed
+/// transparently, so the body can be decoded as protobuf.
+fn build_http_client(
+    http_version: HttpVersion,
+    connect_timeout: Duration,
+    request_timeout: Duration,
+) -> Result<Client, reqwest::Error> {
+    let builder = proxy::client_builder()
+        .gzip(true)
+        .deflate(true)
+        .brotli(true)
+        .connect_timeout(connect_timeout)
+        .timeout(request_timeout);
+    let builder = match http_version {
+        HttpVersion::Auto => builder,
+        HttpVersion::Http1 => builder.http1_only(),
+        HttpVersion::Http2 => builder.http2_prior_knowledge(),
+    };
+    builder.build()
+}
+
 #[derive(Debug, Clone)]
 pub struct OrchestratorClient {
     client: Client,
     environment: Environment,
+    /// Diagnostic: attach full proofs even for `ProofHash` tasks
+    always_attach_proof: bool,
+    /// Largest response body accepted, in bytes
+    max_response_bytes: usize,
+    /// Whether the country may be looked up and reported in telemetry
+    geo_lookups: bool,
 }
 
 impl OrchestratorClient {
     pub fn new(environment: Environment) -> Self {
+        Self::with_http_version(environment, HttpVersion::default())
+    }
+
+    /// Create a client that uses the given HTTP protocol version.
+    pub fn with_http_version(environment: Environment, http_version: HttpVersion) -> Self {
+        Self::with_timeouts(
+            environment,
+            http_version,
+            Duration::from_secs(DEFAULT_CONNECT_TIMEOUT_SECS),
+            Duration::from_secs(DEFAULT_REQUEST_TIMEOUT_SECS),
+        )
+    }
+
+    /// Create a client that uses the given HTTP protocol version and allows `connect`
+    /// to establish a connection and `request` for a whole request, e.g. longer on
+    /// high-latency links.
+    pub fn with_timeouts(
+        environment: Environment,
+        http_version: HttpVersion,
+        connect: Duration,
+        request: Duration,
+    ) -> Self {
         Self {
-            client: ClientBuilder::new()
-                .connect_timeout(Duration::from_secs(10))
-                .timeout(Duration::from_secs(10))
-                .build()
+            client: build_http_client(http_version, connect, request)
                 .expect("Failed to create HTTP client"),
             environment,
+            always_attach_proof: false,
+            max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+            geo_lookups: true,
         }
     }
 
+    /// With `geo_lookups` off (`--no-geo`), telemetry carries no location and no country
+    /// lookup is made for it.
+    pub fn with_geo_lookups(mut self, geo_lookups: bool) -> Self {
+        self.geo_lookups = geo_lookups;
+        self
+    }
+
+    /// Reject response bodies, including error bodies, larger than `max_response_bytes`.
+    pub fn with_max_response_bytes(mut self, max_response_bytes: usize) -> Self {
+        self.max_response_bytes = max_response_bytes;
+        self
+    }
+
+    /// Attach the full proof to `ProofHash` submissions so the server can recompute and
+    /// compare the hash. Diagnostic only; increases bandwidth.
+    pub fn with_always_attach_proof(mut self, always_attach_proof: bool) -> Self {
+        self.always_attach_proof = always_attach_proof;
+        self
+    }
+
     /// Public accessor for privacy-preserving country code (cached during run)
     #[allow(dead_code)]
     pub async fn country(&self) -> String {
         self.get_country().await
     }
 
+    /// Hardware telemetry attached to every proof submission.
+    pub async fn node_telemetry(&self, num_provers: usize) -> NodeTelemetry {
+        let (program_memory, total_memory) = get_memory_info();
+        let flops = estimate_peak_gflops(num_provers);
+
+        // Detect country for network optimization (privacy-preserving: only country code, no precise location)
+        let location = if self.geo_lookups {
+            Some(self.get_country().await)
+        } else {
+            None
+        };
+
+        NodeTelemetry {
+            flops_per_sec: Some(flops as i32),
+            memory_used: Some(program_memory),
+            memory_capacity: Some(total_memory),
+            // Country code for network routing optimization (privacy-preserving)
+            location,
+        }
+    }
+
     fn build_url(&self, endpoint: &str) -> String {
-        format!(
-            "{}/{}",
-            self.environment.orchestrator_url().trim_end_matches('/'),
-            endpoint.trim_start_matches('/')
-        )
+        join_url(&self.environment.orchestrator_url(), endpoint)
     }
 
     fn encode_request<T: Message>(request: &T) -> Vec<u8> {
@@ -103,7 +277,8 @@ impl OrchestratorClient {
     ///
     /// Returns a tuple `(legacy_proof, proofs, individual_proof_hashes)` with the appropriate
     /// fields populated:
-    /// - For `ProofHash`: no proof bytes and no hashes (server derives hash elsewhere).
+    /// - For `ProofHash`: no proof bytes and no hashes (server derives hash elsewhere), unless
+    ///   `always_attach_proof` is set, in which case proofs are attached as for `ProofRequired`.
     /// - For `AllProofHashes`: no proof bytes; `individual_proof_hashes` populated.
     /// - For other types (e.g. `ProofRequired`): `legacy_proof` is set only when exactly
     ///   one proof is present (back-compat), and `proofs` contains the vector of full proofs.
@@ -112,9 +287,10 @@ impl OrchestratorClient {
         legacy_proof: Vec<u8>,
         proofs: Vec<Vec<u8>>,
         individual_proof_hashes: &[String],
+        always_attach_proof: bool,
     ) -> ProofPayload {
         match task_type {
-            crate::nexus_orchestrator::TaskType::ProofHash => {
+            crate::nexus_orchestrator::TaskType::ProofHash if !always_attach_proof => {
                 // For ProofHash tasks, don't send proof or individual hashes
                 (Vec::new(), Vec::new(), Vec::new())
             }
@@ -137,9 +313,12 @@ impl OrchestratorClient {
         }
     }
 
-    async fn handle_response_status(response: Response) -> Result<Response, OrchestratorError> {
+    async fn handle_response_status(
+        &self,
+        response: Response,
+    ) -> Result<Response, OrchestratorError> {
         if !response.status().is_success() {
-            return Err(OrchestratorError::from_response(response).await);
+            return Err(OrchestratorError::from_response(response, self.max_response_bytes).await);
         }
         Ok(response)
     }
@@ -157,8 +336,8 @@ impl OrchestratorClient {
             .send()
             .await?;
 
-        let response = Self::handle_response_status(response).await?;
-        let response_bytes = response.bytes().await?;
+        let response = self.handle_response_status(response).await?;
+        let response_bytes = read_capped_body(response, self.max_response_bytes).await?;
         Self::decode_response(&response_bytes)
     }
 
@@ -178,8 +357,8 @@ impl OrchestratorClient {
             .send()
             .await?;
 
-        let response = Self::handle_response_status(response).await?;
-        let response_bytes = response.bytes().await?;
+        let response = self.handle_response_status(response).await?;
+        let response_bytes = read_capped_body(response, self.max_response_bytes).await?;
         Self::decode_response(&response_bytes)
     }
 
@@ -199,7 +378,7 @@ impl OrchestratorClient {
             .send()
             .await?;
 
-        Self::handle_response_status(response).await?;
+        self.handle_response_status(response).await?;
         Ok(())
     }
 
@@ -227,7 +406,8 @@ impl OrchestratorClient {
     /// information. The country information helps the Nexus network route requests to
     /// the nearest servers for better performance and reduced latency.
     ///
-    /// The detection is cached for the duration of the program run.
+    /// The detection is cached for the duration of the program run, and on disk for a
+    /// day across runs.
     async fn get_country(&self) -> String {
         if let Some(country) = COUNTRY_CODE.get() {
             return country.clone();
@@ -239,59 +419,14 @@ impl OrchestratorClient {
     }
 
     async fn detect_country(&self) -> String {
-        // Try Cloudflare first (most reliable)
-        if let Ok(country) = self.get_country_from_cloudflare().await {
-            return country;
-        }
-
-        // Fallback to ipinfo.io
-        if let Ok(country) = self.get_country_from_ipinfo().await {
-            return country;
-        }
-
-        // If we can't detect the country, use the US as a fallback
-        "US".to_string()
+        geo::detect_country(&self.client).await
     }
+}
 
-    async fn get_country_from_cloudflare(&self) -> Result<String, Box<dyn std::error::Error>> {
-        let response = self
-            .client
-            .get("https://cloudflare.com/cdn-cgi/trace")
-            .timeout(Duration::from_secs(5))
-            .send()
-            .await?;
-
-        let text = response.text().await?;
-
-        for line in text.lines() {
-            if let Some(country) = line.strip_prefix("loc=") {
-                let country = country.trim().to_uppercase();
-                if country.len() == 2 && country.chars().all(|c| c.is_ascii_alphabetic()) {
-                    return Ok(country);
-                }
-            }
-        }
-
-        Err("Country not found in Cloudflare response".into())
-    }
-
-    async fn get_country_from_ipinfo(&self) -> Result<String, Box<dyn std::error::Error>> {
-        let response = self
-            .client
-            .get("https://ipinfo.io/country")
-            .timeout(Duration::from_secs(5))
-            .send()
-            .await?;
-
-        let country = response.text().await?;
-        let country = country.trim().to_uppercase();
-
-        if country.len() == 2 && country.chars().all(|c| c.is_ascii_alphabetic()) {
-            Ok(country)
-        } else {
-            Err("Invalid country code from ipinfo.io".into())
-        }
-    }
+/// Use `country` instead of detecting it for the rest of the process (`--country`). Must be
+/// called before the first lookup.
+pub(crate) fn set_country(country: String) {
+    let _ = COUNTRY_CODE.set(country);
 }
 
 /// Detect country code once globally without requiring a client instance.
@@ -301,45 +436,12 @@ pub(crate) async fn detect_country_once() -> String {
         return country.clone();
     }
 
-    let client = match ClientBuilder::new().timeout(Duration::from_secs(5)).build() {
-        Ok(c) => c,
-        Err(_) => return "US".to_string(),
+    let country = match proxy::client_builder().build() {
+        Ok(client) => geo::detect_country(&client).await,
+        Err(_) => geo::fallback_country(),
     };
-
-    // Try Cloudflare first
-    if let Ok(response) = client
-        .get("https://cloudflare.com/cdn-cgi/trace")
-        .send()
-        .await
-    {
-        if let Ok(text) = response.text().await {
-            for line in text.lines() {
-                if let Some(country) = line.strip_prefix("loc=") {
-                    let country = country.trim().to_uppercase();
-                    if country.len() == 2 && country.chars().all(|c| c.is_ascii_alphabetic()) {
-                        let _ = COUNTRY_CODE.set(country.clone());
-                        return country;
-                    }
-                }
-            }
-        }
-    }
-
-    // Fallback to ipinfo.io
-    if let Ok(response) = client.get("https://ipinfo.io/country").send().await {
-        if let Ok(text) = response.text().await {
-            let country = text.trim().to_uppercase();
-            if country.len() == 2 && country.chars().all(|c| c.is_ascii_alphabetic()) {
-                let _ = COUNTRY_CODE.set(country.clone());
-                return country;
-            }
-        }
-    }
-
-    // Default fallback
-    let fallback = "US".to_string();
-    let _ = COUNTRY_CODE.set(fallback.clone());
-    fallback
+    let _ = COUNTRY_CODE.set(country.clone());
+    country
 }
 
 #[async_trait::async_trait]
@@ -405,12 +507,14 @@ impl Orchestrator for OrchestratorClient {
         let request_bytes = Self::encode_request(&request);
         let response: GetProofTaskResponse = self.post_request("v3/tasks", request_bytes).await?;
 
-        let task = Task::from(&response);
+        let task = Task::try_from(&response)?;
         let actual_difficulty = task.difficulty;
 
         Ok(ProofTaskResult {
             task,
             actual_difficulty,
+            // Zero can't be honored; treat it like no hint
+            max_concurrent_tasks: response.max_concurrent_tasks.filter(|&max| max > 0),
         })
     }
 
@@ -425,12 +529,8 @@ impl Orchestrator for OrchestratorClient {
         task_type: crate::nexus_orchestrator::TaskType,
         individual_proof_hashes: &[String],
     ) -> Result<(), OrchestratorError> {
-        let (program_memory, total_memory) = get_memory_info();
-        let flops = estimate_peak_gflops(num_provers);
         let (signature, public_key) = self.create_signature(&signing_key, task_id, proof_hash);
-
-        // Detect country for network optimization (privacy-preserving: only country code, no precise location)
-        let location = self.get_country().await;
+        let node_telemetry = self.node_telemetry(num_provers).await;
         // Handle different task types
         let (proof_to_send, proofs_to_send, all_proof_hashes_to_send) =
             OrchestratorClient::select_proof_payload(
@@ -438,6 +538,7 @@ impl Orchestrator for OrchestratorClient {
                 proof,
                 proofs,
                 individual_proof_hashes,
+                self.always_attach_proof,
             );
 
         let request = SubmitProofRequest {
@@ -446,16 +547,11 @@ impl Orchestrator for OrchestratorClient {
             proof_hash: proof_hash.to_string(),
             proof: proof_to_send,
             proofs: proofs_to_send,
-            node_telemetry: Some(crate::nexus_orchestrator::NodeTelemetry {
-                flops_per_sec: Some(flops as i32),
-                memory_used: Some(program_memory),
-                memory_capacity: Some(total_memory),
-                // Country code for network routing optimization (privacy-preserving)
-                location: Some(location),
-            }),
+            node_telemetry: Some(node_telemetry),
             ed25519_public_key: public_key,
             signature,
             all_proof_hashes: all_proof_hashes_to_send,
+            idempotency_key: submission_idempotency_key(task_id, proof_hash),
         };
         let request_bytes = Self::encode_request(&request);
         self.post_request_no_response("v3/tasks/submit", request_bytes)
@@ -556,6 +652,225 @@ mod live_orchestrator_tests {
 mod tests {
     use super::*;
     use crate::nexus_orchestrator::TaskType;
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+    use reqwest::ClientBuilder;
+    use std::io::Write;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn test_append_capped_enforces_limit() {
+        let mut body = Vec::new();
+        assert!(append_capped(&mut body, &[0; 6], 10).is_ok());
+        assert!(append_capped(&mut body, &[0; 4], 10).is_ok());
+        assert!(matches!(
+            append_capped(&mut body, &[0; 1], 10),
+            Err(OrchestratorError::ResponseTooLarge { limit: 10 })
+        ));
+        assert_eq!(body.len(), 10);
+    }
+
+    #[tokio::test]
+    async fn test_gzip_response_decodes() {
+        let expected = RegisterNodeResponse {
+            node_id: "12345".to_string(),
+        };
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&expected.encode_to_vec()).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        // Serve a single gzip-encoded response, as a compressing proxy would
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = [0u8; 1024];
+            let _ = stream.read(&mut request).await.unwrap();
+            let header = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/octet-stream\r\n\
+                 Content-Encoding: gzip\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                compressed.len()
+            );
+            stream.write_all(header.as_bytes()).await.unwrap();
+            stream.write_all(&compressed).await.unwrap();
+        });
+
+        let client =
+            OrchestratorClient::with_http_version(Environment::Production, HttpVersion::Http1);
+        let response = client
+            .client
+            .get(format!("http://{}/v3/nodes", addr))
+            .send()
+            .await
+            .unwrap();
+        let body = read_capped_body(response, client.max_response_bytes)
+            .await
+            .unwrap();
+        let decoded: RegisterNodeResponse = OrchestratorClient::decode_response(&body).unwrap();
+        assert_eq!(decoded, expected);
+    }
+
+    #[tokio::test]
+    async fn test_error_body_is_capped() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = [0u8; 1024];
+            let _ = stream.read(&mut request).await.unwrap();
+            let body = vec![b'x'; 64];
+            let header = format!(
+                "HTTP/1.1 500 Internal Server Error\r\nContent-Length: {}\r\n\
+                 Connection: close\r\n\r\n",
+                body.len()
+            );
+            stream.write_all(header.as_bytes()).await.unwrap();
+            stream.write_all(&body).await.unwrap();
+        });
+
+        let client =
+            OrchestratorClient::with_http_version(Environment::Production, HttpVersion::Http1)
+                .with_max_response_bytes(16);
+        let response = client
+            .client
+            .get(format!("http://{}/v3/nodes", addr))
+            .send()
+            .await
+            .unwrap();
+        match client.handle_response_status(response).await {
+            Err(OrchestratorError::Http {
+                status, message, ..
+            }) => {
+                assert_eq!(status, 500);
+                assert_eq!(
+                    message,
+                    OrchestratorError::ResponseTooLarge { limit: 16 }.to_string()
+                );
+            }
+            other => panic!("expected a capped HTTP error, got {:?}", other.err()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_no_geo_makes_no_lookup() {
+        // Route every request through a local proxy that records connections
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let proxy = reqwest::Proxy::all(format!("http://{}", listener.local_addr().unwrap()));
+        let client = OrchestratorClient {
+            client: ClientBuilder::new().proxy(proxy.unwrap()).build().unwrap(),
+            ..OrchestratorClient::new(Environment::Production)
+        }
+        .with_geo_lookups(false);
+
+        let telemetry = client.node_telemetry(1).await;
+        assert_eq!(telemetry.location, None);
+        assert!(
+            tokio::time::timeout(Duration::from_millis(200), listener.accept())
+                .await
+                .is_err(),
+            "no geo request should be made"
+        );
+    }
+
+    #[test]
+    fn test_build_http_client_for_each_version() {
+        for version in [HttpVersion::Auto, HttpVersion::Http1, HttpVersion::Http2] {
+            assert!(
+                build_http_client(version, Duration::from_secs(10), Duration::from_secs(10))
+                    .is_ok(),
+                "failed to build client for {:?}",
+                version
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_request_timeout_is_configurable() {
+        // A server that accepts connections but never responds
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (_socket, _) = listener.accept().await.unwrap();
+            tokio::time::sleep(Duration::from_secs(5)).await;
+        });
+
+        let client = OrchestratorClient::with_timeouts(
+            Environment::Production,
+            HttpVersion::Http1,
+            Duration::from_secs(1),
+            Duration::from_millis(100),
+        );
+        let started = std::time::Instant::now();
+        let error = client
+            .client
+            .get(format!("http://{}/v3/nodes", addr))
+            .send()
+            .await
+            .unwrap_err();
+        assert!(error.is_timeout());
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+
+    #[test]
+    fn test_idempotency_key_stable_across_retries() {
+        let first = submission_idempotency_key("task-1", "abc123");
+        let retry = submission_idempotency_key("task-1", "abc123");
+        assert_eq!(first, retry);
+        assert_eq!(first.len(), 64);
+
+        assert_ne!(first, submission_idempotency_key("task-1", "abc124"));
+        assert_ne!(first, submission_idempotency_key("task-2", "abc123"));
+        // The separator keeps the two fields from running together
+        assert_ne!(
+            submission_idempotency_key("ab", "c"),
+            submission_idempotency_key("a", "bc")
+        );
+    }
+
+    #[test]
+    fn test_join_url_slashes() {
+        let expected = "https://example.com/v3/tasks";
+        assert_eq!(join_url("https://example.com", "v3/tasks"), expected);
+        assert_eq!(join_url("https://example.com/", "v3/tasks"), expected);
+        assert_eq!(join_url("https://example.com", "/v3/tasks"), expected);
+        assert_eq!(join_url("https://example.com//", "//v3/tasks"), expected);
+    }
+
+    #[test]
+    fn test_join_url_empty_endpoint() {
+        assert_eq!(join_url("https://example.com/", ""), "https://example.com");
+        assert_eq!(join_url("https://example.com", "/"), "https://example.com");
+    }
+
+    #[test]
+    fn test_join_url_query_params() {
+        assert_eq!(
+            join_url("https://example.com/", "v3/tasks?limit=5&offset=10"),
+            "https://example.com/v3/tasks?limit=5&offset=10"
+        );
+        assert_eq!(
+            join_url("https://example.com/api/", "?limit=5"),
+            "https://example.com/api?limit=5"
+        );
+    }
+
+    #[test]
+    fn test_join_url_preserves_percent_encoding() {
+        let wallet = urlencoding::encode("0xabc/def?x").into_owned();
+        assert_eq!(
+            join_url("https://example.com", &format!("v3/users/{}", wallet)),
+            "https://example.com/v3/users/0xabc%2Fdef%3Fx"
+        );
+    }
+
+    #[test]
+    fn test_join_url_absolute_endpoint() {
+        assert_eq!(
+            join_url("https://example.com", "https://other.example.com/v3/nodes"),
+            "https://other.example.com/v3/nodes"
+        );
+    }
 
     #[tokio::test]
     /// select_proof_payload rules: only ProofRequired sets proof/proofs.
@@ -572,6 +887,7 @@ mod tests {
             legacy.clone(),
             proofs_multi.clone(),
             &hashes,
+            false,
         );
         assert!(p.is_empty());
         assert!(ps.is_empty());
@@ -583,6 +899,7 @@ mod tests {
             legacy.clone(),
             proofs_multi.clone(),
             &hashes,
+            false,
         );
         assert!(p.is_empty());
         assert!(ps.is_empty());
@@ -594,6 +911,7 @@ mod tests {
             legacy.clone(),
             proofs_multi.clone(),
             &hashes,
+            false,
         );
         assert!(p.is_empty());
         assert_eq!(ps, proofs_multi);
@@ -605,6 +923,19 @@ mod tests {
             legacy.clone(),
             proofs_single.clone(),
             &hashes,
+            false,
+        );
+        assert_eq!(p, legacy);
+        assert_eq!(ps, proofs_single);
+        assert!(hs.is_empty());
+
+        // PROOF_HASH with always_attach_proof: proofs attached as for PROOF_REQUIRED
+        let (p, ps, hs) = OrchestratorClient::select_proof_payload(
+            TaskType::ProofHash,
+            legacy.clone(),
+            proofs_single.clone(),
+            &hashes,
+            true,
         );
         assert_eq!(p, legacy);
         assert_eq!(ps, proofs_single);
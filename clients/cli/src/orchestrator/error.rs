@@ -30,10 +30,19 @@ pub enum OrchestratorError {
         message: String,
         headers: HashMap<String, String>,
     },
+
+    /// The response body exceeded the configured maximum size
+    #[error("Response body exceeds the {limit}-byte limit")]
+    ResponseTooLarge { limit: usize },
+
+    /// The server returned a task this client can't handle (e.g. an unknown task type)
+    #[error("Unsupported task: {0}")]
+    UnsupportedTask(#[from] crate::task::TaskConversionError),
 }
 
 impl OrchestratorError {
-    pub async fn from_response(response: reqwest::Response) -> OrchestratorError {
+    /// Build an error from a non-success response, reading at most `limit` bytes of its body
+    pub async fn from_response(response: reqwest::Response, limit: usize) -> OrchestratorError {
         let status = response.status().as_u16();
 
         // Capture headers before consuming the response
@@ -44,10 +53,11 @@ impl OrchestratorError {
             }
         }
 
-        let message = response
-            .text()
-            .await
-            .unwrap_or_else(|_| "Failed to read response text".to_string());
+        let message = match super::client::read_capped_body(response, limit).await {
+            Ok(body) => String::from_utf8_lossy(&body).into_owned(),
+            Err(e @ OrchestratorError::ResponseTooLarge { .. }) => e.to_string(),
+            Err(_) => "Failed to read response text".to_string(),
+        };
 
         OrchestratorError::Http {
             status,
@@ -56,6 +66,26 @@ impl OrchestratorError {
         }
     }
 
+    /// Whether the server rejected the request because the resource already exists
+    pub fn is_conflict(&self) -> bool {
+        match self {
+            Self::Http {
+                status, message, ..
+            } => *status == 409 || message.to_lowercase().contains("already exists"),
+            _ => false,
+        }
+    }
+
+    /// Whether the server answered a task request without a task, i.e. there is currently
+    /// no work rather than something going wrong
+    pub fn is_no_tasks_available(&self) -> bool {
+        matches!(
+            self,
+            Self::Http { status: 404, .. }
+                | Self::UnsupportedTask(crate::task::TaskConversionError::MissingTask)
+        )
+    }
+
     /// Get the Retry-After header value in seconds, if present
     pub fn get_retry_after_seconds(&self) -> Option<u32> {
         match self {
@@ -111,6 +141,38 @@ mod tests {
         assert_eq!(error.get_retry_after_seconds(), Some(120));
     }
 
+    #[test]
+    fn test_is_conflict() {
+        let conflict = |status: u16, message: &str| OrchestratorError::Http {
+            status,
+            message: message.to_string(),
+            headers: HashMap::new(),
+        };
+
+        assert!(conflict(409, "Conflict").is_conflict());
+        assert!(conflict(400, "User Already Exists").is_conflict());
+        assert!(!conflict(404, "User not found").is_conflict());
+    }
+
+    #[test]
+    fn test_is_no_tasks_available() {
+        let http = |status: u16| OrchestratorError::Http {
+            status,
+            message: String::new(),
+            headers: HashMap::new(),
+        };
+        assert!(http(404).is_no_tasks_available());
+        assert!(!http(500).is_no_tasks_available());
+        assert!(
+            OrchestratorError::UnsupportedTask(crate::task::TaskConversionError::MissingTask)
+                .is_no_tasks_available()
+        );
+        assert!(
+            !OrchestratorError::UnsupportedTask(crate::task::TaskConversionError::NoInputs)
+                .is_no_tasks_available()
+        );
+    }
+
     #[test]
     fn test_get_retry_after_seconds_missing_header() {
         let error = OrchestratorError::Http {
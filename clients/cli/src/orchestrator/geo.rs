@@ -0,0 +1,425 @@
+//! Country detection providers with a persisted circuit breaker
+//!
+//! Country detection queries public endpoints in order, retrying each a few times with
+//! jittered backoff, and falls back to "US" (or `NEXUS_FALLBACK_COUNTRY`) when none
+//! answers. A provider that fails [`BREAKER_FAILURE_THRESHOLD`] times in a row is skipped
+//! for [`BREAKER_COOLDOWN`], across restarts, so an endpoint that is down network-wide
+//! doesn't add its timeout to every startup. A detected country is saved to
+//! `~/.nexus/country` and reused for [`COUNTRY_CACHE_TTL`], so frequent restarts don't
+//! query the providers each time.
+//!
+//! With `--no-geo`, proof submissions carry no location and no lookup is made for them.
+
+use crate::storage::save_json_best_effort;
+use rand::Rng;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Country code used when no provider answers
+pub const FALLBACK_COUNTRY: &str = "US";
+
+/// Environment variable that overrides [`FALLBACK_COUNTRY`], for users who know their region
+pub const FALLBACK_COUNTRY_ENV: &str = "NEXUS_FALLBACK_COUNTRY";
+
+/// Attempts per provider before moving on to the next. Kept low because every attempt
+/// can take up to the provider's timeout at startup.
+const ATTEMPTS_PER_PROVIDER: u32 = 2;
+
+/// Delay before the first retry of a provider; doubled for each later one
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+
+/// How long a detected country is reused across runs before detecting it again
+const COUNTRY_CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Timeout for a provider without a configured one
+pub const DEFAULT_GEO_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Consecutive failures after which a provider is skipped
+const BREAKER_FAILURE_THRESHOLD: u32 = 3;
+
+/// How long a failing provider is skipped
+const BREAKER_COOLDOWN: Duration = Duration::from_secs(60 * 60);
+
+/// A public endpoint that reports the caller's country
+pub struct GeoProvider {
+    /// Name used in `--geo-timeout` and the breaker state file
+    pub name: &'static str,
+    url: &'static str,
+    /// Extracts the country code from the response body
+    parse: fn(&str) -> Option<String>,
+}
+
+/// Providers in the order they are tried
+pub const GEO_PROVIDERS: [GeoProvider; 2] = [
+    GeoProvider {
+        name: "cloudflare",
+        url: "https://cloudflare.com/cdn-cgi/trace",
+        parse: parse_cloudflare_trace,
+    },
+    GeoProvider {
+        name: "ipinfo",
+        url: "https://ipinfo.io/country",
+        parse: parse_country_code,
+    },
+];
+
+/// Per-provider timeouts set with `--geo-timeout`
+static GEO_TIMEOUTS: OnceLock<HashMap<String, Duration>> = OnceLock::new();
+
+/// Override the request timeout of the named providers. Only the first call takes effect.
+pub fn set_geo_timeouts(timeouts: Vec<(String, Duration)>) {
+    let _ = GEO_TIMEOUTS.set(timeouts.into_iter().collect());
+}
+
+fn timeout_for(provider: &GeoProvider) -> Duration {
+    GEO_TIMEOUTS
+        .get()
+        .and_then(|timeouts| timeouts.get(provider.name).copied())
+        .unwrap_or(DEFAULT_GEO_TIMEOUT)
+}
+
+/// Parse a `--geo-timeout` value of the form `PROVIDER=SECS`, e.g. `ipinfo=2`
+pub fn parse_geo_timeout(value: &str) -> Result<(String, Duration), String> {
+    let (name, secs) = value
+        .split_once('=')
+        .ok_or_else(|| format!("expected PROVIDER=SECS, got '{}'", value))?;
+    let name = name.trim().to_lowercase();
+    if !GEO_PROVIDERS.iter().any(|provider| provider.name == name) {
+        let names: Vec<&str> = GEO_PROVIDERS.iter().map(|provider| provider.name).collect();
+        return Err(format!(
+            "unknown provider '{}' (expected one of: {})",
+            name,
+            names.join(", ")
+        ));
+    }
+    let secs: f64 = secs
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid timeout '{}'", secs))?;
+    if !(secs > 0.0 && secs.is_finite()) {
+        return Err("timeout must be positive".to_string());
+    }
+    Ok((name, Duration::from_secs_f64(secs)))
+}
+
+/// Parse a `--country` value: a two-letter code such as `gb`, uppercased
+pub fn parse_country(value: &str) -> Result<String, String> {
+    parse_country_code(value)
+        .ok_or_else(|| format!("expected a two-letter country code, got '{}'", value))
+}
+
+/// A valid two-letter country code from `text`, uppercased
+fn parse_country_code(text: &str) -> Option<String> {
+    let country = text.trim().to_uppercase();
+    (country.len() == 2 && country.chars().all(|c| c.is_ascii_alphabetic())).then_some(country)
+}
+
+/// The country code from the `loc=` line of a Cloudflare trace
+fn parse_cloudflare_trace(text: &str) -> Option<String> {
+    text.lines()
+        .find_map(|line| line.strip_prefix("loc="))
+        .and_then(parse_country_code)
+}
+
+/// Get the path to the breaker state file, typically ~/.nexus/geo_providers.json.
+fn get_breaker_state_path() -> Option<PathBuf> {
+    Some(home::home_dir()?.join(".nexus").join("geo_providers.json"))
+}
+
+/// Get the path to the detected country cache, typically ~/.nexus/country.
+fn get_country_cache_path() -> Option<PathBuf> {
+    Some(home::home_dir()?.join(".nexus").join("country"))
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+/// A detected country, as saved to disk
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+struct CachedCountry {
+    country: String,
+    /// When the country was detected, in milliseconds since the Unix epoch
+    detected_at_ms: u64,
+}
+
+impl CachedCountry {
+    fn load(path: &Path) -> Option<Self> {
+        serde_json::from_slice(&fs::read(path).ok()?).ok()
+    }
+
+    fn save(&self, path: &Path) {
+        save_json_best_effort(path, self);
+    }
+
+    /// The cached country if it was detected less than [`COUNTRY_CACHE_TTL`] before
+    /// `now_ms` and is still a valid two-letter code
+    fn fresh_country(&self, now_ms: u64) -> Option<String> {
+        let age_ms = now_ms.checked_sub(self.detected_at_ms)?;
+        if age_ms >= COUNTRY_CACHE_TTL.as_millis() as u64 {
+            return None;
+        }
+        parse_country_code(&self.country)
+    }
+}
+
+/// Recent outcomes of a provider
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+struct ProviderHealth {
+    consecutive_failures: u32,
+    /// Skip the provider until this time, in milliseconds since the Unix epoch
+    skip_until_ms: Option<u64>,
+}
+
+/// Health of every provider, as saved to disk
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+struct BreakerState {
+    providers: HashMap<String, ProviderHealth>,
+}
+
+impl BreakerState {
+    /// Load the state, starting fresh if the file is missing or unreadable
+    fn load(path: &Path) -> Self {
+        fs::read(path)
+            .ok()
+            .and_then(|buf| serde_json::from_slice(&buf).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self, path: &Path) {
+        save_json_best_effort(path, self);
+    }
+
+    /// Whether `name` is being skipped at `now_ms`
+    fn is_open(&self, name: &str, now_ms: u64) -> bool {
+        self.providers
+            .get(name)
+            .and_then(|health| health.skip_until_ms)
+            .is_some_and(|until| now_ms < until)
+    }
+
+    fn record_success(&mut self, name: &str) {
+        self.providers.remove(name);
+    }
+
+    /// Count a failure, opening the breaker once the threshold is reached
+    fn record_failure(&mut self, name: &str, now_ms: u64) {
+        let health = self.providers.entry(name.to_string()).or_default();
+        health.consecutive_failures += 1;
+        if health.consecutive_failures >= BREAKER_FAILURE_THRESHOLD {
+            health.skip_until_ms = Some(now_ms + BREAKER_COOLDOWN.as_millis() as u64);
+            // Give the provider one more chance after the cooldown
+            health.consecutive_failures = BREAKER_FAILURE_THRESHOLD - 1;
+        }
+    }
+}
+
+/// The country used when no provider answers: `NEXUS_FALLBACK_COUNTRY` if it holds a
+/// two-letter code, otherwise [`FALLBACK_COUNTRY`]
+pub fn fallback_country() -> String {
+    std::env::var(FALLBACK_COUNTRY_ENV)
+        .ok()
+        .and_then(|value| parse_country_code(&value))
+        .unwrap_or_else(|| FALLBACK_COUNTRY.to_string())
+}
+
+/// Delay before retry number `retry` (starting at 1), scaled by `jitter` in [0.5, 1.5) so
+/// many nodes restarting together don't retry in lockstep
+fn retry_delay(retry: u32, jitter: f64) -> Duration {
+    RETRY_BASE_DELAY.mul_f64(2f64.powi(retry as i32 - 1) * jitter)
+}
+
+/// Query one provider, retrying transient failures with jittered backoff
+async fn query_with_retry(client: &Client, provider: &GeoProvider) -> Option<String> {
+    for attempt in 1..=ATTEMPTS_PER_PROVIDER {
+        if let Some(country) = query(client, provider).await {
+            return Some(country);
+        }
+        if attempt < ATTEMPTS_PER_PROVIDER {
+            let jitter = rand::thread_rng().gen_range(0.5..1.5);
+            tokio::time::sleep(retry_delay(attempt, jitter)).await;
+        }
+    }
+    None
+}
+
+/// Query one provider for the country code
+async fn query(client: &Client, provider: &GeoProvider) -> Option<String> {
+    let response = client
+        .get(provider.url)
+        .timeout(timeout_for(provider))
+        .send()
+        .await
+        .ok()?;
+    let text = response.text().await.ok()?;
+    (provider.parse)(&text)
+}
+
+/// Detect the country code, reusing a recent detection from an earlier run if there is one.
+/// Otherwise tries each provider whose breaker is closed and falls back to
+/// [`fallback_country`]. All attempts at a provider count as one breaker failure.
+pub async fn detect_country(client: &Client) -> String {
+    let cache_path = get_country_cache_path();
+    if let Some(country) = cache_path
+        .as_deref()
+        .and_then(CachedCountry::load)
+        .and_then(|cached| cached.fresh_country(now_ms()))
+    {
+        return country;
+    }
+
+    let state_path = get_breaker_state_path();
+    let mut state = state_path
+        .as_deref()
+        .map(BreakerState::load)
+        .unwrap_or_default();
+
+    let mut detected = None;
+    for provider in &GEO_PROVIDERS {
+        if state.is_open(provider.name, now_ms()) {
+            continue;
+        }
+        match query_with_retry(client, provider).await {
+            Some(country) => {
+                state.record_success(provider.name);
+                detected = Some(country);
+                break;
+            }
+            None => state.record_failure(provider.name, now_ms()),
+        }
+    }
+
+    if let Some(path) = &state_path {
+        state.save(path);
+    }
+    // Only cache real detections, so the next run tries the providers again after a fallback
+    if let (Some(country), Some(path)) = (&detected, &cache_path) {
+        CachedCountry {
+            country: country.clone(),
+            detected_at_ms: now_ms(),
+        }
+        .save(path);
+    }
+    detected.unwrap_or_else(fallback_country)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_providers() {
+        assert_eq!(
+            parse_cloudflare_trace("fl=1\nloc=gb\nip=x"),
+            Some("GB".to_string())
+        );
+        assert_eq!(parse_cloudflare_trace("fl=1\n"), None);
+        assert_eq!(parse_country_code(" ca\n"), Some("CA".to_string()));
+        assert_eq!(parse_country_code("<html>"), None);
+    }
+
+    #[test]
+    fn test_parse_country() {
+        assert_eq!(parse_country("de").unwrap(), "DE");
+        assert!(parse_country("DEU").is_err());
+        assert!(parse_country("1a").is_err());
+    }
+
+    #[test]
+    fn test_retry_delay_backs_off_with_jitter() {
+        assert_eq!(retry_delay(1, 1.0), RETRY_BASE_DELAY);
+        assert_eq!(retry_delay(2, 1.0), RETRY_BASE_DELAY * 2);
+        assert_eq!(retry_delay(1, 0.5), RETRY_BASE_DELAY / 2);
+        assert!(retry_delay(2, 1.49) < RETRY_BASE_DELAY * 3);
+    }
+
+    #[test]
+    fn test_breaker_opens_after_repeated_failures() {
+        let mut state = BreakerState::default();
+        for _ in 0..BREAKER_FAILURE_THRESHOLD - 1 {
+            state.record_failure("ipinfo", 0);
+            assert!(!state.is_open("ipinfo", 0));
+        }
+        state.record_failure("ipinfo", 0);
+        assert!(state.is_open("ipinfo", 1));
+        assert!(!state.is_open("cloudflare", 1));
+
+        // Closed again after the cooldown; one more failure reopens it
+        let after = BREAKER_COOLDOWN.as_millis() as u64;
+        assert!(!state.is_open("ipinfo", after));
+        state.record_failure("ipinfo", after);
+        assert!(state.is_open("ipinfo", after + 1));
+
+        state.record_success("ipinfo");
+        assert!(!state.is_open("ipinfo", after + 1));
+    }
+
+    #[test]
+    fn test_breaker_state_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("geo_providers.json");
+        let mut state = BreakerState::default();
+        state.record_failure("cloudflare", 10);
+        state.save(&path);
+        assert_eq!(BreakerState::load(&path), state);
+        assert_eq!(
+            BreakerState::load(&dir.path().join("missing.json")),
+            BreakerState::default()
+        );
+    }
+
+    #[test]
+    fn test_cached_country_expires_and_is_validated() {
+        let cached = CachedCountry {
+            country: "gb".to_string(),
+            detected_at_ms: 1_000,
+        };
+        assert_eq!(cached.fresh_country(1_000), Some("GB".to_string()));
+        let ttl_ms = COUNTRY_CACHE_TTL.as_millis() as u64;
+        assert_eq!(cached.fresh_country(1_000 + ttl_ms), None);
+        // A timestamp in the future (clock moved back) isn't trusted
+        assert_eq!(cached.fresh_country(999), None);
+
+        let corrupt = CachedCountry {
+            country: "G1".to_string(),
+            detected_at_ms: 1_000,
+        };
+        assert_eq!(corrupt.fresh_country(1_000), None);
+    }
+
+    #[test]
+    fn test_cached_country_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("country");
+        let cached = CachedCountry {
+            country: "CA".to_string(),
+            detected_at_ms: 42,
+        };
+        cached.save(&path);
+        assert_eq!(CachedCountry::load(&path), Some(cached));
+        assert_eq!(CachedCountry::load(&dir.path().join("missing")), None);
+    }
+
+    #[test]
+    fn test_parse_geo_timeout() {
+        assert_eq!(
+            parse_geo_timeout("ipinfo=2").unwrap(),
+            ("ipinfo".to_string(), Duration::from_secs(2))
+        );
+        assert_eq!(
+            parse_geo_timeout("Cloudflare=0.5").unwrap().1,
+            Duration::from_millis(500)
+        );
+        assert!(parse_geo_timeout("ipinfo").is_err());
+        assert!(parse_geo_timeout("unknown=1").is_err());
+        assert!(parse_geo_timeout("ipinfo=0").is_err());
+    }
+}
@@ -3,7 +3,8 @@ use crate::orchestrator::error::OrchestratorError;
 use ed25519_dalek::{SigningKey, VerifyingKey};
 
 pub(crate) mod client;
-pub use client::OrchestratorClient;
+pub mod geo;
+pub use client::{HttpVersion, OrchestratorClient};
 pub mod error;
 
 #[cfg(test)]
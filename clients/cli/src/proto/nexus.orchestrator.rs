@@ -101,6 +101,10 @@ pub struct GetProofTaskResponse {
     pub task_id: ::prost::alloc::string::String,
     #[prost(message, optional, tag = "4")]
     pub task: ::core::option::Option<Task>,
+    /// Most tasks the node should have in flight at once. Unset when the server
+    /// doesn't limit it.
+    #[prost(uint32, optional, tag = "5")]
+    pub max_concurrent_tasks: ::core::option::Option<u32>,
 }
 /// Submit the result of a prover task.
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -138,6 +142,10 @@ pub struct SubmitProofRequest {
     /// To be sent on PROOF_REQUIRED tasks, empty on other task types.
     #[prost(bytes = "vec", repeated, tag = "10")]
     pub proofs: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+    /// Client-generated key, derived from task_id and proof_hash, that stays the
+    /// same across retries of a submission so the server can deduplicate them.
+    #[prost(string, tag = "11")]
+    pub idempotency_key: ::prost::alloc::string::String,
 }
 /// Performance stats of a node.
 #[derive(Clone, PartialEq, ::prost::Message)]
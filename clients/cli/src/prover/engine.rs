@@ -2,6 +2,8 @@
 
 use crate::prover::verifier;
 
+use super::envelope::SubprocessResponse;
+use super::program::{ProgramDefinition, ProgramRegistry};
 use super::types::ProverError;
 use crate::analytics::track_likely_oom_error;
 use crate::environment::Environment;
@@ -12,27 +14,59 @@ use nexus_sdk::{
 };
 use postcard::from_bytes;
 use serde_json;
+use sha3::{Digest, Sha3_256};
 use std::env;
 use std::process::Stdio;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Whether the subprocess fallback has already been reported
+static SUBPROCESS_FALLBACK_TRIGGERED: AtomicBool = AtomicBool::new(false);
+
+/// One-time notice describing why subprocess proving was abandoned
+static SUBPROCESS_FALLBACK_NOTICE: Mutex<Option<String>> = Mutex::new(None);
+
+/// Check that `elf` hashes to `expected` (hex SHA3-256)
+fn check_program_hash(elf: &[u8], expected: &str) -> Result<(), ProverError> {
+    let actual = format!("{:x}", Sha3_256::digest(elf));
+    if actual != expected {
+        return Err(ProverError::ProgramMismatch {
+            expected: expected.to_string(),
+            actual,
+        });
+    }
+    Ok(())
+}
 
 /// Core proving engine for ZK proof generation
 pub struct ProvingEngine;
 
 impl ProvingEngine {
-    /// Create a Stwo prover instance for the fibonacci program
-    pub fn create_fib_prover() -> Result<Stwo<Local>, ProverError> {
-        let elf_bytes = include_bytes!("../../assets/fib_input_initial");
-        Stwo::<Local>::new_from_bytes(elf_bytes).map_err(|e| {
+    /// Check every embedded guest program against the hash pinned at build time, so a
+    /// corrupted binary or a build with the wrong program never proves anything
+    pub fn verify_embedded_program() -> Result<(), ProverError> {
+        ProgramRegistry::PROGRAMS
+            .iter()
+            .try_for_each(|program| check_program_hash(program.elf, program.expected_sha3_256))
+    }
+
+    /// Create a Stwo prover instance for `program`
+    pub fn create_prover(program: &ProgramDefinition) -> Result<Stwo<Local>, ProverError> {
+        Stwo::<Local>::new_from_bytes(program.elf).map_err(|e| {
             ProverError::Stwo(format!(
-                "Failed to load fib_input_initial guest program: {}",
-                e
+                "Failed to load {} guest program: {}",
+                program.id, e
             ))
         })
     }
 
-    /// Subprocess entrypoint: generate proof without verification
-    pub fn prove_fib_subprocess(inputs: &(u32, u32, u32)) -> Result<Proof, ProverError> {
-        let prover = Self::create_fib_prover()?;
+    /// Subprocess entrypoint: generate proof without verification. Returns the proof and
+    /// the exit code of the run it proves, which verification expects.
+    pub fn prove_program_subprocess(
+        program: &ProgramDefinition,
+        inputs: &(u32, u32, u32),
+    ) -> Result<(Proof, u32), ProverError> {
+        let prover = Self::create_prover(program)?;
         let (view, proof) = prover
             .prove_with_input::<(), (u32, u32, u32)>(&(), inputs)
             .map_err(|e| {
@@ -42,22 +76,84 @@ impl ProvingEngine {
                 ))
             })?;
         // Check exit code in subprocess
-        verifier::ProofVerifier::check_exit_code(&view)?;
+        let exit_code = verifier::ProofVerifier::check_exit_code(&view, program)?;
+
+        Ok((proof, exit_code))
+    }
+
+    /// Take the one-time notice emitted when subprocess proving falls back to
+    /// in-process proving. Returns `Some` at most once per process.
+    pub fn take_subprocess_fallback_notice() -> Option<String> {
+        SUBPROCESS_FALLBACK_NOTICE
+            .lock()
+            .ok()
+            .and_then(|mut notice| notice.take())
+    }
+
+    /// Generate and verify a proof in the current process
+    pub async fn prove_and_validate_in_process(
+        program: &'static ProgramDefinition,
+        inputs: &(u32, u32, u32),
+    ) -> Result<Proof, ProverError> {
+        let owned_inputs = *inputs;
+        let (proof, exit_code) = tokio::task::spawn_blocking(move || {
+            Self::prove_program_subprocess(program, &owned_inputs)
+        })
+        .await
+        .map_err(ProverError::JoinError)??;
+
+        Self::verify(program, &proof, inputs, exit_code)?;
 
         Ok(proof)
     }
 
-    /// Generate proof for given inputs using the fibonacci program in a subprocess
-    pub async fn prove_and_validate(
+    /// Verify a proof of `program` for the given inputs and exit code. CPU-heavy; run it
+    /// on a blocking thread.
+    pub fn verify(
+        program: &ProgramDefinition,
+        proof: &Proof,
+        inputs: &(u32, u32, u32),
+        exit_code: u32,
+    ) -> Result<(), ProverError> {
+        let verify_prover = Self::create_prover(program)?;
+        verifier::ProofVerifier::verify_proof(proof, inputs, exit_code, &verify_prover)
+    }
+
+    /// Generate proof for given inputs using `program` in a subprocess, without
+    /// verifying it. Returns the proof and the exit code of the run it proves.
+    pub async fn prove(
+        program: &'static ProgramDefinition,
         inputs: &(u32, u32, u32),
         task: &Task,
         environment: &Environment,
         client_id: &str,
-    ) -> Result<Proof, ProverError> {
-        // Spawn a subprocess for proof generation to isolate memory usage
-        let exe_path = env::current_exe()?;
+    ) -> Result<(Proof, u32), ProverError> {
+        // Spawn a subprocess for proof generation to isolate memory usage. If the
+        // executable can't be located (e.g. deleted or renamed while running),
+        // prove in-process instead of failing every task.
+        let exe_path = match env::current_exe() {
+            Ok(path) => path,
+            Err(e) => {
+                if !SUBPROCESS_FALLBACK_TRIGGERED.swap(true, Ordering::SeqCst) {
+                    if let Ok(mut notice) = SUBPROCESS_FALLBACK_NOTICE.lock() {
+                        *notice = Some(format!(
+                            "Cannot locate the nexus-network executable ({}); proving in-process without memory isolation",
+                            e
+                        ));
+                    }
+                }
+                let owned_inputs = *inputs;
+                return tokio::task::spawn_blocking(move || {
+                    Self::prove_program_subprocess(program, &owned_inputs)
+                })
+                .await
+                .map_err(ProverError::JoinError)?;
+            }
+        };
         let mut cmd = tokio::process::Command::new(exe_path);
         cmd.arg("prove-fib-subprocess")
+            .arg("--program")
+            .arg(program.id)
             .arg("--inputs")
             .arg(serde_json::to_string(inputs)?)
             .stdout(Stdio::piped())
@@ -74,14 +170,21 @@ impl ProvingEngine {
                         environment.clone(),
                         client_id.to_string(),
                     ));
+                    return Err(ProverError::OutOfMemory(format!(
+                        "Prover subprocess was killed with status: {}",
+                        output.status
+                    )));
                 }
 
                 if code == crate::consts::cli_consts::SUBPROCESS_INTERNAL_ERROR_CODE {
-                    // error happened inside the subprocess, and so we know that it may be useful information to the user
-                    return Err(ProverError::Subprocess(format!(
-                        "Error while proving within subprocess, captured error: [{}]",
-                        &String::from_utf8_lossy(&output.stderr)
-                    )));
+                    // error happened inside the subprocess, which reports what it was on stdout
+                    return Err(match from_bytes::<SubprocessResponse>(&output.stdout) {
+                        Ok(SubprocessResponse::Error(error)) => error.into(),
+                        _ => ProverError::Subprocess(
+                            "Error while proving within subprocess, no details reported"
+                                .to_string(),
+                        ),
+                    });
                 }
             }
 
@@ -92,12 +195,35 @@ impl ProvingEngine {
         }
 
         // Deserialize proof from subprocess stdout
-        let proof: Proof = from_bytes(&output.stdout)?;
+        match from_bytes::<SubprocessResponse>(&output.stdout)? {
+            SubprocessResponse::Proof { proof, exit_code } => Ok((proof, exit_code)),
+            SubprocessResponse::Error(error) => Err(error.into()),
+        }
+    }
+}
 
-        // Verify proof in main process
-        let verify_prover = Self::create_fib_prover()?;
-        verifier::ProofVerifier::verify_proof(&proof, inputs, &verify_prover)?;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prover::program::FIB_INPUT_INITIAL;
 
-        Ok(proof)
+    #[test]
+    fn test_embedded_program_matches_pinned_hash() {
+        assert!(ProvingEngine::verify_embedded_program().is_ok());
+    }
+
+    #[test]
+    fn test_modified_program_is_rejected() {
+        let program = FIB_INPUT_INITIAL;
+        let mut elf = program.elf.to_vec();
+        elf[0] ^= 0xff;
+
+        match check_program_hash(&elf, program.expected_sha3_256) {
+            Err(ProverError::ProgramMismatch { expected, actual }) => {
+                assert_eq!(expected, program.expected_sha3_256);
+                assert_ne!(actual, expected);
+            }
+            _ => panic!("expected a program mismatch"),
+        }
     }
 }
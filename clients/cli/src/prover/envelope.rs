@@ -0,0 +1,113 @@
+//! Structured output of the proving subprocess
+//!
+//! The subprocess writes a postcard-encoded [`SubprocessResponse`] to stdout, so the
+//! parent can rebuild a typed [`ProverError`] rather than inferring one from the exit
+//! code. Failures the subprocess can't report itself (e.g. being killed for running
+//! out of memory) are still detected from the exit status.
+
+use super::types::ProverError;
+use nexus_sdk::stwo::seq::Proof;
+use serde::{Deserialize, Serialize};
+
+/// What the proving subprocess writes to stdout
+#[derive(Serialize, Deserialize)]
+pub enum SubprocessResponse {
+    /// The proof and the exit code of the run it proves
+    Proof {
+        proof: Proof,
+        exit_code: u32,
+    },
+    Error(SubprocessError),
+}
+
+/// Kind of failure inside the proving subprocess
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SubprocessErrorKind {
+    Stwo,
+    GuestProgram,
+    MalformedTask,
+    Serialization,
+    Io,
+    Other,
+}
+
+/// An error raised inside the proving subprocess
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubprocessError {
+    pub kind: SubprocessErrorKind,
+    pub message: String,
+}
+
+impl From<&ProverError> for SubprocessError {
+    fn from(error: &ProverError) -> Self {
+        let kind = match error {
+            ProverError::Stwo(_) => SubprocessErrorKind::Stwo,
+            ProverError::GuestProgram(_) => SubprocessErrorKind::GuestProgram,
+            ProverError::MalformedTask(_) => SubprocessErrorKind::MalformedTask,
+            ProverError::Serialization(_) | ProverError::SerdeJson(_) => {
+                SubprocessErrorKind::Serialization
+            }
+            ProverError::Io(_) => SubprocessErrorKind::Io,
+            _ => SubprocessErrorKind::Other,
+        };
+        // Keep only the inner message for variants that are rebuilt from it
+        let message = match error {
+            ProverError::Stwo(message)
+            | ProverError::GuestProgram(message)
+            | ProverError::MalformedTask(message) => message.clone(),
+            _ => error.to_string(),
+        };
+        Self { kind, message }
+    }
+}
+
+impl From<SubprocessError> for ProverError {
+    fn from(error: SubprocessError) -> Self {
+        match error.kind {
+            SubprocessErrorKind::Stwo => ProverError::Stwo(error.message),
+            SubprocessErrorKind::GuestProgram => ProverError::GuestProgram(error.message),
+            SubprocessErrorKind::MalformedTask => ProverError::MalformedTask(error.message),
+            SubprocessErrorKind::Io => ProverError::Io(std::io::Error::other(error.message)),
+            SubprocessErrorKind::Serialization | SubprocessErrorKind::Other => {
+                ProverError::Subprocess(format!(
+                    "Error while proving within subprocess: {}",
+                    error.message
+                ))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_round_trip_keeps_kind() {
+        let original = ProverError::GuestProgram("exit code 1".to_string());
+        let bytes =
+            postcard::to_allocvec(&SubprocessResponse::Error(SubprocessError::from(&original)))
+                .unwrap();
+
+        let Ok(SubprocessResponse::Error(error)) = postcard::from_bytes(&bytes) else {
+            panic!("expected an error response");
+        };
+        assert_eq!(error.kind, SubprocessErrorKind::GuestProgram);
+        assert!(matches!(
+            ProverError::from(error),
+            ProverError::GuestProgram(message) if message == "exit code 1"
+        ));
+    }
+
+    #[test]
+    fn test_unstructured_errors_become_subprocess_errors() {
+        let error = SubprocessError {
+            kind: SubprocessErrorKind::Serialization,
+            message: "unexpected end of input".to_string(),
+        };
+        assert!(matches!(
+            ProverError::from(error),
+            ProverError::Subprocess(_)
+        ));
+    }
+}
@@ -0,0 +1,434 @@
+//! On-disk proof serialization formats
+//!
+//! The orchestrator wire format is always postcard. These formats only apply to proofs
+//! written to disk, so saved proofs can be consumed by external tooling.
+//!
+//! Each saved proof file is accompanied by a `<task_id>.json` [`SavedProofMetadata`] file
+//! with what is needed to submit the proof later (see the `submit-saved` command).
+
+use super::types::{ProverError, ProverResult};
+use crate::nexus_orchestrator::{TaskDifficulty, TaskType};
+use crate::task::Task;
+use base64::prelude::{BASE64_STANDARD, Engine as _};
+use ed25519_dalek::SigningKey;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Get the directory proofs are kept in when their submission fails and `--save-proofs`
+/// isn't set, typically ~/.nexus/unsubmitted_proofs.
+pub fn get_unsubmitted_proofs_dir() -> Result<PathBuf, std::io::Error> {
+    let home_path = home::home_dir().ok_or(std::io::Error::new(
+        std::io::ErrorKind::NotFound,
+        "Home directory not found",
+    ))?;
+    Ok(home_path.join(".nexus").join("unsubmitted_proofs"))
+}
+
+/// Save the proofs of `result` in `format` to `dir`, with the metadata `submit-saved` needs.
+/// `signing_key` is the session key the task was fetched with.
+pub fn save_proof_result(
+    dir: &Path,
+    task: &Task,
+    format: ProofFormat,
+    result: &ProverResult,
+    signing_key: Option<&SigningKey>,
+) -> Result<(), ProverError> {
+    format.save(dir, &task.task_id, &result.proofs)?;
+    SavedProofMetadata::new(
+        task,
+        format,
+        &result.combined_hash,
+        &result.individual_proof_hashes,
+        signing_key,
+    )
+    .save(dir)?;
+    Ok(())
+}
+
+/// Write `contents` to `path`. On Unix a new file is readable only by its owner, since
+/// metadata files hold a signing key.
+fn write_private(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    std::io::Write::write_all(&mut options.open(path)?, contents)
+}
+
+/// `task_id` if it is safe to use as a file name: non-empty, with only ASCII letters,
+/// digits, `_` and `-`. Task IDs come from the orchestrator, so anything else (`../`, an
+/// absolute path) could write outside the proof directory.
+pub fn task_file_stem(task_id: &str) -> Result<&str, ProverError> {
+    let safe = !task_id.is_empty()
+        && task_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if safe {
+        Ok(task_id)
+    } else {
+        Err(ProverError::MalformedTask(format!(
+            "Task ID {:?} can't be used as a file name",
+            task_id
+        )))
+    }
+}
+
+/// Size of the length prefix preceding each proof in `LengthPrefixed` files
+const LENGTH_PREFIX_BYTES: usize = 8;
+
+/// Serialization format for proofs saved to disk
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum ProofFormat {
+    /// A postcard-encoded list of proofs, matching the wire format
+    #[default]
+    Postcard,
+    /// Each proof as a little-endian u64 byte length followed by its postcard bytes
+    LengthPrefixed,
+}
+
+impl ProofFormat {
+    /// File extension used for proofs in this format
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ProofFormat::Postcard => "postcard",
+            ProofFormat::LengthPrefixed => "lp",
+        }
+    }
+
+    /// Format whose files use `extension`
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        [ProofFormat::Postcard, ProofFormat::LengthPrefixed]
+            .into_iter()
+            .find(|format| format.extension() == extension)
+    }
+
+    /// Encode a list of proofs
+    pub fn encode<T: Serialize>(&self, proofs: &[T]) -> Result<Vec<u8>, ProverError> {
+        match self {
+            ProofFormat::Postcard => Ok(postcard::to_allocvec(proofs)?),
+            ProofFormat::LengthPrefixed => {
+                let mut out = Vec::new();
+                for proof in proofs {
+                    let bytes = postcard::to_allocvec(proof)?;
+                    out.extend_from_slice(&(bytes.len() as u64).to_le_bytes());
+                    out.extend_from_slice(&bytes);
+                }
+                Ok(out)
+            }
+        }
+    }
+
+    /// Decode a list of proofs previously written with `encode`
+    pub fn decode<T: DeserializeOwned>(&self, bytes: &[u8]) -> Result<Vec<T>, ProverError> {
+        match self {
+            ProofFormat::Postcard => Ok(postcard::from_bytes(bytes)?),
+            ProofFormat::LengthPrefixed => {
+                let mut proofs = Vec::new();
+                let mut rest = bytes;
+                while !rest.is_empty() {
+                    let (prefix, tail) = rest
+                        .split_at_checked(LENGTH_PREFIX_BYTES)
+                        .ok_or_else(|| truncated("length prefix"))?;
+                    let mut len = [0u8; LENGTH_PREFIX_BYTES];
+                    len.copy_from_slice(prefix);
+                    let len =
+                        usize::try_from(u64::from_le_bytes(len)).map_err(|_| truncated("proof"))?;
+                    let (proof, tail) = tail
+                        .split_at_checked(len)
+                        .ok_or_else(|| truncated("proof"))?;
+                    proofs.push(postcard::from_bytes(proof)?);
+                    rest = tail;
+                }
+                Ok(proofs)
+            }
+        }
+    }
+
+    /// Write the proofs for a task to `dir/<task_id>.<extension>`. Fails for task IDs
+    /// that aren't safe file names (see [`task_file_stem`]).
+    pub fn save<T: Serialize>(
+        &self,
+        dir: &Path,
+        task_id: &str,
+        proofs: &[T],
+    ) -> Result<PathBuf, ProverError> {
+        let path = dir.join(format!("{}.{}", task_file_stem(task_id)?, self.extension()));
+        std::fs::create_dir_all(dir)?;
+        std::fs::write(&path, self.encode(proofs)?)?;
+        Ok(path)
+    }
+}
+
+/// Task details saved next to a proof file, so the proof can be submitted later
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SavedProofMetadata {
+    pub task_id: String,
+    pub program_id: String,
+    /// Task type, e.g. "PROOF_HASH"
+    pub task_type: String,
+    /// Task difficulty, e.g. "LARGE"
+    pub difficulty: String,
+    /// Extension of the proof file, which identifies its [`ProofFormat`]
+    pub format: String,
+    pub combined_hash: String,
+    pub individual_proof_hashes: Vec<String>,
+    /// Whether the orchestrator has accepted the proof
+    #[serde(default)]
+    pub submitted: bool,
+    /// Base64 secret of the session key the task was fetched with. The orchestrator only
+    /// accepts the proof signed with that key, so `submit-saved` signs with it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signing_key: Option<String>,
+}
+
+impl SavedProofMetadata {
+    pub fn new(
+        task: &Task,
+        format: ProofFormat,
+        combined_hash: &str,
+        individual_proof_hashes: &[String],
+        signing_key: Option<&SigningKey>,
+    ) -> Self {
+        Self {
+            task_id: task.task_id.clone(),
+            program_id: task.program_id.clone(),
+            task_type: task.task_type.as_str_name().to_string(),
+            difficulty: task.difficulty.as_str_name().to_string(),
+            format: format.extension().to_string(),
+            combined_hash: combined_hash.to_string(),
+            individual_proof_hashes: individual_proof_hashes.to_vec(),
+            submitted: false,
+            signing_key: signing_key.map(|key| BASE64_STANDARD.encode(key.to_bytes())),
+        }
+    }
+
+    /// Path of the metadata file for `task_id` in `dir`
+    pub fn path(dir: &Path, task_id: &str) -> Result<PathBuf, ProverError> {
+        Ok(dir.join(format!("{}.json", task_file_stem(task_id)?)))
+    }
+
+    /// Path of the proof file described by this metadata
+    pub fn proof_path(&self, dir: &Path) -> Result<PathBuf, ProverError> {
+        Ok(dir.join(format!(
+            "{}.{}",
+            task_file_stem(&self.task_id)?,
+            self.format
+        )))
+    }
+
+    /// The session key the task was fetched with, to sign its submission
+    pub fn signing_key(&self) -> Result<SigningKey, ProverError> {
+        let malformed = || {
+            ProverError::MalformedTask(format!(
+                "No valid signing key saved for task {}; the orchestrator would reject the proof",
+                self.task_id
+            ))
+        };
+        let bytes = BASE64_STANDARD
+            .decode(self.signing_key.as_deref().ok_or_else(malformed)?)
+            .map_err(|_| malformed())?;
+        let secret: [u8; 32] = bytes.try_into().map_err(|_| malformed())?;
+        Ok(SigningKey::from_bytes(&secret))
+    }
+
+    /// Write the metadata to `dir/<task_id>.json`
+    pub fn save(&self, dir: &Path) -> Result<PathBuf, ProverError> {
+        let path = Self::path(dir, &self.task_id)?;
+        std::fs::create_dir_all(dir)?;
+        write_private(&path, &serde_json::to_vec_pretty(self)?)?;
+        Ok(path)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, ProverError> {
+        Ok(serde_json::from_slice(&std::fs::read(path)?)?)
+    }
+
+    /// Mark the saved proof for `task_id` in `dir` as accepted, so it isn't submitted again
+    pub fn mark_submitted(dir: &Path, task_id: &str) -> Result<(), ProverError> {
+        let mut metadata = Self::load(&Self::path(dir, task_id)?)?;
+        metadata.submitted = true;
+        metadata.save(dir).map(|_| ())
+    }
+
+    /// Metadata files in `dir`, sorted by path
+    pub fn list(dir: &Path) -> Result<Vec<PathBuf>, ProverError> {
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .collect();
+        paths.sort();
+        Ok(paths)
+    }
+
+    /// Rebuild the task the proof was generated for. Public inputs aren't saved, so the
+    /// task carries none; they aren't needed to submit.
+    pub fn task(&self) -> Result<Task, ProverError> {
+        let task_type = TaskType::from_str_name(&self.task_type).ok_or_else(|| {
+            ProverError::MalformedTask(format!("Unknown task type {}", self.task_type))
+        })?;
+        let difficulty = TaskDifficulty::from_str_name(&self.difficulty).ok_or_else(|| {
+            ProverError::MalformedTask(format!("Unknown difficulty {}", self.difficulty))
+        })?;
+        Ok(Task::new(
+            self.task_id.clone(),
+            self.program_id.clone(),
+            Vec::new(),
+            task_type,
+            difficulty,
+        ))
+    }
+}
+
+fn truncated(what: &str) -> ProverError {
+    ProverError::Io(std::io::Error::new(
+        std::io::ErrorKind::UnexpectedEof,
+        format!("Truncated {} in length-prefixed proof file", what),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_all_formats() {
+        let proofs: Vec<(u32, String)> = vec![(1, "a".to_string()), (2, "bb".to_string())];
+        for format in [ProofFormat::Postcard, ProofFormat::LengthPrefixed] {
+            let bytes = format.encode(&proofs).unwrap();
+            let decoded: Vec<(u32, String)> = format.decode(&bytes).unwrap();
+            assert_eq!(decoded, proofs, "{:?}", format);
+        }
+    }
+
+    #[test]
+    fn test_length_prefixed_layout() {
+        let bytes = ProofFormat::LengthPrefixed.encode(&[7u8]).unwrap();
+        let inner = postcard::to_allocvec(&7u8).unwrap();
+        assert_eq!(
+            &bytes[..LENGTH_PREFIX_BYTES],
+            &(inner.len() as u64).to_le_bytes()
+        );
+        assert_eq!(&bytes[LENGTH_PREFIX_BYTES..], inner.as_slice());
+    }
+
+    #[test]
+    fn test_length_prefixed_rejects_truncated_input() {
+        let bytes = ProofFormat::LengthPrefixed.encode(&[7u32, 8u32]).unwrap();
+        let result: Result<Vec<u32>, _> =
+            ProofFormat::LengthPrefixed.decode(&bytes[..bytes.len() - 1]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_save_writes_task_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = ProofFormat::LengthPrefixed
+            .save(dir.path(), "task-1", &[1u32])
+            .unwrap();
+        assert_eq!(path, dir.path().join("task-1.lp"));
+        let decoded: Vec<u32> = ProofFormat::LengthPrefixed
+            .decode(&std::fs::read(path).unwrap())
+            .unwrap();
+        assert_eq!(decoded, vec![1]);
+    }
+
+    #[test]
+    fn test_unsafe_task_ids_are_not_saved() {
+        let dir = tempfile::tempdir().unwrap();
+        for task_id in ["", "../escape", "/tmp/abs", "a/b", "a.b", "..", "task\\x"] {
+            assert!(task_file_stem(task_id).is_err(), "{:?}", task_id);
+            assert!(
+                ProofFormat::Postcard
+                    .save(dir.path(), task_id, &[1u8])
+                    .is_err()
+            );
+        }
+        assert_eq!(task_file_stem("Task_1-a").unwrap(), "Task_1-a");
+        assert!(
+            ProofFormat::Postcard
+                .save(dir.path(), "Task_1-a", &[1u8])
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_metadata_round_trip_and_mark_submitted() {
+        let dir = tempfile::tempdir().unwrap();
+        let task = Task::new(
+            "task-1".to_string(),
+            "fib_input_initial".to_string(),
+            vec![1, 2, 3],
+            TaskType::AllProofHashes,
+            TaskDifficulty::Large,
+        );
+        let metadata = SavedProofMetadata::new(
+            &task,
+            ProofFormat::LengthPrefixed,
+            "abc",
+            &["a".to_string(), "b".to_string()],
+            None,
+        );
+        let path = metadata.save(dir.path()).unwrap();
+        assert_eq!(
+            SavedProofMetadata::list(dir.path()).unwrap(),
+            vec![path.clone()]
+        );
+        assert_eq!(
+            metadata.proof_path(dir.path()).unwrap(),
+            dir.path().join("task-1.lp")
+        );
+
+        let loaded = SavedProofMetadata::load(&path).unwrap();
+        assert_eq!(loaded, metadata);
+        let rebuilt = loaded.task().unwrap();
+        assert_eq!(rebuilt.task_type, TaskType::AllProofHashes);
+        assert_eq!(rebuilt.difficulty, TaskDifficulty::Large);
+        // Without the fetch-time key the proof can't be submitted
+        assert!(loaded.signing_key().is_err());
+
+        SavedProofMetadata::mark_submitted(dir.path(), "task-1").unwrap();
+        assert!(SavedProofMetadata::load(&path).unwrap().submitted);
+    }
+
+    #[test]
+    fn test_save_proof_result_is_listed_for_submit_saved() {
+        let dir = tempfile::tempdir().unwrap();
+        let task = Task::new(
+            "task-2".to_string(),
+            "fib_input_initial".to_string(),
+            vec![1, 2, 3],
+            TaskType::ProofHash,
+            TaskDifficulty::Small,
+        );
+        let result = ProverResult {
+            proofs: Vec::new(),
+            combined_hash: "abc".to_string(),
+            individual_proof_hashes: Vec::new(),
+        };
+        let signing_key = SigningKey::from_bytes(&[7; 32]);
+        save_proof_result(
+            dir.path(),
+            &task,
+            ProofFormat::Postcard,
+            &result,
+            Some(&signing_key),
+        )
+        .unwrap();
+
+        let listed = SavedProofMetadata::list(dir.path()).unwrap();
+        assert_eq!(listed.len(), 1);
+        let metadata = SavedProofMetadata::load(&listed[0]).unwrap();
+        assert_eq!(metadata.combined_hash, "abc");
+        assert!(metadata.proof_path(dir.path()).unwrap().exists());
+        // Submitted later with the key the task was fetched with
+        assert_eq!(
+            metadata.signing_key().unwrap().to_bytes(),
+            signing_key.to_bytes()
+        );
+        assert!(!metadata.submitted);
+    }
+}
@@ -6,12 +6,15 @@ use crate::environment::Environment;
 use crate::task::Task;
 use nexus_sdk::stwo::seq::Proof;
 
-/// Proves a program with authenticated task inputs
+/// Proves a program with authenticated task inputs, verifying each proof unless
+/// `skip_verify` is set
 pub async fn authenticated_proving(
     task: &Task,
     environment: &Environment,
     client_id: &str,
     num_workers: usize,
+    skip_verify: bool,
 ) -> Result<(Vec<Proof>, String, Vec<String>), ProverError> {
-    ProvingPipeline::prove_authenticated(task, environment, client_id, num_workers).await
+    ProvingPipeline::prove_authenticated(task, environment, client_id, num_workers, skip_verify)
+        .await
 }
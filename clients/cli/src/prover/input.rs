@@ -28,4 +28,34 @@ impl InputParser {
 
         Ok((n, init_a, init_b))
     }
+
+    /// Parse triple public input from text of the form "n,init_a,init_b"
+    pub fn parse_triple_text(text: &str) -> Result<(u32, u32, u32), ProverError> {
+        let values = text
+            .split(',')
+            .map(|value| value.trim().parse::<u32>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| ProverError::MalformedTask(format!("Invalid input {:?}: {}", text, e)))?;
+
+        match values[..] {
+            [n, init_a, init_b] => Ok((n, init_a, init_b)),
+            _ => Err(ProverError::MalformedTask(format!(
+                "Expected three comma-separated values (n,init_a,init_b), got {:?}",
+                text
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_triple_text() {
+        assert_eq!(InputParser::parse_triple_text("9, 1,1").unwrap(), (9, 1, 1));
+        assert!(InputParser::parse_triple_text("9,1").is_err());
+        assert!(InputParser::parse_triple_text("9,1,1,1").is_err());
+        assert!(InputParser::parse_triple_text("9,-1,1").is_err());
+    }
 }
@@ -1,7 +1,10 @@
 pub mod engine;
+pub mod envelope;
+pub mod format;
 pub mod handlers;
 pub mod input;
 pub mod pipeline;
+pub mod program;
 pub mod types;
 pub mod verifier;
 
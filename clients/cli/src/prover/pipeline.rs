@@ -3,44 +3,81 @@
 use std::sync::Arc;
 
 use super::engine::ProvingEngine;
-use super::input::InputParser;
+use super::program::{ProgramDefinition, ProgramRegistry};
 use super::types::ProverError;
 use crate::analytics::track_verification_failed;
+use crate::consts::cli_consts::MAX_PARALLEL_VERIFICATIONS;
 use crate::environment::Environment;
 use crate::task::Task;
 use futures::future::join_all;
 use nexus_sdk::stwo::seq::Proof;
 use sha3::{Digest, Keccak256};
+use tokio::sync::Semaphore;
 use tokio_util::sync::CancellationToken;
 
 /// Orchestrates the complete proving pipeline
 pub struct ProvingPipeline;
 
+/// Number of a task's proofs to verify at the same time
+fn verification_limit(num_inputs: usize) -> usize {
+    crate::system::num_cores()
+        .min(MAX_PARALLEL_VERIFICATIONS)
+        .min(num_inputs)
+        .max(1)
+}
+
+/// Run `verify` on a blocking thread once a verification permit is free
+async fn verify_bounded<T, F>(permits: Arc<Semaphore>, verify: F) -> Result<T, ProverError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, ProverError> + Send + 'static,
+{
+    let _permit = permits
+        .acquire_owned()
+        .await
+        .map_err(|_| ProverError::MalformedTask("Task cancelled".to_string()))?;
+    tokio::task::spawn_blocking(verify)
+        .await
+        .map_err(ProverError::JoinError)?
+}
+
 impl ProvingPipeline {
-    /// Execute authenticated proving for a task
+    /// Execute authenticated proving for a task. With `skip_verify`, proofs are hashed
+    /// without being verified first.
     pub async fn prove_authenticated(
         task: &Task,
         environment: &Environment,
         client_id: &str,
         num_workers: usize,
+        skip_verify: bool,
     ) -> Result<(Vec<Proof>, String, Vec<String>), ProverError> {
-        match task.program_id.as_str() {
-            "fib_input_initial" => {
-                Self::prove_fib_task(task, environment, client_id, num_workers).await
+        match ProgramRegistry::get(&task.program_id) {
+            Some(program) => {
+                Self::prove_program_task(
+                    program,
+                    task,
+                    environment,
+                    client_id,
+                    num_workers,
+                    skip_verify,
+                )
+                .await
             }
-            _ => Err(ProverError::MalformedTask(format!(
+            None => Err(ProverError::MalformedTask(format!(
                 "Unsupported program ID: {}",
                 task.program_id
             ))),
         }
     }
 
-    /// Process fibonacci proving task with multiple inputs
-    async fn prove_fib_task(
+    /// Process a proving task for `program` with multiple inputs
+    async fn prove_program_task(
+        program: &'static ProgramDefinition,
         task: &Task,
         environment: &Environment,
         client_id: &str,
         num_workers: usize,
+        skip_verify: bool,
     ) -> Result<(Vec<Proof>, String, Vec<String>), ProverError> {
         let all_inputs = task.all_inputs();
 
@@ -55,8 +92,15 @@ impl ProvingPipeline {
         let environment_shared = Arc::new(environment.clone());
         let client_id_shared = Arc::new(client_id.to_string());
 
-        // Create a semaphore with a specific number of permits
-        let semaphore = Arc::new(tokio::sync::Semaphore::new(num_workers));
+        // Create a semaphore with a specific number of permits. Inputs are not assigned
+        // to workers up front: each waits for the next free permit, so a slow input
+        // never holds up the others.
+        let semaphore = Arc::new(Semaphore::new(num_workers));
+
+        // Verification runs on blocking threads after the proving permit is released, so
+        // one input's verification overlaps the next input's proving. It is CPU-heavy, so
+        // cap how many run at once.
+        let verification_permits = Arc::new(Semaphore::new(verification_limit(all_inputs.len())));
 
         // Create cancellation token for graceful shutdown
         let cancellation_token = CancellationToken::new();
@@ -71,6 +115,7 @@ impl ProvingPipeline {
                 let client_id_ref = Arc::clone(&client_id_shared);
                 let input_data = input_data.clone();
                 let semaphore_ref = Arc::clone(&semaphore);
+                let verification_ref = Arc::clone(&verification_permits);
                 let cancellation_ref = cancellation_token.clone();
 
                 tokio::spawn(async move {
@@ -80,7 +125,7 @@ impl ProvingPipeline {
                     }
 
                     // Acquire a permit from the semaphore. This waits if the limit is reached.
-                    let _permit = semaphore_ref.acquire_owned().await;
+                    let permit = semaphore_ref.acquire_owned().await;
 
                     // Check for cancellation after acquiring permit
                     if cancellation_ref.is_cancelled() {
@@ -88,19 +133,28 @@ impl ProvingPipeline {
                     }
 
                     // Step 1: Parse and validate input
-                    let inputs = InputParser::parse_triple_input(&input_data)?;
+                    let inputs = (program.parse_input)(&input_data)?;
 
-                    // Step 2: Generate and verify proof
-                    let proof = ProvingEngine::prove_and_validate(
+                    // Step 2: Generate proof
+                    let (proof, exit_code) = ProvingEngine::prove(
+                        program,
                         &inputs,
                         &task_ref,
                         &environment_ref,
                         &client_id_ref,
                     )
                     .await?;
+                    drop(permit);
 
-                    // Step 3: Generate proof hash
-                    let proof_hash = Self::generate_proof_hash(&proof);
+                    // Step 3: Verify proof (unless skipped) and generate its hash
+                    let (proof, proof_hash) = verify_bounded(verification_ref, move || {
+                        if !skip_verify {
+                            ProvingEngine::verify(program, &proof, &inputs, exit_code)?;
+                        }
+                        let proof_hash = Self::generate_proof_hash(&proof);
+                        Ok((proof, proof_hash))
+                    })
+                    .await?;
 
                     Ok((proof, proof_hash, input_index))
                 })
@@ -170,7 +224,7 @@ impl ProvingPipeline {
     }
 
     /// Generate hash for a proof
-    fn generate_proof_hash(proof: &Proof) -> String {
+    pub fn generate_proof_hash(proof: &Proof) -> String {
         let proof_bytes = postcard::to_allocvec(proof).expect("Failed to serialize proof");
         format!("{:x}", Keccak256::digest(&proof_bytes))
     }
@@ -186,3 +240,51 @@ impl ProvingPipeline {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    /// Verify stand-in proofs with `limit` verifications at a time, finishing in a
+    /// scrambled order, and combine their hashes in input order like the pipeline does
+    async fn combined_hash_with_limit(limit: usize) -> String {
+        let permits = Arc::new(Semaphore::new(limit));
+        let handles: Vec<_> = (0..8u64)
+            .map(|input_index| {
+                let permits = Arc::clone(&permits);
+                tokio::spawn(async move {
+                    verify_bounded(permits, move || {
+                        std::thread::sleep(Duration::from_millis((input_index * 7) % 5 * 10));
+                        Ok(format!(
+                            "{:x}",
+                            Keccak256::digest(input_index.to_le_bytes())
+                        ))
+                    })
+                    .await
+                })
+            })
+            .collect();
+
+        let hashes: Vec<String> = join_all(handles)
+            .await
+            .into_iter()
+            .map(|result| result.unwrap().unwrap())
+            .collect();
+        Task::combine_proof_hashes(&hashes)
+    }
+
+    #[tokio::test]
+    async fn test_parallel_and_serial_verification_combine_identically() {
+        let serial = combined_hash_with_limit(1).await;
+        let parallel = combined_hash_with_limit(MAX_PARALLEL_VERIFICATIONS).await;
+        assert_eq!(serial, parallel);
+    }
+
+    #[test]
+    fn test_verification_limit_is_bounded() {
+        assert_eq!(verification_limit(1), 1);
+        assert!(verification_limit(100) <= MAX_PARALLEL_VERIFICATIONS);
+        assert!(verification_limit(100) >= 1);
+    }
+}
@@ -0,0 +1,96 @@
+//! Guest programs this CLI can prove
+//!
+//! Each program the orchestrator may assign is described by a [`ProgramDefinition`] and
+//! listed in [`ProgramRegistry`]; proving looks a task's `program_id` up there. Adding a
+//! guest program means embedding its ELF, pinning its hash in the build script and
+//! registering its definition.
+
+use super::input::InputParser;
+use super::types::ProverError;
+use nexus_sdk::KnownExitCodes;
+
+/// A guest program and the runs of it that count as valid
+#[derive(Clone, Copy)]
+pub struct ProgramDefinition {
+    /// Program ID used by the orchestrator
+    pub id: &'static str,
+    /// Guest exit codes that indicate a valid run
+    pub accepted_exit_codes: &'static [u32],
+    /// Guest program embedded in the binary
+    pub elf: &'static [u8],
+    /// SHA3-256 of `elf`, pinned by the build script
+    pub expected_sha3_256: &'static str,
+    /// Decode one entry of a task's public inputs. Registered programs currently all take
+    /// three u32 inputs, which is what the prover and verifier pass to the guest.
+    pub parse_input: fn(&[u8]) -> Result<(u32, u32, u32), ProverError>,
+}
+
+impl std::fmt::Debug for ProgramDefinition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // The ELF is large and not useful in logs
+        f.debug_struct("ProgramDefinition")
+            .field("id", &self.id)
+            .field("accepted_exit_codes", &self.accepted_exit_codes)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The fibonacci program served as `fib_input_initial`
+pub const FIB_INPUT_INITIAL: ProgramDefinition = ProgramDefinition {
+    id: "fib_input_initial",
+    accepted_exit_codes: &[KnownExitCodes::ExitSuccess as u32],
+    elf: include_bytes!("../../assets/fib_input_initial"),
+    expected_sha3_256: env!("FIB_ELF_EXPECTED_SHA3_256"),
+    parse_input: InputParser::parse_triple_input,
+};
+
+impl ProgramDefinition {
+    /// Whether a run that exited with `exit_code` is valid
+    pub fn accepts_exit_code(&self, exit_code: u32) -> bool {
+        self.accepted_exit_codes.contains(&exit_code)
+    }
+}
+
+/// The guest programs this CLI can prove, by program ID
+pub struct ProgramRegistry;
+
+impl ProgramRegistry {
+    /// Every registered program
+    pub const PROGRAMS: &'static [ProgramDefinition] = &[FIB_INPUT_INITIAL];
+
+    /// The program served under `id`, if this CLI can prove it
+    pub fn get(id: &str) -> Option<&'static ProgramDefinition> {
+        Self::PROGRAMS.iter().find(|program| program.id == id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fib_accepts_only_success() {
+        assert!(FIB_INPUT_INITIAL.accepts_exit_code(KnownExitCodes::ExitSuccess as u32));
+        assert!(!FIB_INPUT_INITIAL.accepts_exit_code(1));
+    }
+
+    #[test]
+    fn test_alternate_accepted_exit_code() {
+        const EARLY_EXIT: ProgramDefinition = ProgramDefinition {
+            id: "early_exit",
+            accepted_exit_codes: &[KnownExitCodes::ExitSuccess as u32, 2],
+            ..FIB_INPUT_INITIAL
+        };
+        assert!(EARLY_EXIT.accepts_exit_code(2));
+        assert!(!EARLY_EXIT.accepts_exit_code(1));
+    }
+
+    #[test]
+    fn test_registry_lookup() {
+        assert_eq!(
+            ProgramRegistry::get("fib_input_initial").map(|program| program.id),
+            Some(FIB_INPUT_INITIAL.id)
+        );
+        assert!(ProgramRegistry::get("unknown_program").is_none());
+    }
+}
@@ -24,11 +24,19 @@ pub enum ProverError {
     #[error("Subprocess error: {0}")]
     Subprocess(String),
 
+    #[error("Out of memory: {0}")]
+    OutOfMemory(String),
+
     #[error("Serde JSON error: {0}")]
     SerdeJson(#[from] serde_json::Error),
 
     #[error("Task Join Error: {0}")]
     JoinError(JoinError),
+
+    #[error(
+        "Embedded guest program does not match this build (expected SHA3-256 {expected}, got {actual}); reinstall the CLI"
+    )]
+    ProgramMismatch { expected: String, actual: String },
 }
 
 /// Result of a proof generation, including combined hash for multiple inputs
@@ -1,25 +1,22 @@
 //! Proof verification
 
+use super::program::ProgramDefinition;
 use super::types::ProverError;
-use nexus_sdk::{KnownExitCodes, Verifiable, Viewable, stwo::seq::Proof};
+use nexus_sdk::{Verifiable, Viewable, stwo::seq::Proof};
 
 /// Proof verifier for validating generated proofs
 pub struct ProofVerifier;
 
 impl ProofVerifier {
-    /// Verify a proof with expected inputs and exit code
+    /// Verify a proof with expected inputs and the exit code its run ended with
     pub fn verify_proof(
         proof: &Proof,
         inputs: &(u32, u32, u32),
+        exit_code: u32,
         prover: &nexus_sdk::stwo::seq::Stwo<nexus_sdk::Local>,
     ) -> Result<(), ProverError> {
-        match proof.verify_expected::<(u32, u32, u32), ()>(
-            inputs,
-            KnownExitCodes::ExitSuccess as u32,
-            &(),
-            &prover.elf,
-            &[],
-        ) {
+        match proof.verify_expected::<(u32, u32, u32), ()>(inputs, exit_code, &(), &prover.elf, &[])
+        {
             Ok(_) => Ok(()),
             Err(e) => Err(ProverError::Stwo(format!(
                 "Proof verification failed: {} for inputs: {:?}",
@@ -28,15 +25,26 @@ impl ProofVerifier {
         }
     }
 
-    /// Check exit code from proof execution
-    pub fn check_exit_code<T: Viewable>(view: &T) -> Result<(), ProverError> {
+    /// Check exit code from proof execution, returning it if the program accepts it
+    pub fn check_exit_code<T: Viewable>(
+        view: &T,
+        program: &ProgramDefinition,
+    ) -> Result<u32, ProverError> {
         let exit_code = view.exit_code().map_err(|e| {
             ProverError::GuestProgram(format!("Failed to deserialize exit code: {}", e))
         })?;
+        Self::check_accepted_exit_code(exit_code, program)?;
+        Ok(exit_code)
+    }
 
-        if exit_code != KnownExitCodes::ExitSuccess as u32 {
+    /// Reject exit codes the program doesn't accept
+    fn check_accepted_exit_code(
+        exit_code: u32,
+        program: &ProgramDefinition,
+    ) -> Result<(), ProverError> {
+        if !program.accepts_exit_code(exit_code) {
             return Err(ProverError::GuestProgram(format!(
-                "Prover exited with non-zero exit code: {}",
+                "Prover exited with unexpected exit code: {}",
                 exit_code
             )));
         }
@@ -44,3 +52,23 @@ impl ProofVerifier {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prover::program::FIB_INPUT_INITIAL;
+
+    #[test]
+    fn test_check_accepted_exit_code_uses_program_definition() {
+        const ALTERNATE: ProgramDefinition = ProgramDefinition {
+            id: "alternate",
+            accepted_exit_codes: &[3],
+            ..FIB_INPUT_INITIAL
+        };
+
+        assert!(ProofVerifier::check_accepted_exit_code(0, &FIB_INPUT_INITIAL).is_ok());
+        assert!(ProofVerifier::check_accepted_exit_code(3, &FIB_INPUT_INITIAL).is_err());
+        assert!(ProofVerifier::check_accepted_exit_code(3, &ALTERNATE).is_ok());
+        assert!(ProofVerifier::check_accepted_exit_code(0, &ALTERNATE).is_err());
+    }
+}
@@ -4,6 +4,7 @@ use crate::cli_messages::{print_error, print_info, print_success};
 use crate::config::Config;
 use crate::keys;
 use crate::orchestrator::Orchestrator;
+use crate::storage::describe_write_error;
 use std::path::Path;
 
 /// Registers a user with the orchestrator.
@@ -11,10 +12,12 @@ use std::path::Path;
 /// # Arguments
 /// * `wallet_address` - The Ethereum wallet address of the user.
 /// * `config_path` - The path to the configuration file where user details will be saved.
+/// * `profile` - The config profile to save the user to.
 /// * `orchestrator` - The orchestrator client to communicate with the orchestrator.
 pub async fn register_user(
     wallet_address: &str,
     config_path: &Path,
+    profile: &str,
     orchestrator: Box<dyn Orchestrator>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     // Check if the wallet address is valid.
@@ -35,7 +38,9 @@ pub async fn register_user(
 
     // Check if the config file exists and contains this wallet address and a user ID.
     if config_path.exists() {
-        if let Ok(config) = Config::load_from_file(config_path) {
+        if let Ok(config) =
+            Config::load_from_file(config_path).and_then(|config| config.select_profile(profile))
+        {
             if config.wallet_address.to_lowercase() == wallet_address.to_lowercase()
                 && !config.user_id.is_empty()
             {
@@ -63,16 +68,13 @@ pub async fn register_user(
             "Wallet address is already registered",
             &format!("User ID: {}, Wallet Address: {}", user_id, wallet_address),
         );
-        let config = Config::new(
+        save_user(
+            config_path,
+            profile,
             user_id,
-            wallet_address.to_string(),
-            String::new(), // node_id is empty for now
-            orchestrator.environment().clone(),
-        );
-        // Save the configuration file with the user ID and wallet address.
-        config.save(config_path).inspect_err(|e| {
-            print_error("Failed to save config", Some(&e.to_string()));
-        })?;
+            wallet_address,
+            orchestrator.as_ref(),
+        )?;
 
         // Guide user to next step
         print_success(
@@ -85,13 +87,29 @@ pub async fn register_user(
 
     // Otherwise, register the user with the orchestrator.
     let uuid = uuid::Uuid::new_v4().to_string();
-    match orchestrator.register_user(&uuid, wallet_address).await {
+    let user_id = match orchestrator.register_user(&uuid, wallet_address).await {
         Ok(_) => {
             print_success(
                 "User registered successfully",
                 &format!("User ID: {}", uuid),
             );
+            uuid
         }
+        // Another process registered this wallet between our lookup and registration;
+        // use the user it created.
+        Err(e) if e.is_conflict() => match orchestrator.get_user(wallet_address).await {
+            Ok(user_id) => {
+                print_info(
+                    "Wallet address was registered concurrently",
+                    &format!("User ID: {}, Wallet Address: {}", user_id, wallet_address),
+                );
+                user_id
+            }
+            Err(_) => {
+                print_error("Failed to register user", Some(&e.to_string()));
+                return Err(e.into());
+            }
+        },
         Err(e) => {
             // Check if this looks like an orchestrator traffic issue
             if let Some(pretty_error) = e.to_pretty() {
@@ -102,18 +120,15 @@ pub async fn register_user(
 
             return Err(e.into());
         }
-    }
+    };
 
-    // Save the configuration file with the user ID and wallet address.
-    let config = Config::new(
-        uuid,
-        wallet_address.to_string(),
-        String::new(), // node_id is empty for now
-        orchestrator.environment().clone(),
-    );
-    config.save(config_path).inspect_err(|e| {
-        print_error("Failed to save config", Some(&e.to_string()));
-    })?;
+    save_user(
+        config_path,
+        profile,
+        user_id,
+        wallet_address,
+        orchestrator.as_ref(),
+    )?;
 
     // Guide user to next step
     print_success(
@@ -124,27 +139,59 @@ pub async fn register_user(
     Ok(())
 }
 
+/// Save the user ID and wallet address to the config profile, with no node yet. Other
+/// profiles and settings in an existing config file are kept.
+fn save_user(
+    config_path: &Path,
+    profile: &str,
+    user_id: String,
+    wallet_address: &str,
+    orchestrator: &dyn Orchestrator,
+) -> Result<(), std::io::Error> {
+    let user = Config::new(
+        user_id,
+        wallet_address.to_string(),
+        String::new(), // node_id is empty for now
+        orchestrator.environment().clone(),
+    );
+    Config::load_from_file(config_path)
+        .unwrap_or_default()
+        .store_profile(profile, &user)
+        .save(config_path)
+        .inspect_err(|e| {
+            print_error(
+                "Failed to save config",
+                Some(&describe_write_error("the node configuration", e)),
+            );
+        })
+}
+
 /// Registers a node with the orchestrator.
 ///
 /// # Arguments
 /// * `node_id` - Optional node ID. If provided, it will be used to register the node.
 /// * `config_path` - The path to the configuration file where node details will be saved.
+/// * `profile` - The config profile whose user the node is registered to.
 /// * `orchestrator` - The orchestrator client to communicate with the orchestrator.
 pub async fn register_node(
     node_id: Option<u64>,
     config_path: &Path,
+    profile: &str,
     orchestrator: Box<dyn Orchestrator>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     // Register a new node, or link an existing node to a user.
     // Requires: a config file with a registered user.
     // If a node_id is provided, update the config with it and use it.
     // If no node_id is provided, generate a new one.
-    let mut config = Config::load_from_file(config_path).inspect_err(|e| {
+    let file_config = Config::load_from_file(config_path).inspect_err(|e| {
         print_error(
             "Failed to load config, please register a user first",
             Some(&e.to_string()),
         );
     })?;
+    let mut config = file_config.select_profile(profile).inspect_err(|e| {
+        print_error("Failed to select config profile", Some(&e.to_string()));
+    })?;
     if config.user_id.is_empty() {
         print_error("No user registered", Some("Please register a user first."));
         return Err(Box::from(
@@ -155,8 +202,12 @@ pub async fn register_node(
         // If a node_id is provided, update the config with it.
         println!("Registering node ID: {}", node_id);
         config.node_id = node_id.to_string();
+        let config = file_config.store_profile(profile, &config);
         config.save(config_path).inspect_err(|e| {
-            print_error("Failed to save updated config", Some(&e.to_string()));
+            print_error(
+                "Failed to save updated config",
+                Some(&describe_write_error("the node configuration", e)),
+            );
         })?;
 
         // Guide user to next step
@@ -177,10 +228,13 @@ pub async fn register_node(
         match orchestrator.register_node(&config.user_id).await {
             Ok(node_id) => {
                 // Update the config with the new node ID
-                let mut updated_config = config;
-                updated_config.node_id = node_id.clone();
+                config.node_id = node_id.clone();
+                let updated_config = file_config.store_profile(profile, &config);
                 updated_config.save(config_path).inspect_err(|e| {
-                    print_error("Failed to save updated config", Some(&e.to_string()));
+                    print_error(
+                        "Failed to save updated config",
+                        Some(&describe_write_error("the node configuration", e)),
+                    );
                 })?;
 
                 // Guide user to next step
@@ -205,6 +259,7 @@ pub async fn register_node(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::DEFAULT_PROFILE;
     use crate::environment::Environment;
     use crate::orchestrator::MockOrchestrator;
     use crate::orchestrator::error::OrchestratorError;
@@ -242,7 +297,7 @@ mod tests {
             .returning(|_, _| Ok(()));
 
         // ---- call the function under test ----
-        register_user(WALLET, &path, Box::new(orchestrator))
+        register_user(WALLET, &path, DEFAULT_PROFILE, Box::new(orchestrator))
             .await
             .expect("registration should succeed");
 
@@ -252,6 +307,108 @@ mod tests {
         assert!(!cfg.user_id.is_empty());
     }
 
+    /// Registering to a named profile keeps the top-level user, and a node registered to
+    /// that profile lands in it too.
+    #[tokio::test]
+    async fn registers_user_and_node_to_named_profile() {
+        const WALLET: &str = "0x1234567890123456789012345678901234567890";
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        Config::new(
+            "default-user".to_string(),
+            WALLET.to_string(),
+            "1".to_string(),
+            Environment::Production,
+        )
+        .save(&path)
+        .unwrap();
+
+        let dev = Environment::Custom {
+            orchestrator_url: "http://localhost:8080".to_string(),
+        };
+        let mut orchestrator = MockOrchestrator::new();
+        orchestrator.expect_environment().return_const(dev.clone());
+        orchestrator
+            .expect_get_user()
+            .returning(|_| Ok("dev-user".to_string()));
+        register_user(WALLET, &path, "dev", Box::new(orchestrator))
+            .await
+            .expect("registration should succeed");
+
+        let mut orchestrator = MockOrchestrator::new();
+        orchestrator.expect_environment().return_const(dev);
+        orchestrator
+            .expect_register_node()
+            .with(eq("dev-user"))
+            .returning(|_| Ok("42".to_string()));
+        register_node(None, &path, "dev", Box::new(orchestrator))
+            .await
+            .expect("node registration should succeed");
+
+        let cfg = Config::load_from_file(&path).unwrap();
+        assert_eq!(cfg.user_id, "default-user");
+        assert_eq!(cfg.node_id, "1");
+        let profile = &cfg.profiles["dev"];
+        assert_eq!(profile.user_id, "dev-user");
+        assert_eq!(profile.node_id, "42");
+        // The profile connects to the environment it was registered in
+        assert_eq!(
+            Config::profile_environment(&path, "dev").unwrap(),
+            Some(Environment::Custom {
+                orchestrator_url: "http://localhost:8080".to_string(),
+            })
+        );
+    }
+
+    /// Another process registers the same wallet between our lookup and registration.
+    #[tokio::test]
+    async fn uses_existing_user_when_registration_conflicts() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        const WALLET: &str = "0x1234567890123456789012345678901234567890";
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.json");
+
+        let mut orchestrator = MockOrchestrator::new();
+        orchestrator
+            .expect_environment()
+            .return_const(Environment::Production);
+
+        // Not found on the first lookup, present after the conflict
+        let lookups = Arc::new(AtomicUsize::new(0));
+        orchestrator
+            .expect_get_user()
+            .with(eq(WALLET))
+            .times(2)
+            .returning(move |_| {
+                if lookups.fetch_add(1, Ordering::SeqCst) == 0 {
+                    Err(OrchestratorError::Http {
+                        status: 404,
+                        message: "User not found".to_string(),
+                        headers: std::collections::HashMap::new(),
+                    })
+                } else {
+                    Ok("concurrent-user-id".to_string())
+                }
+            });
+
+        orchestrator.expect_register_user().returning(|_, _| {
+            Err(OrchestratorError::Http {
+                status: 409,
+                message: "User already exists".to_string(),
+                headers: std::collections::HashMap::new(),
+            })
+        });
+
+        register_user(WALLET, &path, DEFAULT_PROFILE, Box::new(orchestrator))
+            .await
+            .expect("conflicting registration should use the existing user");
+
+        let cfg = Config::load_from_file(&path).unwrap();
+        assert_eq!(cfg.user_id, "concurrent-user-id");
+    }
+
     #[tokio::test]
     /// Config file already exists with a registered user.
     async fn skips_registration_if_config_matches_wallet_and_user_id() {
@@ -286,7 +443,13 @@ mod tests {
         orchestrator.expect_register_user().never();
 
         // Call the function
-        let result = register_user(wallet_address, &config_path, Box::new(orchestrator)).await;
+        let result = register_user(
+            wallet_address,
+            &config_path,
+            DEFAULT_PROFILE,
+            Box::new(orchestrator),
+        )
+        .await;
 
         assert!(result.is_ok(), "should succeed without making any requests");
 
@@ -1,50 +1,126 @@
 //! Simplified runtime for coordinating authenticated workers
 
-use crate::environment::Environment;
-use crate::events::Event;
+use crate::events::{Event, EventType};
+use crate::logging::LogLevel;
+use crate::network::request_timer::get_request_timer_state_dir;
 use crate::orchestrator::OrchestratorClient;
+use crate::session::summary::ShutdownReason;
+use crate::version::manager::{version_checks_enabled, watch_version_requirements};
 use crate::workers::authenticated_worker::AuthenticatedWorker;
-use crate::workers::core::WorkerConfig;
+use crate::workers::core::{EventSender, WorkerConfig};
+use crate::workers::difficulty_state::get_difficulty_state_path;
+use crate::workers::rejections::get_rejections_path;
 use ed25519_dalek::SigningKey;
 use tokio::sync::{broadcast, mpsc};
 use tokio::task::JoinHandle;
 
-/// Start single authenticated worker
-#[allow(clippy::too_many_arguments)]
+/// Split `total` proving workers across `nodes` nodes as evenly as possible, giving
+/// each node at least one
+pub fn split_workers(total: usize, nodes: usize) -> Vec<usize> {
+    let nodes = nodes.max(1);
+    let total = total.max(nodes);
+    (0..nodes)
+        .map(|index| total / nodes + usize::from(index < total % nodes))
+        .collect()
+}
+
+/// Log the hardware telemetry attached to proof submissions, so users can review exactly
+/// what is reported
+async fn log_node_telemetry(orchestrator: OrchestratorClient, event_sender: EventSender) {
+    // Proofs are submitted with a single prover, so report exactly that
+    let telemetry = orchestrator.node_telemetry(1).await;
+    event_sender
+        .send_proof_event(
+            format!(
+                "Telemetry sent with every proof submission: flops_per_sec={} (estimated GFLOP/s), memory_used={} MB, memory_capacity={} MB, location={}",
+                telemetry.flops_per_sec.unwrap_or_default(),
+                telemetry.memory_used.unwrap_or_default(),
+                telemetry.memory_capacity.unwrap_or_default(),
+                telemetry.location.unwrap_or_default()
+            ),
+            EventType::Refresh,
+            LogLevel::Debug,
+        )
+        .await;
+}
+
+/// Start one authenticated worker per node. With several nodes, every event is
+/// attributed to the node it belongs to, and `config.num_workers` is shared between them.
 pub async fn start_authenticated_worker(
-    node_id: u64,
-    signing_key: SigningKey,
+    nodes: Vec<(u64, SigningKey)>,
     orchestrator: OrchestratorClient,
     shutdown: broadcast::Receiver<()>,
-    environment: Environment,
-    client_id: String,
+    mut config: WorkerConfig,
     max_tasks: Option<u32>,
-    max_difficulty: Option<crate::nexus_orchestrator::TaskDifficulty>,
-    num_workers: usize,
+    persist_rate_limits: bool,
 ) -> (
     mpsc::Receiver<Event>,
     Vec<JoinHandle<()>>,
-    broadcast::Sender<()>,
+    broadcast::Sender<ShutdownReason>,
 ) {
-    let mut config = WorkerConfig::new(environment, client_id);
-    config.max_difficulty = max_difficulty;
-    config.num_workers = num_workers;
+    config.rejections_path = get_rejections_path().ok();
     let (event_sender, event_receiver) =
         mpsc::channel::<Event>(crate::consts::cli_consts::EVENT_QUEUE_SIZE);
 
-    // Create a separate shutdown sender for max tasks completion
+    // Create a separate shutdown sender for the worker to end the session
     let (shutdown_sender, _) = broadcast::channel(1);
 
-    let worker = AuthenticatedWorker::new(
-        node_id,
-        signing_key,
-        orchestrator,
-        config,
-        event_sender,
-        max_tasks,
-        shutdown_sender.clone(),
-    );
-
-    let join_handles = worker.run(shutdown).await;
+    let multiple_nodes = nodes.len() > 1;
+    let shares = split_workers(config.num_workers, nodes.len());
+    let mut join_handles = Vec::new();
+    for ((node_id, signing_key), num_workers) in nodes.into_iter().zip(shares) {
+        // Adaptive difficulty and rate limits are tracked per node
+        let mut node_config = config.clone();
+        node_config.num_workers = num_workers;
+        node_config.difficulty_state_path = get_difficulty_state_path(node_id).ok();
+        if persist_rate_limits {
+            node_config.request_timer_state_dir = get_request_timer_state_dir(node_id).ok();
+        }
+
+        let mut node_events = EventSender::new(event_sender.clone());
+        if multiple_nodes {
+            node_events = node_events.with_node_id(node_id);
+        }
+
+        let worker = AuthenticatedWorker::new(
+            node_id,
+            signing_key,
+            orchestrator.clone(),
+            node_config,
+            node_events,
+            max_tasks,
+            shutdown_sender.clone(),
+        );
+        join_handles.extend(worker.run(shutdown.resubscribe()).await);
+    }
+
+    if config.dump_telemetry {
+        tokio::spawn(log_node_telemetry(
+            orchestrator,
+            EventSender::new(event_sender.clone()),
+        ));
+    }
+
+    // Stop proving if the server starts blocking this version mid-session
+    if version_checks_enabled() {
+        tokio::spawn(watch_version_requirements(
+            EventSender::new(event_sender),
+            shutdown_sender.clone(),
+            shutdown,
+        ));
+    }
     (event_receiver, join_handles, shutdown_sender)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_workers() {
+        assert_eq!(split_workers(4, 1), vec![4]);
+        assert_eq!(split_workers(5, 2), vec![3, 2]);
+        // Every node gets a worker, even past the requested total
+        assert_eq!(split_workers(1, 3), vec![1, 1, 1]);
+    }
+}
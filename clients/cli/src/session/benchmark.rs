@@ -0,0 +1,123 @@
+//! Offline proving benchmark
+//!
+//! Proves a fixed fibonacci workload several times in-process, without any network
+//! calls, and reports min/median/max wall time alongside the machine's measured GFLOP/s,
+//! so hardware can be sized before registering a node.
+
+use crate::difficulty::difficulty_rank;
+use crate::nexus_orchestrator::TaskDifficulty;
+use crate::print_cmd_info;
+use crate::prover::engine::ProvingEngine;
+use crate::prover::program::FIB_INPUT_INITIAL;
+use crate::system::{describe_cpu_features, measure_gflops, prover_cpu_features};
+use std::error::Error;
+use std::time::{Duration, Instant};
+
+/// Fibonacci index proved at the lowest difficulty; each level above doubles it
+const BENCHMARK_BASE_N: u32 = 1_000;
+
+/// Inputs proved for `difficulty`. The orchestrator decides what a real task at each level
+/// contains; these only scale the local workload in the same order.
+fn benchmark_inputs(difficulty: TaskDifficulty) -> (u32, u32, u32) {
+    let rank = difficulty_rank(difficulty).unwrap_or(0) as u32;
+    (BENCHMARK_BASE_N << rank, 1, 1)
+}
+
+/// Shortest, median and longest of `durations`, or `None` if there are none
+fn summarize(durations: &[Duration]) -> Option<(Duration, Duration, Duration)> {
+    let mut sorted = durations.to_vec();
+    sorted.sort();
+    let median = match sorted.len() {
+        0 => return None,
+        len if len % 2 == 0 => (sorted[len / 2 - 1] + sorted[len / 2]) / 2,
+        len => sorted[len / 2],
+    };
+    Some((sorted[0], median, sorted[sorted.len() - 1]))
+}
+
+/// Prove the workload for `difficulty` `iterations` times and print the timings.
+/// Each proof is verified, outside the timed section, so a broken prover can't look fast.
+pub async fn run_benchmark(
+    difficulty: TaskDifficulty,
+    iterations: u32,
+) -> Result<(), Box<dyn Error>> {
+    let inputs = benchmark_inputs(difficulty);
+    print_cmd_info!(
+        "Benchmark",
+        "Proving fib({}) {} time(s) at {} difficulty, offline",
+        inputs.0,
+        iterations,
+        difficulty.as_str_name()
+    );
+    print_cmd_info!(
+        "CPU features",
+        "{}",
+        describe_cpu_features(&prover_cpu_features())
+    );
+
+    let mut durations = Vec::new();
+    for iteration in 1..=iterations {
+        let started = Instant::now();
+        let (proof, exit_code) = tokio::task::spawn_blocking(move || {
+            ProvingEngine::prove_program_subprocess(&FIB_INPUT_INITIAL, &inputs)
+        })
+        .await??;
+        let duration = started.elapsed();
+        ProvingEngine::verify(&FIB_INPUT_INITIAL, &proof, &inputs, exit_code)?;
+
+        println!(
+            "  {:>3}/{}  {:>8.2}s",
+            iteration,
+            iterations,
+            duration.as_secs_f64()
+        );
+        durations.push(duration);
+    }
+
+    if let Some((min, median, max)) = summarize(&durations) {
+        println!();
+        println!("  {:<8} {:>8.2}s", "min", min.as_secs_f64());
+        println!("  {:<8} {:>8.2}s", "median", median.as_secs_f64());
+        println!("  {:<8} {:>8.2}s", "max", max.as_secs_f64());
+    }
+    println!("  {:<8} {:>8.1} GFLOP/s", "measured", measure_gflops());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_benchmark_inputs_grow_with_difficulty() {
+        assert_eq!(benchmark_inputs(TaskDifficulty::Small), (1_000, 1, 1));
+        assert_eq!(benchmark_inputs(TaskDifficulty::Medium), (4_000, 1, 1));
+    }
+
+    #[test]
+    fn test_summarize() {
+        let secs = |values: &[u64]| {
+            values
+                .iter()
+                .map(|s| Duration::from_secs(*s))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(summarize(&[]), None);
+        assert_eq!(
+            summarize(&secs(&[5, 1, 3])),
+            Some((
+                Duration::from_secs(1),
+                Duration::from_secs(3),
+                Duration::from_secs(5)
+            ))
+        );
+        assert_eq!(
+            summarize(&secs(&[4, 1, 2, 9])),
+            Some((
+                Duration::from_secs(1),
+                Duration::from_secs(3),
+                Duration::from_secs(9)
+            ))
+        );
+    }
+}
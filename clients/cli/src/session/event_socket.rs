@@ -0,0 +1,132 @@
+//! Live event stream over a Unix domain socket
+//!
+//! With `--event-socket`, every event is written as JSON, one per line, to each
+//! connected client. Any number of clients may connect; a client that falls behind
+//! misses events rather than holding up the session.
+
+use crate::events::Event;
+use std::io;
+use std::path::Path;
+use tokio::sync::mpsc;
+
+/// Stream every event from `receiver` to clients of a Unix socket bound at `path` on its
+/// way through [`tap_events`](crate::events::tap_events).
+#[cfg(unix)]
+pub fn serve_event_socket(
+    receiver: mpsc::Receiver<Event>,
+    path: &Path,
+) -> io::Result<mpsc::Receiver<Event>> {
+    use crate::consts::cli_consts::EVENT_QUEUE_SIZE;
+    use crate::events::tap_events;
+    use std::os::unix::fs::FileTypeExt;
+    use tokio::io::AsyncWriteExt;
+    use tokio::net::UnixListener;
+    use tokio::sync::broadcast;
+
+    // Replace a socket left behind by a previous run, but never another kind of file
+    match std::fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_socket() => std::fs::remove_file(path)?,
+        Ok(_) => {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{} exists and is not a socket", path.display()),
+            ));
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e),
+    }
+    let listener = UnixListener::bind(path)?;
+
+    let (lines, _) = broadcast::channel::<String>(EVENT_QUEUE_SIZE);
+
+    let clients = lines.clone();
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let mut client_lines = clients.subscribe();
+            tokio::spawn(async move {
+                loop {
+                    match client_lines.recv().await {
+                        Ok(line) => {
+                            if stream.write_all(line.as_bytes()).await.is_err() {
+                                break;
+                            }
+                        }
+                        Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    }
+                }
+            });
+        }
+    });
+
+    Ok(tap_events(receiver, move |event| {
+        if let Ok(line) = serde_json::to_string(event) {
+            // No connected clients is not an error
+            let _ = lines.send(line + "\n");
+        }
+        None
+    }))
+}
+
+/// Unix domain sockets are only available on Unix platforms.
+#[cfg(not(unix))]
+pub fn serve_event_socket(
+    _receiver: mpsc::Receiver<Event>,
+    _path: &Path,
+) -> io::Result<mpsc::Receiver<Event>> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "--event-socket is only available on Unix platforms",
+    ))
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::events::EventType;
+    use crate::logging::LogLevel;
+    use tempfile::tempdir;
+    use tokio::io::{AsyncBufReadExt, BufReader};
+    use tokio::net::UnixStream;
+
+    #[tokio::test]
+    async fn test_clients_receive_events_as_json_lines() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("events.sock");
+
+        let (sender, receiver) = mpsc::channel(8);
+        let mut forwarded = serve_event_socket(receiver, &path).unwrap();
+
+        let stream = UnixStream::connect(&path).await.unwrap();
+        let mut lines = BufReader::new(stream).lines();
+        // Let the server register the client before sending
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+        sender
+            .send(Event::task_fetcher_with_level(
+                "Step 1 of 4: Got task abc".to_string(),
+                EventType::Success,
+                LogLevel::Info,
+            ))
+            .await
+            .unwrap();
+
+        let line = lines.next_line().await.unwrap().unwrap();
+        let event: Event = serde_json::from_str(&line).unwrap();
+        assert_eq!(event.msg, "Step 1 of 4: Got task abc");
+        assert_eq!(forwarded.recv().await.unwrap().msg, event.msg);
+    }
+
+    #[test]
+    fn test_refuses_to_replace_regular_file() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("not-a-socket");
+        std::fs::write(&path, "keep me").unwrap();
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let (_sender, receiver) = mpsc::channel(1);
+        let result = runtime.block_on(async { serve_event_socket(receiver, &path) });
+        assert!(result.is_err());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "keep me");
+    }
+}
@@ -1,12 +1,45 @@
 //! Headless mode execution
 
+use super::signals::{RuntimeSignal, RuntimeSignals};
+use super::stats::SessionStats;
+use super::summary::{SessionSummary, ShutdownReason};
 use super::{
     SessionData,
     messages::{print_session_exit_success, print_session_shutdown, print_session_starting},
 };
+use crate::events::Event;
+use crate::logging::{cycle_log_level, log_level_override};
 use crate::print_cmd_info;
-use crate::version::checker::check_for_new_version;
+use crate::system::process_memory_gb;
+use crate::version::checker::{check_for_new_version, rate_limit_remaining_event};
 use std::error::Error;
+use tokio::sync::{broadcast, mpsc};
+use tokio::task::JoinHandle;
+
+/// How headless mode prints events
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable log lines
+    #[default]
+    Text,
+    /// One JSON object per event and line (NDJSON), for supervisor scripts
+    Json,
+}
+
+/// Print an event in the given output format. Every event is printed unless the log level
+/// was changed at runtime (`-v` or SIGUSR1), which then decides what is shown.
+fn print_event(event: &Event, output: OutputFormat) {
+    if log_level_override().is_some() && !event.should_display() {
+        return;
+    }
+    match output {
+        OutputFormat::Text => println!("{}", event),
+        OutputFormat::Json => match serde_json::to_string(event) {
+            Ok(line) => println!("{}", line),
+            Err(e) => eprintln!("Failed to serialize event: {}", e),
+        },
+    }
+}
 
 /// Runs the application in headless mode
 ///
@@ -14,16 +47,26 @@ use std::error::Error;
 /// 1. Console event logging
 /// 2. Ctrl+C shutdown handling
 /// 3. Event loop management
+/// 4. SIGUSR1/SIGUSR2 log level toggling and metrics dumps (Unix only)
 ///
 /// # Arguments
 /// * `session` - Session data from setup
+/// * `output` - Whether to print events as text or as JSON lines; JSON output leaves out
+///   the session banners
 ///
 /// # Returns
-/// * `Ok(())` - Headless mode completed successfully
+/// * `Ok(SessionSummary)` - Headless mode completed successfully
 /// * `Err` - Headless mode failed
-pub async fn run_headless_mode(mut session: SessionData) -> Result<(), Box<dyn Error>> {
+pub async fn run_headless_mode(
+    mut session: SessionData,
+    output: OutputFormat,
+) -> Result<SessionSummary, Box<dyn Error>> {
+    let text = output == OutputFormat::Text;
+
     // Print session start message
-    print_session_starting("headless", session.node_id);
+    if text {
+        print_session_starting("headless", session.node_id);
+    }
 
     // Check for new version and inform user
     let current_version = env!("CARGO_PKG_VERSION");
@@ -31,41 +74,130 @@ pub async fn run_headless_mode(mut session: SessionData) -> Result<(), Box<dyn E
     // First check constraint violations
     if let Some(message) = check_for_new_version(current_version).await {
         // If no constraints violated, check for newer versions available
-        print_cmd_info!("Version check", "{}", message);
+        if text {
+            print_cmd_info!("Version check", "{}", message);
+        }
+    }
+    if let Some(event) = rate_limit_remaining_event() {
+        print_event(&event, output);
     }
 
+    let mut stats = SessionStats::new();
+    let reason = run_event_loop(
+        &mut session.event_receiver,
+        &session.shutdown_sender,
+        &session.max_tasks_shutdown_sender,
+        session.num_workers,
+        &mut stats,
+        output,
+    )
+    .await;
+
+    // Wait for workers to finish, counting the work they complete while shutting down
+    if text {
+        print_session_shutdown();
+    }
+    finish_workers(
+        session.join_handles,
+        &mut session.event_receiver,
+        &mut stats,
+        output,
+    )
+    .await;
+    if text {
+        print_session_exit_success();
+    }
+
+    Ok(SessionSummary::new(
+        stats.uptime(),
+        &stats.zkvm_metrics(),
+        reason,
+    ))
+}
+
+/// Wait for the workers to stop, printing and counting the events they send meanwhile so
+/// the session summary includes tasks finished during shutdown
+pub(super) async fn finish_workers(
+    join_handles: Vec<JoinHandle<()>>,
+    event_receiver: &mut mpsc::Receiver<Event>,
+    stats: &mut SessionStats,
+    output: OutputFormat,
+) {
+    let workers = async {
+        for handle in join_handles {
+            let _ = handle.await;
+        }
+    };
+    tokio::pin!(workers);
+    loop {
+        tokio::select! {
+            _ = &mut workers => break,
+            Some(event) = event_receiver.recv() => {
+                stats.observe(&event);
+                print_event(&event, output);
+            }
+        }
+    }
+    while let Ok(event) = event_receiver.try_recv() {
+        stats.observe(&event);
+        print_event(&event, output);
+    }
+}
+
+/// Print events to the console until shutdown, or until the worker ends the session
+///
+/// Also used by TUI mode to keep the session alive when the terminal fails.
+/// Returns why the loop stopped.
+pub(super) async fn run_event_loop(
+    event_receiver: &mut mpsc::Receiver<Event>,
+    shutdown_sender: &broadcast::Sender<()>,
+    max_tasks_shutdown_sender: &broadcast::Sender<ShutdownReason>,
+    num_workers: usize,
+    stats: &mut SessionStats,
+    output: OutputFormat,
+) -> ShutdownReason {
     // Trigger shutdown on Ctrl+C
-    let shutdown_sender_clone = session.shutdown_sender.clone();
+    let shutdown_sender_clone = shutdown_sender.clone();
     tokio::spawn(async move {
         if tokio::signal::ctrl_c().await.is_ok() {
             let _ = shutdown_sender_clone.send(());
         }
     });
 
-    let mut shutdown_receiver = session.shutdown_sender.subscribe();
-    let mut max_tasks_shutdown_receiver = session.max_tasks_shutdown_sender.subscribe();
+    let mut shutdown_receiver = shutdown_sender.subscribe();
+    let mut max_tasks_shutdown_receiver = max_tasks_shutdown_sender.subscribe();
+    let mut signals = RuntimeSignals::install();
 
     // Event loop: log events to console until shutdown
     loop {
         tokio::select! {
-            Some(event) = session.event_receiver.recv() => {
-                println!("{}", event);
+            Some(event) = event_receiver.recv() => {
+                stats.observe(&event);
+                print_event(&event, output);
             }
+            signal = signals.recv() => match signal {
+                RuntimeSignal::CycleLogLevel => {
+                    print_cmd_info!("Log level changed", "{:?}", cycle_log_level());
+                }
+                RuntimeSignal::DumpMetrics => {
+                    print_cmd_info!(
+                        "Metrics",
+                        "{}, memory: {:.2} GB, workers: {}",
+                        stats,
+                        process_memory_gb(),
+                        num_workers
+                    );
+                }
+            },
             _ = shutdown_receiver.recv() => {
-                break;
+                return ShutdownReason::UserRequested;
             }
-            _ = max_tasks_shutdown_receiver.recv() => {
-                break;
+            reason = max_tasks_shutdown_receiver.recv() => {
+                // Stop every worker, not just the one that ended the session, as the
+                // dashboard does
+                let _ = shutdown_sender.send(());
+                return reason.unwrap_or(ShutdownReason::MaxTasksReached);
             }
         }
     }
-
-    // Wait for workers to finish
-    print_session_shutdown();
-    for handle in session.join_handles {
-        let _ = handle.await;
-    }
-    print_session_exit_success();
-
-    Ok(())
 }
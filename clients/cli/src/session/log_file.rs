@@ -0,0 +1,211 @@
+//! Plain-text event log with size-based rotation
+//!
+//! With `--log-file`, every event is appended to a log file (by default
+//! ~/.nexus/logs/nexus.log) as one line with its timestamp, level, worker and message, so
+//! failures that scroll off the dashboard can be read later. When the file would grow past
+//! [`LOG_FILE_MAX_BYTES`] it is rotated to `nexus.log.1`, older rotations move up to
+//! `nexus.log.4`, and the oldest is removed.
+
+use crate::consts::cli_consts::FAILED_WRITE_RETRY_SECS;
+use crate::events::{Event, EventType, Worker, tap_events};
+use crate::logging::LogLevel;
+use crate::storage::{describe_write_error, rotate_file};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+/// Size at which the log file is rotated
+pub const LOG_FILE_MAX_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Log files kept, counting the one being written
+pub const LOG_FILE_COUNT: usize = 5;
+
+/// Get the default log file path, typically ~/.nexus/logs/nexus.log.
+pub fn get_default_log_path() -> Result<PathBuf, io::Error> {
+    let home_path = home::home_dir().ok_or(io::Error::new(
+        io::ErrorKind::NotFound,
+        "Home directory not found",
+    ))?;
+    Ok(home_path.join(".nexus").join("logs").join("nexus.log"))
+}
+
+/// One event as a log line: timestamp, level, worker, node (if any) and message
+fn format_log_line(event: &Event) -> String {
+    let worker = match event.worker {
+        Worker::TaskFetcher => "fetcher".to_string(),
+        Worker::Prover(thread_id) => format!("prover-{}", thread_id),
+        Worker::ProofSubmitter => "submitter".to_string(),
+    };
+    let level = format!("{:?}", event.log_level).to_uppercase();
+    match event.node_id {
+        Some(node_id) => format!(
+            "{} {:<5} [{}] [node {}] {}",
+            event.timestamp, level, worker, node_id, event.msg
+        ),
+        None => format!(
+            "{} {:<5} [{}] {}",
+            event.timestamp, level, worker, event.msg
+        ),
+    }
+}
+
+/// A log file that rotates once it reaches `max_bytes`, keeping `count` files in total
+struct RotatingLog {
+    path: PathBuf,
+    file: File,
+    /// Bytes in the current file
+    size: u64,
+    max_bytes: u64,
+    count: usize,
+}
+
+impl RotatingLog {
+    fn open(path: &Path, max_bytes: u64, count: usize) -> io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let size = file.metadata()?.len();
+        Ok(Self {
+            path: path.to_path_buf(),
+            file,
+            size,
+            max_bytes,
+            count,
+        })
+    }
+
+    fn write_line(&mut self, line: &str) -> io::Result<()> {
+        let len = line.len() as u64 + 1;
+        if self.size > 0 && self.size + len > self.max_bytes {
+            self.rotate()?;
+        }
+        writeln!(self.file, "{}", line)?;
+        self.size += len;
+        Ok(())
+    }
+
+    /// Rotate the current file away and start a new one at `path`
+    fn rotate(&mut self) -> io::Result<()> {
+        rotate_file(&self.path, self.count)?;
+        self.file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+/// Append every event from `receiver` to the log file at `path` on its way through
+/// [`tap_events`]. Like event recording, a failed write is reported once and retried
+/// after [`FAILED_WRITE_RETRY_SECS`]; the session is never interrupted.
+pub fn log_events(
+    receiver: mpsc::Receiver<Event>,
+    path: &Path,
+) -> io::Result<mpsc::Receiver<Event>> {
+    let mut log = RotatingLog::open(path, LOG_FILE_MAX_BYTES, LOG_FILE_COUNT)?;
+
+    // When the last write failed, and whether that has been reported
+    let mut failed_at: Option<Instant> = None;
+    Ok(tap_events(receiver, move |event| {
+        let retry_due =
+            failed_at.is_none_or(|at| at.elapsed() >= Duration::from_secs(FAILED_WRITE_RETRY_SECS));
+        if !retry_due {
+            return None;
+        }
+        match log.write_line(&format_log_line(event)) {
+            Ok(()) => {
+                failed_at = None;
+                None
+            }
+            Err(e) => {
+                // Warn on the first failure, not on every retry
+                let first = failed_at.is_none();
+                failed_at = Some(Instant::now());
+                first.then(|| {
+                    Event::task_fetcher_with_level(
+                        format!(
+                            "{} Retrying every {}s.",
+                            describe_write_error("the log file", &e),
+                            FAILED_WRITE_RETRY_SECS
+                        ),
+                        EventType::Error,
+                        LogLevel::Warn,
+                    )
+                })
+            }
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::rotated_path;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_format_log_line() {
+        let event = Event::prover_with_level(
+            2,
+            "Step 3 of 4: Proof generated for task abc".to_string(),
+            EventType::Success,
+            LogLevel::Info,
+        );
+        let line = format_log_line(&event);
+        assert!(line.starts_with(&event.timestamp));
+        assert!(line.ends_with("INFO  [prover-2] Step 3 of 4: Proof generated for task abc"));
+
+        let line = format_log_line(&event.with_node_id(7));
+        assert!(line.contains("[prover-2] [node 7] Step 3"));
+    }
+
+    #[test]
+    fn test_rotation_keeps_bounded_files() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("nexus.log");
+        let mut log = RotatingLog::open(&path, 20, 3).unwrap();
+        for index in 0..6 {
+            log.write_line(&format!("line number {}", index)).unwrap();
+        }
+
+        // Each 14-byte line fills a file, so the newest three are kept
+        assert_eq!(fs::read_to_string(&path).unwrap(), "line number 5\n");
+        assert_eq!(
+            fs::read_to_string(rotated_path(&path, 1)).unwrap(),
+            "line number 4\n"
+        );
+        assert_eq!(
+            fs::read_to_string(rotated_path(&path, 2)).unwrap(),
+            "line number 3\n"
+        );
+        assert!(!rotated_path(&path, 3).exists());
+    }
+
+    #[tokio::test]
+    async fn test_log_events_forwards_unchanged() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("logs").join("nexus.log");
+        let event = Event::task_fetcher_with_level(
+            "Step 1 of 4: Got task abc".to_string(),
+            EventType::Success,
+            LogLevel::Info,
+        );
+
+        let (sender, receiver) = mpsc::channel(8);
+        let mut forwarded = log_events(receiver, &path).unwrap();
+        sender.send(event.clone()).await.unwrap();
+        drop(sender);
+
+        assert_eq!(forwarded.recv().await, Some(event));
+        assert!(forwarded.recv().await.is_none());
+        assert!(
+            fs::read_to_string(&path)
+                .unwrap()
+                .contains("[fetcher] Step 1 of 4: Got task abc")
+        );
+    }
+}
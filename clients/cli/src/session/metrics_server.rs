@@ -0,0 +1,311 @@
+//! Prometheus metrics endpoint
+//!
+//! With `--metrics-port`, a small HTTP server answers `GET /metrics` with the session's
+//! counters (derived from the event stream, like the session summary) and the process's
+//! CPU and memory use in the Prometheus text format. Any other path gets a 404.
+
+use crate::difficulty::difficulty_rank;
+use crate::events::{Event, Worker, tap_events};
+use crate::nexus_orchestrator::TaskDifficulty;
+use crate::session::stats::SessionStats;
+use crate::ui::SystemMetrics;
+use std::fmt::Write as _;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use sysinfo::System;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{broadcast, mpsc};
+
+/// Content type of the Prometheus text exposition format
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Everything a scrape reports, updated from events and refreshed on each scrape
+struct MetricsState {
+    stats: SessionStats,
+    /// Difficulty of the most recently fetched task
+    difficulty: Option<String>,
+    system: SystemMetrics,
+    sysinfo: System,
+}
+
+impl MetricsState {
+    fn observe(&mut self, event: &Event) {
+        self.stats.observe(event);
+        if event.worker == Worker::TaskFetcher {
+            if let Some(difficulty) = &event.difficulty {
+                self.difficulty = Some(difficulty.clone());
+            }
+        }
+    }
+
+    /// Sample CPU and memory use, then render all metrics
+    fn scrape(&mut self) -> String {
+        let previous = self.system.clone();
+        self.system =
+            SystemMetrics::update(&mut self.sysinfo, previous.peak_ram_bytes, Some(&previous));
+        render_metrics(&self.stats, self.difficulty.as_deref(), &self.system)
+    }
+}
+
+/// Append one metric with its HELP and TYPE lines
+fn push_metric(
+    out: &mut String,
+    name: &str,
+    kind: &str,
+    help: &str,
+    value: impl std::fmt::Display,
+) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+    let _ = writeln!(out, "{} {}", name, value);
+}
+
+/// Render the metrics in the Prometheus text format
+fn render_metrics(
+    stats: &SessionStats,
+    difficulty: Option<&str>,
+    system: &SystemMetrics,
+) -> String {
+    let mut out = String::new();
+    push_metric(
+        &mut out,
+        "nexus_tasks_fetched_total",
+        "counter",
+        "Tasks received from the orchestrator.",
+        stats.tasks_fetched,
+    );
+    push_metric(
+        &mut out,
+        "nexus_tasks_submitted_total",
+        "counter",
+        "Proofs accepted by the orchestrator.",
+        stats.proofs_submitted,
+    );
+    push_metric(
+        &mut out,
+        "nexus_success_rate_percent",
+        "gauge",
+        "Submitted proofs as a percentage of fetched tasks, excluding skipped tasks.",
+        stats.zkvm_metrics().success_rate(),
+    );
+    // The level is a label; the value ranks it so dashboards can graph changes
+    if let Some(rank) = difficulty
+        .and_then(TaskDifficulty::from_str_name)
+        .and_then(difficulty_rank)
+    {
+        let _ = writeln!(
+            out,
+            "# HELP nexus_current_difficulty Difficulty of the most recently fetched task, \
+             from 0 (SMALL) upwards."
+        );
+        let _ = writeln!(out, "# TYPE nexus_current_difficulty gauge");
+        let _ = writeln!(
+            out,
+            "nexus_current_difficulty{{difficulty=\"{}\"}} {}",
+            difficulty.unwrap_or_default(),
+            rank
+        );
+    }
+    // Summary of fetch-to-acceptance latencies, so rates over time give the mean
+    let _ = writeln!(
+        out,
+        "# HELP nexus_submission_latency_seconds Time from fetching a task to its proof being \
+         accepted."
+    );
+    let _ = writeln!(out, "# TYPE nexus_submission_latency_seconds summary");
+    let _ = writeln!(
+        out,
+        "nexus_submission_latency_seconds_sum {}",
+        stats.total_latency.as_secs_f64()
+    );
+    let _ = writeln!(
+        out,
+        "nexus_submission_latency_seconds_count {}",
+        stats.timed_submissions
+    );
+    push_metric(
+        &mut out,
+        "nexus_cpu_percent",
+        "gauge",
+        "CPU use of the CLI and its proving subprocesses.",
+        system.cpu_percent,
+    );
+    push_metric(
+        &mut out,
+        "nexus_ram_bytes",
+        "gauge",
+        "Memory use of the CLI and its proving subprocesses.",
+        system.ram_bytes,
+    );
+    push_metric(
+        &mut out,
+        "nexus_uptime_seconds",
+        "gauge",
+        "Time since the session started.",
+        stats.uptime().as_secs(),
+    );
+    out
+}
+
+/// Answer one HTTP request on `stream`
+async fn handle_connection(mut stream: TcpStream, state: Arc<Mutex<MetricsState>>) {
+    let mut buffer = [0u8; 1024];
+    let Ok(read) = stream.read(&mut buffer).await else {
+        return;
+    };
+    let request = String::from_utf8_lossy(&buffer[..read]);
+    let mut request_line = request
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .split_whitespace();
+    let (method, path) = (request_line.next(), request_line.next());
+
+    let response = if method == Some("GET") && path == Some("/metrics") {
+        let body = match state.lock() {
+            Ok(mut state) => state.scrape(),
+            Err(_) => return,
+        };
+        format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            CONTENT_TYPE,
+            body.len(),
+            body
+        )
+    } else {
+        "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string()
+    };
+    let _ = stream.write_all(response.as_bytes()).await;
+    let _ = stream.shutdown().await;
+}
+
+/// Serve metrics derived from the events in `receiver` on `addr` until `shutdown` fires,
+/// observing each event on its way through [`tap_events`].
+pub fn serve_metrics(
+    receiver: mpsc::Receiver<Event>,
+    addr: SocketAddr,
+    mut shutdown: broadcast::Receiver<()>,
+) -> io::Result<mpsc::Receiver<Event>> {
+    // Bind synchronously so a port already in use is reported before the session starts
+    let listener = std::net::TcpListener::bind(addr)?;
+    listener.set_nonblocking(true)?;
+    let listener = TcpListener::from_std(listener)?;
+
+    let state = Arc::new(Mutex::new(MetricsState {
+        stats: SessionStats::new(),
+        difficulty: None,
+        system: SystemMetrics::default(),
+        sysinfo: System::new(),
+    }));
+
+    let server_state = state.clone();
+    tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                _ = shutdown.recv() => break,
+                accepted = listener.accept() => {
+                    if let Ok((stream, _)) = accepted {
+                        tokio::spawn(handle_connection(stream, server_state.clone()));
+                    }
+                }
+            }
+        }
+    });
+
+    Ok(tap_events(receiver, move |event| {
+        if let Ok(mut state) = state.lock() {
+            state.observe(event);
+        }
+        None
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::EventType;
+    use crate::logging::LogLevel;
+
+    #[test]
+    fn test_render_metrics() {
+        let mut stats = SessionStats::new();
+        stats.observe(&Event::task_fetcher_with_level(
+            "Step 1 of 4: Got task abc".to_string(),
+            EventType::Success,
+            LogLevel::Info,
+        ));
+        stats.observe(
+            &Event::proof_submitter_with_level(
+                "Step 4 of 4: Proof submitted for task abc".to_string(),
+                EventType::Success,
+                LogLevel::Info,
+            )
+            .with_latency(std::time::Duration::from_millis(2_500)),
+        );
+        let system = SystemMetrics {
+            cpu_percent: 12.5,
+            ram_bytes: 2048,
+            ..Default::default()
+        };
+
+        let text = render_metrics(&stats, Some("MEDIUM"), &system);
+        assert!(
+            text.contains(
+                "# TYPE nexus_tasks_fetched_total counter\nnexus_tasks_fetched_total 1\n"
+            )
+        );
+        assert!(text.contains("\nnexus_tasks_submitted_total 1\n"));
+        assert!(text.contains("\nnexus_success_rate_percent 100\n"));
+        assert!(text.contains("\nnexus_current_difficulty{difficulty=\"MEDIUM\"} 2\n"));
+        assert!(text.contains("\nnexus_submission_latency_seconds_sum 2.5\n"));
+        assert!(text.contains("\nnexus_submission_latency_seconds_count 1\n"));
+        assert!(text.contains("\nnexus_cpu_percent 12.5\n"));
+        assert!(text.contains("\nnexus_ram_bytes 2048\n"));
+
+        // No difficulty is reported before the first task
+        let text = render_metrics(&stats, None, &system);
+        assert!(!text.contains("nexus_current_difficulty"));
+    }
+
+    #[tokio::test]
+    async fn test_serves_metrics_over_http() {
+        let (shutdown_sender, _) = broadcast::channel(1);
+        let (sender, receiver) = mpsc::channel(8);
+        // Port 0 can't be scraped by address, so pick a free port first
+        let addr = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let mut forwarded = serve_metrics(receiver, addr, shutdown_sender.subscribe()).unwrap();
+
+        let event = Event::task_fetcher_with_level(
+            "Step 1 of 4: Got task abc".to_string(),
+            EventType::Success,
+            LogLevel::Info,
+        )
+        .with_difficulty(TaskDifficulty::Large);
+        sender.send(event.clone()).await.unwrap();
+        assert_eq!(forwarded.recv().await, Some(event));
+
+        let get = |path: &'static str| async move {
+            let mut stream = TcpStream::connect(addr).await.unwrap();
+            stream
+                .write_all(format!("GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path).as_bytes())
+                .await
+                .unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).await.unwrap();
+            response
+        };
+
+        let response = get("/metrics").await;
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.contains("nexus_tasks_fetched_total 1\n"));
+        assert!(response.contains("nexus_current_difficulty{difficulty=\"LARGE\"} 3\n"));
+
+        assert!(get("/").await.starts_with("HTTP/1.1 404"));
+        let _ = shutdown_sender.send(());
+    }
+}
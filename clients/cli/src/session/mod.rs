@@ -1,8 +1,25 @@
+pub mod benchmark;
+pub mod event_socket;
 pub mod headless_mode;
+pub mod log_file;
 pub mod messages;
+pub mod metrics_server;
+pub mod monitor_mode;
+pub mod probe;
+pub mod recorder;
+pub mod replay_mode;
+pub mod restart;
+pub mod self_test;
 pub mod setup;
+pub mod signals;
+pub mod stats;
+pub mod status;
+pub mod submit_saved;
+pub mod summary;
+pub mod throttle;
 pub mod tui_mode;
 
 pub use headless_mode::run_headless_mode;
-pub use setup::{SessionData, setup_session};
+pub use replay_mode::run_replay_mode;
+pub use setup::{SessionData, SessionOptions, setup_session};
 pub use tui_mode::run_tui_mode;
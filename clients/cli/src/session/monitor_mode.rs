@@ -0,0 +1,137 @@
+//! Read-only dashboard for a running instance
+//!
+//! Connects to the Unix socket of a session started with `--event-socket` and feeds its
+//! events into the same dashboard as a live session. Nothing is fetched or proved, and
+//! quitting the monitor leaves the monitored instance running.
+
+use crate::events::Event;
+use std::error::Error;
+use std::path::Path;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt};
+use tokio::sync::mpsc;
+
+/// Forward events read as JSON lines from `reader` to `sender` until the stream ends.
+/// Lines that aren't events, e.g. from a newer or older version, are skipped.
+#[cfg_attr(not(unix), allow(dead_code))]
+async fn forward_event_lines<R: AsyncBufRead + Unpin>(reader: R, sender: mpsc::Sender<Event>) {
+    let mut lines = reader.lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        let Ok(event) = serde_json::from_str::<Event>(&line) else {
+            continue;
+        };
+        if sender.send(event).await.is_err() {
+            break;
+        }
+    }
+}
+
+/// Watch a running instance in the dashboard. Press `q` to exit.
+///
+/// # Arguments
+/// * `socket` - Path passed to `--event-socket` by the monitored instance
+/// * `with_background` - Whether to enable background colors
+#[cfg(unix)]
+pub async fn run_monitor_mode(socket: &Path, with_background: bool) -> Result<(), Box<dyn Error>> {
+    use super::summary::ShutdownReason;
+    use crate::consts::cli_consts::EVENT_QUEUE_SIZE;
+    use crate::environment::Environment;
+    use crate::events::EventType;
+    use crate::logging::LogLevel;
+    use crate::ui::{self, UIConfig};
+    use crossterm::{
+        event::{DisableMouseCapture, EnableMouseCapture},
+        execute,
+        terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+    };
+    use ratatui::{Terminal, backend::CrosstermBackend};
+    use std::io;
+    use tokio::io::BufReader;
+    use tokio::net::UnixStream;
+    use tokio::sync::broadcast;
+
+    let stream = UnixStream::connect(socket).await.map_err(|e| {
+        format!(
+            "Could not connect to {}: {} (is `nexus-cli start --event-socket` running?)",
+            socket.display(),
+            e
+        )
+    })?;
+
+    let (event_sender, event_receiver) = mpsc::channel(EVENT_QUEUE_SIZE);
+    tokio::spawn(async move {
+        forward_event_lines(BufReader::new(stream), event_sender.clone()).await;
+        let _ = event_sender
+            .send(Event::task_fetcher_with_level(
+                "Monitored instance closed the event stream".to_string(),
+                EventType::Error,
+                LogLevel::Warn,
+            ))
+            .await;
+    });
+
+    // Quitting only stops the monitor; the monitored instance has its own shutdown
+    let (shutdown_sender, _) = broadcast::channel(1);
+    let (max_tasks_shutdown_sender, _) = broadcast::channel::<ShutdownReason>(1);
+
+    // Terminal setup
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend)?;
+
+    let mut app = ui::App::new(
+        None,
+        Environment::default(),
+        event_receiver,
+        shutdown_sender,
+        max_tasks_shutdown_sender.subscribe(),
+        UIConfig::new(with_background, 1, false, None),
+    );
+
+    let result = ui::run(&mut terminal, &mut app).await;
+
+    // Clean up the terminal after running the application
+    disable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        LeaveAlternateScreen,
+        DisableMouseCapture
+    )?;
+    terminal.show_cursor()?;
+
+    result?;
+    Ok(())
+}
+
+/// Unix domain sockets are only available on Unix platforms.
+#[cfg(not(unix))]
+pub async fn run_monitor_mode(
+    _socket: &Path,
+    _with_background: bool,
+) -> Result<(), Box<dyn Error>> {
+    Err("monitor is only available on Unix platforms".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::EventType;
+    use crate::logging::LogLevel;
+
+    #[tokio::test]
+    async fn test_forwards_events_and_skips_other_lines() {
+        let event = Event::task_fetcher_with_level(
+            "Step 1 of 4: Got task abc".to_string(),
+            EventType::Success,
+            LogLevel::Info,
+        );
+        let input = format!("not json\n{}\n", serde_json::to_string(&event).unwrap());
+
+        let (sender, mut receiver) = mpsc::channel(8);
+        forward_event_lines(input.as_bytes(), sender).await;
+
+        assert_eq!(receiver.recv().await, Some(event));
+        assert_eq!(receiver.recv().await, None);
+    }
+}
@@ -0,0 +1,222 @@
+//! One-shot difficulty calibration
+//!
+//! Proves one task at each difficulty level, from the smallest upwards, until a
+//! proof takes longer than the time budget, then recommends a `--max-difficulty`. The
+//! recommendation is saved and caps adaptive difficulty on later runs.
+
+use crate::consts::cli_consts::EVENT_QUEUE_SIZE;
+use crate::difficulty::DIFFICULTY_LEVELS;
+use crate::environment::Environment;
+use crate::events::Event;
+use crate::nexus_orchestrator::TaskDifficulty;
+use crate::orchestrator::OrchestratorClient;
+use crate::storage::describe_write_error;
+use crate::system::{describe_cpu_features, prover_cpu_features};
+use crate::workers::core::{EventSender, WorkerConfig};
+use crate::workers::difficulty_state::{DifficultyCap, get_difficulty_cap_path};
+use crate::workers::fetcher::TaskFetcher;
+use crate::workers::prover::TaskProver;
+use crate::workers::submitter::ProofSubmitter;
+use crate::{print_cmd_info, print_cmd_warn};
+use ed25519_dalek::SigningKey;
+use std::error::Error;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+/// Outcome of proving one task at a requested difficulty
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProbeResult {
+    pub requested: TaskDifficulty,
+    pub assigned: TaskDifficulty,
+    pub duration: Duration,
+}
+
+/// Highest requested difficulty whose proof was assigned as requested and
+/// finished within the budget.
+fn recommend_max_difficulty(results: &[ProbeResult], budget: Duration) -> Option<TaskDifficulty> {
+    results
+        .iter()
+        .take_while(|result| result.duration <= budget)
+        .filter(|result| result.assigned == result.requested)
+        .map(|result| result.requested)
+        .last()
+}
+
+/// Prove one task per difficulty level until proofs exceed `budget`, printing
+/// the timings and a recommended `--max-difficulty`, which is saved as the node's
+/// difficulty cap. Proofs are submitted as usual, so the calibration work still counts.
+pub async fn run_difficulty_probe(
+    node_id: u64,
+    orchestrator: OrchestratorClient,
+    environment: Environment,
+    client_id: String,
+    num_workers: usize,
+    budget: Duration,
+) -> Result<(), Box<dyn Error>> {
+    print_cmd_info!(
+        "Difficulty probe",
+        "Proving one task per difficulty level until a proof takes longer than {}s",
+        budget.as_secs()
+    );
+    print_cmd_info!(
+        "CPU features",
+        "{}",
+        describe_cpu_features(&prover_cpu_features())
+    );
+
+    // Print worker events as they arrive, like headless mode
+    let (sender, mut receiver) = mpsc::channel::<Event>(EVENT_QUEUE_SIZE);
+    let printer = tokio::spawn(async move {
+        while let Some(event) = receiver.recv().await {
+            if event.should_display() {
+                println!("{}", event);
+            }
+        }
+    });
+    let event_sender = EventSender::new(sender);
+
+    let signing_key = SigningKey::generate(&mut rand_core::OsRng);
+    let mut config = WorkerConfig::new(environment, client_id);
+    config.num_workers = num_workers;
+    let mut prover = TaskProver::new(event_sender.clone(), config.clone());
+    let mut submitter = ProofSubmitter::new(
+        node_id,
+        signing_key.clone(),
+        Box::new(orchestrator.clone()),
+        event_sender.clone(),
+        &config,
+    );
+
+    let mut results = Vec::new();
+    for (name, level) in DIFFICULTY_LEVELS {
+        config.max_difficulty = Some(*level);
+        let mut fetcher = TaskFetcher::new(
+            node_id,
+            signing_key.verifying_key(),
+            Box::new(orchestrator.clone()),
+            event_sender.clone(),
+            &config,
+        );
+
+        let task = match fetcher.fetch_task().await {
+            Ok(task) => task,
+            Err(e) => {
+                print_cmd_warn!("Difficulty probe", "Stopping at {}: {}", name, e);
+                break;
+            }
+        };
+
+        let started = Instant::now();
+        let proof_result = match prover.prove_task(&task).await {
+            Ok(proof_result) => proof_result,
+            Err(e) => {
+                print_cmd_warn!("Difficulty probe", "Stopping at {}: {}", name, e);
+                break;
+            }
+        };
+        let result = ProbeResult {
+            requested: *level,
+            assigned: task.difficulty,
+            duration: started.elapsed(),
+        };
+        let _ = submitter.submit_proof(&task, &proof_result).await;
+
+        print_cmd_info!(
+            "Difficulty probe",
+            "{}: proved in {}s (assigned {})",
+            name,
+            result.duration.as_secs(),
+            result.assigned.as_str_name()
+        );
+        results.push(result);
+
+        if result.duration > budget {
+            break;
+        }
+        if result.assigned != result.requested {
+            // The server won't hand out this level yet; higher ones won't be reachable either
+            print_cmd_warn!(
+                "Difficulty probe",
+                "Server assigned {} instead of {}, stopping",
+                result.assigned.as_str_name(),
+                name
+            );
+            break;
+        }
+    }
+
+    drop(event_sender);
+    drop(prover);
+    drop(submitter);
+    let _ = printer.await;
+
+    match recommend_max_difficulty(&results, budget) {
+        Some(difficulty) => {
+            print_cmd_info!(
+                "Difficulty probe",
+                "Recommended: --max-difficulty {}",
+                difficulty.as_str_name()
+            );
+            let path = get_difficulty_cap_path(node_id)?;
+            match DifficultyCap::new(difficulty, budget.as_secs()).save(&path) {
+                Ok(()) => print_cmd_info!(
+                    "Difficulty probe",
+                    "Saved {} as the adaptive difficulty cap; `start` won't request harder tasks unless given --max-difficulty or --ignore-difficulty-cap",
+                    difficulty.as_str_name()
+                ),
+                Err(e) => print_cmd_warn!(
+                    "Difficulty probe",
+                    "{}",
+                    describe_write_error("the difficulty cap", &e)
+                ),
+            }
+        }
+        None => print_cmd_warn!(
+            "Difficulty probe",
+            "No difficulty finished within {}s; consider --max-difficulty SMALL",
+            budget.as_secs()
+        ),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(requested: TaskDifficulty, assigned: TaskDifficulty, secs: u64) -> ProbeResult {
+        ProbeResult {
+            requested,
+            assigned,
+            duration: Duration::from_secs(secs),
+        }
+    }
+
+    #[test]
+    fn test_recommend_stops_at_budget() {
+        let budget = Duration::from_secs(420);
+        let results = [
+            result(TaskDifficulty::Small, TaskDifficulty::Small, 30),
+            result(TaskDifficulty::SmallMedium, TaskDifficulty::SmallMedium, 90),
+            result(TaskDifficulty::Medium, TaskDifficulty::Medium, 600),
+        ];
+        assert_eq!(
+            recommend_max_difficulty(&results, budget),
+            Some(TaskDifficulty::SmallMedium)
+        );
+    }
+
+    #[test]
+    fn test_recommend_ignores_gated_levels() {
+        let budget = Duration::from_secs(420);
+        let results = [
+            result(TaskDifficulty::Small, TaskDifficulty::Small, 30),
+            result(TaskDifficulty::SmallMedium, TaskDifficulty::Small, 30),
+        ];
+        assert_eq!(
+            recommend_max_difficulty(&results, budget),
+            Some(TaskDifficulty::Small)
+        );
+        assert_eq!(recommend_max_difficulty(&[], budget), None);
+    }
+}
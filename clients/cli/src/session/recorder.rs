@@ -0,0 +1,136 @@
+//! Event recording and replay
+//!
+//! Events are stored as JSON, one per line, so a session recorded with
+//! `--record-events` can be fed back into the dashboard with `replay-events`.
+
+use crate::consts::cli_consts::FAILED_WRITE_RETRY_SECS;
+use crate::events::{Event, EventType, tap_events};
+use crate::logging::LogLevel;
+use crate::storage::describe_write_error;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+/// Append every event from `receiver` to the file at `path` on its way through
+/// [`tap_events`]. If a write fails (e.g. the disk is full), a warning is forwarded and
+/// recording pauses for [`FAILED_WRITE_RETRY_SECS`] before it is retried; the session is
+/// never interrupted.
+pub fn record_events(
+    receiver: mpsc::Receiver<Event>,
+    path: &Path,
+) -> io::Result<mpsc::Receiver<Event>> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+
+    // When the last write failed, and whether that has been reported
+    let mut failed_at: Option<Instant> = None;
+    Ok(tap_events(receiver, move |event| {
+        let retry_due =
+            failed_at.is_none_or(|at| at.elapsed() >= Duration::from_secs(FAILED_WRITE_RETRY_SECS));
+        if !retry_due {
+            return None;
+        }
+        let line = serde_json::to_string(event).ok()?;
+        // Recording is best-effort; never interrupt the session over it
+        match writeln!(file, "{}", line) {
+            Ok(()) => {
+                failed_at = None;
+                None
+            }
+            Err(e) => {
+                // Warn on the first failure, not on every retry
+                let first = failed_at.is_none();
+                failed_at = Some(Instant::now());
+                first.then(|| {
+                    Event::task_fetcher_with_level(
+                        format!(
+                            "{} Retrying every {}s.",
+                            describe_write_error("the event recording", &e),
+                            FAILED_WRITE_RETRY_SECS
+                        ),
+                        EventType::Error,
+                        LogLevel::Warn,
+                    )
+                })
+            }
+        }
+    }))
+}
+
+/// Load events previously written by `record_events`.
+pub fn load_recorded_events(path: &Path) -> io::Result<Vec<Event>> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut events = Vec::new();
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let event = serde_json::from_str(&line).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Invalid event on line {}: {}", index + 1, e),
+            )
+        })?;
+        events.push(event);
+    }
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{EventType, ProverState};
+    use crate::logging::LogLevel;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_recorded_events_round_trip() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("events.jsonl");
+
+        let events = vec![
+            Event::task_fetcher_with_level(
+                "Step 1 of 4: Got task abc".to_string(),
+                EventType::Success,
+                LogLevel::Info,
+            ),
+            Event::prover_with_level(
+                2,
+                "Step 3 of 4: Proof generated for task abc".to_string(),
+                EventType::Success,
+                LogLevel::Info,
+            ),
+            Event::state_change(ProverState::Waiting, "ready".to_string()),
+        ];
+
+        let (sender, receiver) = mpsc::channel(8);
+        let mut forwarded = record_events(receiver, &path).unwrap();
+        for event in &events {
+            sender.send(event.clone()).await.unwrap();
+        }
+        drop(sender);
+
+        // Every event is forwarded unchanged
+        for event in &events {
+            assert_eq!(forwarded.recv().await.as_ref(), Some(event));
+        }
+        assert!(forwarded.recv().await.is_none());
+
+        assert_eq!(load_recorded_events(&path).unwrap(), events);
+    }
+
+    #[test]
+    fn test_load_rejects_invalid_line() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("events.jsonl");
+        fs::write(&path, "not json\n").unwrap();
+
+        let err = load_recorded_events(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,66 @@
+//! Replay of recorded events in the TUI
+
+use super::recorder::load_recorded_events;
+use super::summary::ShutdownReason;
+use crate::environment::Environment;
+use crate::ui::{self, UIConfig};
+use crossterm::{
+    event::{DisableMouseCapture, EnableMouseCapture},
+    execute,
+    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+};
+use ratatui::{Terminal, backend::CrosstermBackend};
+use std::path::Path;
+use std::{error::Error, io};
+use tokio::sync::{broadcast, mpsc};
+
+/// Replays a recorded event log through the dashboard
+///
+/// The events are fed into the same update and render logic as a live session,
+/// so the dashboard ends up in the state the recording user saw. Press `q` to exit.
+///
+/// # Arguments
+/// * `path` - Path to a file written with `--record-events`
+/// * `with_background` - Whether to enable background colors
+pub async fn run_replay_mode(path: &Path, with_background: bool) -> Result<(), Box<dyn Error>> {
+    let events = load_recorded_events(path)?;
+
+    let (event_sender, event_receiver) = mpsc::channel(events.len().max(1));
+    for event in events {
+        event_sender.send(event).await?;
+    }
+
+    // Nothing to shut down, but the app expects both channels
+    let (shutdown_sender, _) = broadcast::channel(1);
+    let (max_tasks_shutdown_sender, _) = broadcast::channel::<ShutdownReason>(1);
+
+    // Terminal setup
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend)?;
+
+    let mut app = ui::App::new(
+        None,
+        Environment::default(),
+        event_receiver,
+        shutdown_sender,
+        max_tasks_shutdown_sender.subscribe(),
+        UIConfig::new(with_background, 1, false, None),
+    );
+
+    let result = ui::run(&mut terminal, &mut app).await;
+
+    // Clean up the terminal after running the application
+    disable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        LeaveAlternateScreen,
+        DisableMouseCapture
+    )?;
+    terminal.show_cursor()?;
+
+    result?;
+    Ok(())
+}
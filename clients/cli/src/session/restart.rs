@@ -0,0 +1,83 @@
+//! `--max-uptime` and `--restart-on-max-uptime`
+//!
+//! Long-running nodes can be recycled periodically: once the uptime limit is reached,
+//! workers finish the task in hand, the session ends normally, and the binary can then
+//! replace itself with a fresh process started with the same arguments.
+
+use std::error::Error;
+use std::process::Command;
+use std::time::Duration;
+
+/// Parse a `--max-uptime` value: a number with an optional `s`, `m`, `h` or `d` unit,
+/// e.g. `90`, `30m` or `12h`. Plain numbers are seconds.
+pub fn parse_max_uptime(value: &str) -> Result<Duration, String> {
+    let value = value.trim().to_lowercase();
+    let (number, unit_secs) = match value.char_indices().last() {
+        Some((i, 's')) => (&value[..i], 1),
+        Some((i, 'm')) => (&value[..i], 60),
+        Some((i, 'h')) => (&value[..i], 60 * 60),
+        Some((i, 'd')) => (&value[..i], 24 * 60 * 60),
+        _ => (value.as_str(), 1),
+    };
+    let number: u64 = number.trim().parse().map_err(|_| {
+        format!(
+            "invalid duration '{}' (expected e.g. 90s, 30m, 12h or 2d)",
+            value
+        )
+    })?;
+    if number == 0 {
+        return Err("duration must be positive".to_string());
+    }
+    number
+        .checked_mul(unit_secs)
+        .map(Duration::from_secs)
+        .ok_or_else(|| format!("duration '{}' is too long", value))
+}
+
+/// Replace this process with a fresh copy of the binary, started with the same arguments.
+/// Only returns on failure, except on platforms without `exec`, where the new process is
+/// spawned and this one exits.
+pub fn restart_current_process() -> Result<(), Box<dyn Error>> {
+    let exe = std::env::current_exe()?;
+    let mut command = Command::new(exe);
+    command.args(std::env::args_os().skip(1));
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        // `exec` only returns if it failed
+        Err(command.exec().into())
+    }
+    #[cfg(not(unix))]
+    {
+        command.spawn()?;
+        std::process::exit(0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_max_uptime() {
+        assert_eq!(parse_max_uptime("90").unwrap(), Duration::from_secs(90));
+        assert_eq!(parse_max_uptime("90s").unwrap(), Duration::from_secs(90));
+        assert_eq!(
+            parse_max_uptime("30m").unwrap(),
+            Duration::from_secs(30 * 60)
+        );
+        assert_eq!(
+            parse_max_uptime("12H").unwrap(),
+            Duration::from_secs(12 * 60 * 60)
+        );
+        assert_eq!(
+            parse_max_uptime("2d").unwrap(),
+            Duration::from_secs(2 * 24 * 60 * 60)
+        );
+        assert!(parse_max_uptime("0h").is_err());
+        assert!(parse_max_uptime("h").is_err());
+        assert!(parse_max_uptime("1.5h").is_err());
+        assert!(parse_max_uptime("-1").is_err());
+    }
+}
@@ -0,0 +1,306 @@
+//! Release smoke test
+//!
+//! Runs a couple of fib tasks through the real fetch → prove → verify → submit
+//! pipeline against an in-process orchestrator, so packaged binaries can be
+//! checked without network access or `cargo test`.
+
+use crate::analytics::set_enabled_analytics_events;
+use crate::consts::cli_consts::EVENT_QUEUE_SIZE;
+use crate::environment::Environment;
+use crate::events::Event;
+use crate::nexus_orchestrator::{TaskDifficulty, TaskType};
+use crate::orchestrator::Orchestrator;
+use crate::orchestrator::client::ProofTaskResult;
+use crate::orchestrator::error::OrchestratorError;
+use crate::system::{describe_cpu_features, prover_cpu_features};
+use crate::task::Task;
+use crate::workers::core::{EventSender, WorkerConfig};
+use crate::workers::fetcher::TaskFetcher;
+use crate::workers::prover::TaskProver;
+use crate::workers::submitter::ProofSubmitter;
+use crate::{print_cmd_error, print_cmd_info};
+use ed25519_dalek::{SigningKey, VerifyingKey};
+use std::collections::{HashMap, VecDeque};
+use std::error::Error;
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc;
+
+/// Node ID used for self-test requests; never sent to a real orchestrator
+const SELF_TEST_NODE_ID: u64 = 0;
+
+/// A proof received by the in-process orchestrator
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ReceivedProof {
+    proof_hash: String,
+    num_proofs: usize,
+}
+
+/// In-process orchestrator that serves a fixed list of tasks and records submissions.
+/// Clones share the same task queue and submissions.
+#[derive(Clone)]
+struct SelfTestOrchestrator {
+    environment: Environment,
+    tasks: Arc<Mutex<VecDeque<Task>>>,
+    received: Arc<Mutex<HashMap<String, ReceivedProof>>>,
+}
+
+impl SelfTestOrchestrator {
+    fn new(tasks: Vec<Task>) -> Self {
+        Self {
+            environment: Environment::default(),
+            tasks: Arc::new(Mutex::new(tasks.into())),
+            received: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Handle for inspecting submissions after the orchestrator is boxed
+    fn received(&self) -> Arc<Mutex<HashMap<String, ReceivedProof>>> {
+        self.received.clone()
+    }
+
+    fn not_available() -> OrchestratorError {
+        OrchestratorError::Http {
+            status: 404,
+            message: "Not available in self-test".to_string(),
+            headers: HashMap::new(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Orchestrator for SelfTestOrchestrator {
+    fn environment(&self) -> &Environment {
+        &self.environment
+    }
+
+    async fn get_user(&self, _wallet_address: &str) -> Result<String, OrchestratorError> {
+        Err(Self::not_available())
+    }
+
+    async fn register_user(
+        &self,
+        _user_id: &str,
+        _wallet_address: &str,
+    ) -> Result<(), OrchestratorError> {
+        Err(Self::not_available())
+    }
+
+    async fn register_node(&self, _user_id: &str) -> Result<String, OrchestratorError> {
+        Err(Self::not_available())
+    }
+
+    async fn get_node(&self, _node_id: &str) -> Result<String, OrchestratorError> {
+        Err(Self::not_available())
+    }
+
+    async fn get_proof_task(
+        &self,
+        _node_id: &str,
+        _verifying_key: VerifyingKey,
+        _max_difficulty: TaskDifficulty,
+    ) -> Result<ProofTaskResult, OrchestratorError> {
+        let task = self
+            .tasks
+            .lock()
+            .unwrap()
+            .pop_front()
+            .ok_or_else(Self::not_available)?;
+        Ok(ProofTaskResult {
+            actual_difficulty: task.difficulty,
+            task,
+            max_concurrent_tasks: None,
+        })
+    }
+
+    async fn submit_proof(
+        &self,
+        task_id: &str,
+        proof_hash: &str,
+        _proof: Vec<u8>,
+        proofs: Vec<Vec<u8>>,
+        _signing_key: SigningKey,
+        _num_provers: usize,
+        _task_type: TaskType,
+        _individual_proof_hashes: &[String],
+    ) -> Result<(), OrchestratorError> {
+        self.received.lock().unwrap().insert(
+            task_id.to_string(),
+            ReceivedProof {
+                proof_hash: proof_hash.to_string(),
+                num_proofs: proofs.len(),
+            },
+        );
+        Ok(())
+    }
+}
+
+/// Encode fib inputs the way the orchestrator does: three little-endian u32s
+fn fib_inputs(n: u32, init_a: u32, init_b: u32) -> Vec<u8> {
+    [n, init_a, init_b]
+        .iter()
+        .flat_map(|value| value.to_le_bytes())
+        .collect()
+}
+
+/// The tasks served during the self-test: one of each submission shape
+fn self_test_tasks() -> Vec<Task> {
+    vec![
+        Task::new(
+            "self-test-proof-hash".to_string(),
+            "fib_input_initial".to_string(),
+            fib_inputs(9, 1, 1),
+            TaskType::ProofHash,
+            TaskDifficulty::Small,
+        ),
+        Task::new(
+            "self-test-proof-required".to_string(),
+            "fib_input_initial".to_string(),
+            fib_inputs(12, 0, 1),
+            TaskType::ProofRequired,
+            TaskDifficulty::Small,
+        ),
+    ]
+}
+
+/// Check that every task was submitted with a hash, and with a proof where one is required
+fn check_submissions(
+    tasks: &[Task],
+    received: &HashMap<String, ReceivedProof>,
+) -> Result<(), String> {
+    for task in tasks {
+        let proof = received
+            .get(&task.task_id)
+            .ok_or_else(|| format!("no proof submitted for task {}", task.task_id))?;
+        if proof.proof_hash.is_empty() {
+            return Err(format!("empty proof hash for task {}", task.task_id));
+        }
+        if task.task_type == TaskType::ProofRequired && proof.num_proofs == 0 {
+            return Err(format!("no proof attached for task {}", task.task_id));
+        }
+    }
+    Ok(())
+}
+
+/// Run the smoke test, printing PASS or FAIL. Returns an error on failure so the
+/// process exits non-zero.
+pub async fn run_self_test() -> Result<(), Box<dyn Error>> {
+    print_cmd_info!(
+        "Self-test",
+        "Proving {} fib tasks against an in-process orchestrator",
+        self_test_tasks().len()
+    );
+
+    print_cmd_info!(
+        "CPU features",
+        "{}",
+        describe_cpu_features(&prover_cpu_features())
+    );
+
+    // Nothing from the self-test should reach analytics
+    set_enabled_analytics_events(Some(Vec::new()));
+
+    let (sender, mut receiver) = mpsc::channel::<Event>(EVENT_QUEUE_SIZE);
+    let printer = tokio::spawn(async move {
+        while let Some(event) = receiver.recv().await {
+            if event.should_display() {
+                println!("{}", event);
+            }
+        }
+    });
+    let event_sender = EventSender::new(sender);
+
+    let tasks = self_test_tasks();
+    let orchestrator = SelfTestOrchestrator::new(tasks.clone());
+    let received = orchestrator.received();
+
+    let signing_key = SigningKey::generate(&mut rand_core::OsRng);
+    let config = WorkerConfig::new(Environment::default(), "self-test".to_string());
+    let mut fetcher = TaskFetcher::new(
+        SELF_TEST_NODE_ID,
+        signing_key.verifying_key(),
+        Box::new(orchestrator.clone()),
+        event_sender.clone(),
+        &config,
+    );
+    let mut prover = TaskProver::new(event_sender.clone(), config.clone());
+    let mut submitter = ProofSubmitter::new(
+        SELF_TEST_NODE_ID,
+        signing_key,
+        Box::new(orchestrator),
+        event_sender.clone(),
+        &config,
+    );
+
+    let mut outcome = Ok(());
+    for _ in 0..tasks.len() {
+        let step = async {
+            let task = fetcher.fetch_task().await.map_err(|e| e.to_string())?;
+            let proof_result = prover.prove_task(&task).await.map_err(|e| e.to_string())?;
+            submitter
+                .submit_proof(&task, &proof_result)
+                .await
+                .map_err(|e| e.to_string())
+        };
+        if let Err(e) = step.await {
+            outcome = Err(e);
+            break;
+        }
+    }
+    let outcome = outcome.and_then(|()| check_submissions(&tasks, &received.lock().unwrap()));
+
+    drop(fetcher);
+    drop(prover);
+    drop(submitter);
+    drop(event_sender);
+    let _ = printer.await;
+
+    match outcome {
+        Ok(()) => {
+            print_cmd_info!("Self-test", "PASS");
+            Ok(())
+        }
+        Err(e) => {
+            print_cmd_error!("Self-test", &format!("FAIL: {}", e));
+            Err(e.into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fib_inputs_round_trip() {
+        let inputs = fib_inputs(9, 1, 1);
+        assert_eq!(
+            crate::prover::input::InputParser::parse_triple_input(&inputs).unwrap(),
+            (9, 1, 1)
+        );
+    }
+
+    #[test]
+    fn test_check_submissions_requires_proofs() {
+        let tasks = self_test_tasks();
+        let mut received = HashMap::new();
+        for task in &tasks {
+            received.insert(
+                task.task_id.clone(),
+                ReceivedProof {
+                    proof_hash: "abc".to_string(),
+                    num_proofs: 0,
+                },
+            );
+        }
+        assert!(check_submissions(&tasks, &received).is_err());
+
+        received
+            .get_mut("self-test-proof-required")
+            .unwrap()
+            .num_proofs = 1;
+        assert!(check_submissions(&tasks, &received).is_ok());
+
+        received.remove("self-test-proof-hash");
+        assert!(check_submissions(&tasks, &received).is_err());
+    }
+}
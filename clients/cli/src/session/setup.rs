@@ -1,13 +1,25 @@
 //! Session setup and initialization
 
-use crate::analytics::set_wallet_address_for_reporting;
+use crate::analytics::{
+    set_enabled_analytics_events, set_node_label, set_wallet_address_for_reporting,
+};
 use crate::config::Config;
-use crate::environment::Environment;
 use crate::events::Event;
-use crate::orchestrator::OrchestratorClient;
+use crate::orchestrator::{HttpVersion, OrchestratorClient};
 use crate::runtime::start_authenticated_worker;
+use crate::session::event_socket::serve_event_socket;
+use crate::session::log_file::log_events;
+use crate::session::metrics_server::serve_metrics;
+use crate::session::recorder::record_events;
+use crate::session::summary::ShutdownReason;
+use crate::session::throttle::throttle_events;
+use crate::task::TaskTypeFilter;
+use crate::workers::core::WorkerConfig;
 use ed25519_dalek::SigningKey;
 use std::error::Error;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::time::Duration;
 use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
 use tokio::sync::{broadcast, mpsc};
 use tokio::task::JoinHandle;
@@ -21,14 +33,28 @@ pub struct SessionData {
     pub join_handles: Vec<JoinHandle<()>>,
     /// Shutdown sender to stop all workers
     pub shutdown_sender: broadcast::Sender<()>,
-    /// Shutdown sender for max tasks completion
-    pub max_tasks_shutdown_sender: broadcast::Sender<()>,
+    /// Sender the worker uses to end the session (max tasks reached, workers died)
+    pub max_tasks_shutdown_sender: broadcast::Sender<ShutdownReason>,
     /// Node ID
     pub node_id: u64,
     /// Orchestrator client
     pub orchestrator: OrchestratorClient,
     /// Number of workers (for display purposes)
     pub num_workers: usize,
+    /// Task types being proved (for display purposes)
+    pub task_type_filter: TaskTypeFilter,
+}
+
+/// Nodes to prove for: the configured node first, then the additional ones, without
+/// duplicates
+fn session_node_ids(node_id: u64, additional_node_ids: &[u64]) -> Vec<u64> {
+    let mut node_ids = vec![node_id];
+    for &id in additional_node_ids {
+        if !node_ids.contains(&id) {
+            node_ids.push(id);
+        }
+    }
+    node_ids
 }
 
 /// Clamp thread count based on available system memory
@@ -50,9 +76,23 @@ fn clamp_threads_by_memory(requested_threads: usize) -> usize {
     requested_threads.min(max_threads_by_memory.max(1))
 }
 
+/// Largest worker count, at most `requested`, whose projected memory fits in `budget_bytes`.
+/// Always allows at least one worker.
+fn workers_within_memory_budget(requested: usize, budget_bytes: u64) -> usize {
+    let per_worker = crate::consts::cli_consts::PROJECTED_MEMORY_REQUIREMENT;
+    requested.min(((budget_bytes / per_worker) as usize).max(1))
+}
+
+/// Worker count requested on the command line, taking the deprecated `--max-threads`
+/// only when `--workers` isn't set. The deprecated value is clamped to the CPU cores.
+pub fn resolve_workers(workers: Option<u32>, max_threads: Option<u32>) -> Option<u32> {
+    workers
+        .or_else(|| max_threads.map(|threads| threads.clamp(1, crate::system::num_cores() as u32)))
+}
+
 /// Warn the user if their available memory seems insufficient for the task(s) at hand
-pub fn warn_memory_configuration(max_threads: Option<u32>) {
-    if let Some(threads) = max_threads {
+pub fn warn_memory_configuration(workers: Option<u32>) {
+    if let Some(threads) = workers {
         let current_pid = Pid::from(std::process::id() as usize);
 
         let mut sysinfo = System::new();
@@ -68,7 +108,7 @@ pub fn warn_memory_configuration(max_threads: Option<u32>) {
             {
                 crate::print_cmd_warn!(
                     "OOM warning",
-                    "Projected memory usage across {} requested threads exceeds memory currently available to process. In the event that proving fails due to an out-of-memory error, please restart the Nexus CLI with a smaller value supplied to `--max-threads`.",
+                    "Projected memory usage across {} requested threads exceeds memory currently available to process. In the event that proving fails due to an out-of-memory error, please restart the Nexus CLI with a smaller value supplied to `--workers`.",
                     threads
                 );
                 std::thread::sleep(std::time::Duration::from_secs(3));
@@ -77,48 +117,129 @@ pub fn warn_memory_configuration(max_threads: Option<u32>) {
     }
 }
 
+/// Options for a proving session, set from the `start` flags
+#[derive(Clone)]
+pub struct SessionOptions {
+    /// Settings for every node's worker. `client_id` is taken from the resolved config
+    /// and `num_workers` is clamped to the cores and memory during setup.
+    pub worker: WorkerConfig,
+    /// Further nodes to prove for in the same process
+    pub additional_node_ids: Vec<u64>,
+    /// Number of proving workers requested with `--workers`, if any
+    pub workers: Option<u32>,
+    /// Whether to check risky memory usage
+    pub check_mem: bool,
+    /// Stop after proving this many tasks, if set
+    pub max_tasks: Option<u32>,
+    /// Path to append every event to as JSON, if any
+    pub record_events_path: Option<PathBuf>,
+    /// Unix socket path to stream every event to as JSON, if any
+    pub event_socket_path: Option<PathBuf>,
+    /// Path of a rotating plain-text log of every event, if any
+    pub log_file_path: Option<PathBuf>,
+    /// Address to serve Prometheus metrics on, if any
+    pub metrics_addr: Option<SocketAddr>,
+    /// Window within which identical events are coalesced (zero disables)
+    pub dedupe_window: Duration,
+    /// HTTP protocol version for orchestrator requests
+    pub http_version: HttpVersion,
+    /// Time allowed to connect to the orchestrator
+    pub connect_timeout: Duration,
+    /// Time allowed for a whole orchestrator request
+    pub request_timeout: Duration,
+    /// Largest orchestrator response body accepted, in bytes
+    pub max_response_bytes: usize,
+    /// Whether to look up the country for telemetry; off with `--no-geo`
+    pub geo_lookups: bool,
+    /// Whether to persist request rate-limit state across restarts
+    pub persist_rate_limits: bool,
+    /// Label attached to analytics events, if any
+    pub node_label: Option<String>,
+}
+
 /// Sets up an authenticated worker session
 ///
 /// This function handles all the common setup required for both TUI and headless modes:
-/// 1. Creates signing key for the prover
+/// 1. Creates a signing key for each node
 /// 2. Sets up shutdown channel
-/// 3. Starts authenticated worker
+/// 3. Starts an authenticated worker per node
 /// 4. Returns session data for mode-specific handling
 ///
 /// # Arguments
 /// * `config` - Resolved configuration with node_id and client_id
-/// * `env` - Environment to connect to
-/// * `max_threads` - Optional maximum number of threads for proving
-/// * `max_difficulty` - Optional override for task difficulty
+/// * `options` - Session and worker options from the command line
 ///
 /// # Returns
 /// * `Ok(SessionData)` - Successfully set up session
 /// * `Err` - Session setup failed
 pub async fn setup_session(
     config: Config,
-    env: Environment,
-    check_mem: bool,
-    max_threads: Option<u32>,
-    max_tasks: Option<u32>,
-    max_difficulty: Option<crate::nexus_orchestrator::TaskDifficulty>,
+    options: SessionOptions,
 ) -> Result<SessionData, Box<dyn Error>> {
+    let SessionOptions {
+        mut worker,
+        additional_node_ids,
+        workers,
+        check_mem,
+        max_tasks,
+        record_events_path,
+        event_socket_path,
+        log_file_path,
+        metrics_addr,
+        dedupe_window,
+        http_version,
+        connect_timeout,
+        request_timeout,
+        max_response_bytes,
+        geo_lookups,
+        persist_rate_limits,
+        node_label,
+    } = options;
     let node_id = config.node_id.parse::<u64>()?;
-    let client_id = config.user_id;
+    worker.client_id = config.user_id;
 
-    // Create a signing key for the prover
+    // Create a signing key for each node. Keys are generated fresh each session and
+    // the orchestrator learns each one's verifying key from its task requests, so
+    // there is no long-lived key to compare against the node. A key only reaches disk
+    // inside saved proof files (`--save-proofs` or `--on-submit-failure save`), which
+    // are written with 0600 permissions so `submit-saved` can sign them later.
     let mut csprng = rand_core::OsRng;
-    let signing_key: SigningKey = SigningKey::generate(&mut csprng);
+    let nodes: Vec<(u64, SigningKey)> = session_node_ids(node_id, &additional_node_ids)
+        .into_iter()
+        .map(|id| (id, SigningKey::generate(&mut csprng)))
+        .collect();
+
+    if worker.always_attach_proof {
+        crate::print_cmd_warn!(
+            "Diagnostic mode",
+            "Attaching full proofs to proof-hash submissions. This increases bandwidth."
+        );
+    }
+    if worker.skip_local_verify {
+        crate::print_cmd_warn!(
+            "Local verification off",
+            "Proofs are submitted without being verified. Invalid proofs from faulty hardware will be rejected by the orchestrator."
+        );
+    }
 
     // Create orchestrator client
-    let orchestrator_client = OrchestratorClient::new(env.clone());
+    let orchestrator_client = OrchestratorClient::with_timeouts(
+        worker.environment.clone(),
+        http_version,
+        connect_timeout,
+        request_timeout,
+    )
+    .with_always_attach_proof(worker.always_attach_proof)
+    .with_max_response_bytes(max_response_bytes)
+    .with_geo_lookups(geo_lookups);
 
     // Clamp the number of workers to [1, 75% of num_cores]. Leave room for other processes.
     let total_cores = crate::system::num_cores();
     let max_workers = ((total_cores as f64 * 0.75).ceil() as usize).max(1);
-    let mut num_workers: usize = max_threads.unwrap_or(1).clamp(1, max_workers as u32) as usize;
+    let mut num_workers: usize = workers.unwrap_or(1).clamp(1, max_workers as u32) as usize;
 
-    // Check memory and clamp threads if max-threads was explicitly set OR check-memory flag is set
-    if max_threads.is_some() || check_mem {
+    // Check memory and clamp threads if workers was explicitly set OR check-memory flag is set
+    if workers.is_some() || check_mem {
         let memory_clamped_workers = clamp_threads_by_memory(num_workers);
         if memory_clamped_workers < num_workers {
             crate::print_cmd_warn!(
@@ -131,6 +252,32 @@ pub async fn setup_session(
         }
     }
 
+    // Fit the worker count into the memory budget, if one was given
+    if let Some(budget_bytes) = worker.memory_budget_bytes {
+        let budgeted_workers = workers_within_memory_budget(num_workers, budget_bytes);
+        if budgeted_workers < num_workers {
+            crate::print_cmd_warn!(
+                "Memory budget",
+                "Reduced thread count from {} to {} to fit the {} MB memory budget. Each thread requires ~4GB RAM.",
+                num_workers,
+                budgeted_workers,
+                budget_bytes / (1024 * 1024)
+            );
+            num_workers = budgeted_workers;
+        }
+    }
+
+    // The workers are shared between nodes, but each node needs one
+    if nodes.len() > num_workers {
+        crate::print_cmd_warn!(
+            "Workers",
+            "Raised the worker count from {} to {} so each node has one.",
+            num_workers,
+            nodes.len()
+        );
+        num_workers = nodes.len();
+    }
+
     // Additional memory warning if explicitly requested
     if check_mem {
         warn_memory_configuration(Some(num_workers as u32));
@@ -140,22 +287,51 @@ pub async fn setup_session(
     let (shutdown_sender, _) = broadcast::channel(1);
 
     // Set wallet for reporting
-    set_wallet_address_for_reporting(config.wallet_address.clone());
+    set_wallet_address_for_reporting(node_id, config.wallet_address.clone());
+    set_enabled_analytics_events(config.analytics_events.clone());
+    set_node_label(node_label.as_deref());
 
     // Start authenticated worker (only mode we support now)
+    worker.num_workers = num_workers;
+    let task_type_filter = worker.task_type_filter;
     let (event_receiver, join_handles, max_tasks_shutdown_sender) = start_authenticated_worker(
-        node_id,
-        signing_key,
+        nodes,
         orchestrator_client.clone(),
         shutdown_sender.subscribe(),
-        env,
-        client_id,
+        worker,
         max_tasks,
-        max_difficulty,
-        num_workers,
+        persist_rate_limits,
     )
     .await;
 
+    // Optionally record every event for later replay
+    let event_receiver = match record_events_path {
+        Some(path) => record_events(event_receiver, &path)?,
+        None => event_receiver,
+    };
+
+    // Optionally write every event to a rotating log file
+    let event_receiver = match log_file_path {
+        Some(path) => log_events(event_receiver, &path)?,
+        None => event_receiver,
+    };
+
+    // Optionally serve metrics for scraping, until shutdown
+    let event_receiver = match metrics_addr {
+        Some(addr) => serve_metrics(event_receiver, addr, shutdown_sender.subscribe())?,
+        None => event_receiver,
+    };
+
+    // Optionally stream every event to local supervisors
+    let event_receiver = match event_socket_path {
+        Some(path) => serve_event_socket(event_receiver, &path)?,
+        None => event_receiver,
+    };
+
+    // Coalesce repeated events for both TUI and headless output (after recording and
+    // streaming, so those stay complete)
+    let event_receiver = throttle_events(event_receiver, dedupe_window);
+
     Ok(SessionData {
         event_receiver,
         join_handles,
@@ -164,5 +340,40 @@ pub async fn setup_session(
         node_id,
         orchestrator: orchestrator_client,
         num_workers,
+        task_type_filter,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GB: u64 = 1024 * 1024 * 1024;
+
+    #[test]
+    fn test_resolve_workers_maps_deprecated_max_threads() {
+        let cores = crate::system::num_cores() as u32;
+        assert_eq!(resolve_workers(None, None), None);
+        assert_eq!(resolve_workers(Some(2), None), Some(2));
+        // --workers wins over --max-threads
+        assert_eq!(resolve_workers(Some(2), Some(8)), Some(2));
+        // --max-threads alone is used, clamped to the cores
+        assert_eq!(resolve_workers(None, Some(1)), Some(1));
+        assert_eq!(resolve_workers(None, Some(cores + 8)), Some(cores));
+        assert_eq!(resolve_workers(None, Some(0)), Some(1));
+    }
+
+    #[test]
+    fn test_session_node_ids_keep_order_without_duplicates() {
+        assert_eq!(session_node_ids(7, &[]), vec![7]);
+        assert_eq!(session_node_ids(7, &[3, 7, 9, 3]), vec![7, 3, 9]);
+    }
+
+    #[test]
+    fn test_workers_within_memory_budget() {
+        assert_eq!(workers_within_memory_budget(4, 8 * GB), 2);
+        assert_eq!(workers_within_memory_budget(1, 64 * GB), 1);
+        // A budget below one worker's projection still runs one worker
+        assert_eq!(workers_within_memory_budget(4, GB), 1);
+    }
+}
@@ -0,0 +1,60 @@
+//! Runtime control signals
+//!
+//! On Unix, `SIGUSR1` toggles the log level between Info and Debug and `SIGUSR2`
+//! dumps the current metrics. On other platforms no signal is ever delivered.
+
+#[cfg(unix)]
+use tokio::signal::unix::{Signal, SignalKind, signal};
+
+/// A control request delivered by the operator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuntimeSignal {
+    /// Toggle the log level threshold (SIGUSR1)
+    CycleLogLevel,
+    /// Dump the current metrics (SIGUSR2)
+    DumpMetrics,
+}
+
+/// Listener for runtime control signals
+pub struct RuntimeSignals {
+    #[cfg(unix)]
+    usr1: Option<Signal>,
+    #[cfg(unix)]
+    usr2: Option<Signal>,
+}
+
+impl RuntimeSignals {
+    /// Install the signal handlers. Failing to install one only disables that signal.
+    pub fn install() -> Self {
+        Self {
+            #[cfg(unix)]
+            usr1: signal(SignalKind::user_defined1()).ok(),
+            #[cfg(unix)]
+            usr2: signal(SignalKind::user_defined2()).ok(),
+        }
+    }
+
+    /// Wait for the next control signal.
+    #[cfg(unix)]
+    pub async fn recv(&mut self) -> RuntimeSignal {
+        async fn next(signal: &mut Option<Signal>) {
+            if let Some(signal) = signal {
+                if signal.recv().await.is_some() {
+                    return;
+                }
+            }
+            std::future::pending::<()>().await
+        }
+
+        tokio::select! {
+            _ = next(&mut self.usr1) => RuntimeSignal::CycleLogLevel,
+            _ = next(&mut self.usr2) => RuntimeSignal::DumpMetrics,
+        }
+    }
+
+    /// Wait for the next control signal. Never resolves on non-Unix platforms.
+    #[cfg(not(unix))]
+    pub async fn recv(&mut self) -> RuntimeSignal {
+        std::future::pending().await
+    }
+}
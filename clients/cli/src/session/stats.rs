@@ -0,0 +1,225 @@
+//! Session statistics derived from the worker event stream
+
+use crate::events::{Event, EventType, Worker};
+use crate::ui::ZkVMMetrics;
+use std::fmt::{self, Display};
+use std::time::{Duration, Instant};
+
+/// Running counters for a session, updated from worker events
+#[derive(Debug, Clone)]
+pub struct SessionStats {
+    /// When the session started
+    pub started_at: Instant,
+    /// Tasks received from the orchestrator
+    pub tasks_fetched: u32,
+    /// Fetched tasks skipped by the `--task-type` filter
+    pub tasks_skipped: u32,
+    /// Proofs generated locally
+    pub proofs_generated: u32,
+    /// Proofs accepted by the orchestrator
+    pub proofs_submitted: u32,
+    /// Error events seen
+    pub errors: u32,
+    /// Sum of fetch-to-acceptance latencies of submitted proofs
+    pub total_latency: Duration,
+    /// Number of submissions included in `total_latency`
+    pub timed_submissions: u32,
+    /// Time spent proving, from receiving each task to its proof being generated
+    pub zkvm_runtime: Duration,
+    /// When the task currently being proved was received
+    proving_started: Option<Instant>,
+}
+
+impl SessionStats {
+    pub fn new() -> Self {
+        Self {
+            started_at: Instant::now(),
+            tasks_fetched: 0,
+            tasks_skipped: 0,
+            proofs_generated: 0,
+            proofs_submitted: 0,
+            errors: 0,
+            total_latency: Duration::ZERO,
+            timed_submissions: 0,
+            zkvm_runtime: Duration::ZERO,
+            proving_started: None,
+        }
+    }
+
+    /// Update the counters from a single event
+    pub fn observe(&mut self, event: &Event) {
+        match (event.worker, event.event_type) {
+            (_, EventType::Error) => {
+                // A throttle summary stands for the errors it suppressed
+                self.errors += event.repeated.unwrap_or(1);
+                if matches!(event.worker, Worker::Prover(_)) {
+                    self.proving_started = None;
+                }
+            }
+            (Worker::TaskFetcher, EventType::Success) if event.msg.contains("Got task") => {
+                self.tasks_fetched += 1;
+                self.proving_started = Some(Instant::now());
+            }
+            (_, EventType::Skipped) => self.tasks_skipped += 1,
+            (Worker::Prover(_), EventType::Success) => {
+                self.proofs_generated += 1;
+                if let Some(started) = self.proving_started.take() {
+                    self.zkvm_runtime += started.elapsed();
+                }
+            }
+            (Worker::ProofSubmitter, EventType::Success) => {
+                self.proofs_submitted += 1;
+                if let Some(latency_ms) = event.latency_ms {
+                    self.total_latency += Duration::from_millis(latency_ms);
+                    self.timed_submissions += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Mean fetch-to-acceptance latency, if any submission has been timed
+    pub fn average_latency(&self) -> Option<Duration> {
+        (self.timed_submissions > 0).then(|| self.total_latency / self.timed_submissions)
+    }
+
+    /// Time since the session started
+    pub fn uptime(&self) -> Duration {
+        self.started_at.elapsed()
+    }
+
+    /// Task counters in the form the dashboard uses
+    pub fn zkvm_metrics(&self) -> ZkVMMetrics {
+        ZkVMMetrics {
+            tasks_fetched: self.tasks_fetched as usize,
+            tasks_submitted: self.proofs_submitted as usize,
+            tasks_skipped: self.tasks_skipped as usize,
+            zkvm_runtime_secs: self.zkvm_runtime.as_secs(),
+            total_latency_ms: self.total_latency.as_millis() as u64,
+            timed_submissions: self.timed_submissions as usize,
+            ..Default::default()
+        }
+    }
+}
+
+impl Default for SessionStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Display for SessionStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "uptime: {}s, tasks fetched: {}, proofs generated: {}, proofs submitted: {}, errors: {}",
+            self.uptime().as_secs(),
+            self.tasks_fetched,
+            self.proofs_generated,
+            self.proofs_submitted,
+            self.errors
+        )?;
+        if let Some(latency) = self.average_latency() {
+            write!(f, ", avg fetch-to-accept: {:.1}s", latency.as_secs_f64())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logging::LogLevel;
+
+    #[test]
+    fn test_observe_counts_events() {
+        let mut stats = SessionStats::new();
+        stats.observe(&Event::task_fetcher_with_level(
+            "Step 1 of 4: Got task abc".to_string(),
+            EventType::Success,
+            LogLevel::Info,
+        ));
+        stats.observe(&Event::prover_with_level(
+            0,
+            "Step 3 of 4: Proof generated for task abc".to_string(),
+            EventType::Success,
+            LogLevel::Info,
+        ));
+        stats.observe(&Event::proof_submitter_with_level(
+            "Step 4 of 4: Proof submitted successfully for task abc".to_string(),
+            EventType::Success,
+            LogLevel::Info,
+        ));
+        stats.observe(&Event::proof_submitter_with_level(
+            "Failed to submit proof".to_string(),
+            EventType::Error,
+            LogLevel::Error,
+        ));
+
+        assert_eq!(stats.tasks_fetched, 1);
+        assert_eq!(stats.proofs_generated, 1);
+        assert_eq!(stats.proofs_submitted, 1);
+        assert_eq!(stats.errors, 1);
+        assert_eq!(stats.average_latency(), None);
+        // Proving time is counted once the proof is generated
+        assert!(stats.proving_started.is_none());
+    }
+
+    #[test]
+    fn test_throttle_summary_counts_suppressed_errors() {
+        let mut stats = SessionStats::new();
+        let mut event = Event::task_fetcher_with_level(
+            "Failed to fetch task".to_string(),
+            EventType::Error,
+            LogLevel::Error,
+        );
+        stats.observe(&event);
+        event.repeated = Some(4);
+        stats.observe(&event);
+        assert_eq!(stats.errors, 5);
+    }
+
+    #[test]
+    fn test_skipped_tasks_excluded_from_success_rate() {
+        let mut stats = SessionStats::new();
+        for msg in ["Step 1 of 4: Got task abc", "Step 1 of 4: Got task def"] {
+            stats.observe(&Event::task_fetcher_with_level(
+                msg.to_string(),
+                EventType::Success,
+                LogLevel::Info,
+            ));
+        }
+        stats.observe(&Event::task_fetcher_with_level(
+            "Skipping task abc of type ProofHash (--task-type proof-required), 1 skipped so far"
+                .to_string(),
+            EventType::Skipped,
+            LogLevel::Info,
+        ));
+        stats.observe(&Event::proof_submitter_with_level(
+            "Step 4 of 4: Proof submitted successfully for task def".to_string(),
+            EventType::Success,
+            LogLevel::Info,
+        ));
+
+        assert_eq!(stats.tasks_skipped, 1);
+        assert_eq!(stats.zkvm_metrics().success_rate(), 100.0);
+    }
+
+    #[test]
+    fn test_average_latency() {
+        let mut stats = SessionStats::new();
+        for latency_ms in [1_000, 3_000] {
+            stats.observe(
+                &Event::proof_submitter_with_level(
+                    "Step 4 of 4: Proof submitted successfully for task abc".to_string(),
+                    EventType::Success,
+                    LogLevel::Info,
+                )
+                .with_latency(Duration::from_millis(latency_ms)),
+            );
+        }
+
+        assert_eq!(stats.average_latency(), Some(Duration::from_secs(2)));
+        assert!(stats.to_string().ends_with("avg fetch-to-accept: 2.0s"));
+    }
+}
@@ -0,0 +1,44 @@
+//! One-shot health check for supervised nodes
+//!
+//! Resolves the node the way `start` would and reports whether the orchestrator answers,
+//! without fetching tasks or opening the dashboard. The result is the exit code: zero when
+//! the config and the orchestrator both resolve.
+
+use crate::config::Config;
+use crate::environment::Environment;
+use crate::orchestrator::OrchestratorClient;
+use crate::{print_cmd_error, print_cmd_info};
+use std::error::Error;
+use std::path::Path;
+
+/// Print the node ID, environment and wallet address of the configured node, and whether
+/// the orchestrator is reachable. Fails if the config is missing or the orchestrator call
+/// fails.
+pub async fn run_status(
+    node_id: Option<u64>,
+    environment: Environment,
+    config_path: &Path,
+    profile: &str,
+) -> Result<(), Box<dyn Error>> {
+    let orchestrator = OrchestratorClient::new(environment.clone());
+    // Resolving the node looks up its wallet address, so success means the orchestrator
+    // answered
+    let config = match Config::resolve(node_id, config_path, profile, &orchestrator).await {
+        Ok(config) => config,
+        Err(e) => {
+            print_cmd_error!("Status", &e.to_string());
+            print_cmd_info!(
+                "Orchestrator",
+                "{} (unreachable or rejected the node)",
+                environment
+            );
+            return Err(e);
+        }
+    };
+
+    print_cmd_info!("Node ID", "{}", config.node_id);
+    print_cmd_info!("Environment", "{}", environment);
+    print_cmd_info!("Wallet", "{}", config.wallet_address);
+    print_cmd_info!("Orchestrator", "reachable");
+    Ok(())
+}
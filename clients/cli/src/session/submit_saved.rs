@@ -0,0 +1,148 @@
+//! Submitting proofs saved with `start --save-proofs`
+//!
+//! Recovers proofs that were generated but never accepted, e.g. because the CLI crashed
+//! or was offline before submitting them. Nothing new is fetched or proved; proofs are
+//! submitted through the regular submitter, so rate limits and retries apply as usual.
+
+use crate::config::{Config, DEFAULT_PROFILE};
+use crate::consts::cli_consts::EVENT_QUEUE_SIZE;
+use crate::environment::Environment;
+use crate::events::Event;
+use crate::logging::LogLevel;
+use crate::network::request_timer::get_request_timer_state_dir;
+use crate::nexus_orchestrator::TaskDifficulty;
+use crate::orchestrator::OrchestratorClient;
+use crate::prover::ProverResult;
+use crate::prover::format::{ProofFormat, SavedProofMetadata};
+use crate::prover::types::ProverError;
+use crate::workers::core::{EventSender, WorkerConfig};
+use crate::workers::rejections::get_rejections_path;
+use crate::workers::submission_order::{SubmissionOrder, order_for_submission};
+use crate::workers::submitter::ProofSubmitter;
+use crate::{print_cmd_error, print_cmd_info};
+use ed25519_dalek::SigningKey;
+use std::error::Error;
+use std::path::Path;
+use tokio::sync::mpsc;
+
+/// Load the proofs described by a metadata file and rebuild what the submitter needs,
+/// including the key the task was fetched with
+fn load_saved_proof(
+    dir: &Path,
+    metadata: &SavedProofMetadata,
+) -> Result<(crate::task::Task, ProverResult, SigningKey), ProverError> {
+    let format = ProofFormat::from_extension(&metadata.format).ok_or_else(|| {
+        ProverError::MalformedTask(format!("Unknown proof format {}", metadata.format))
+    })?;
+    let signing_key = metadata.signing_key()?;
+    let proofs = format.decode(&std::fs::read(metadata.proof_path(dir)?)?)?;
+    Ok((
+        metadata.task()?,
+        ProverResult {
+            proofs,
+            combined_hash: metadata.combined_hash.clone(),
+            individual_proof_hashes: metadata.individual_proof_hashes.clone(),
+        },
+        signing_key,
+    ))
+}
+
+/// Submit every saved proof in `dir` that hasn't been accepted yet, in the given `order`,
+/// reporting the outcome per file. Returns an error if any submission failed so the process
+/// exits non-zero.
+pub async fn run_submit_saved(
+    dir: &Path,
+    order: SubmissionOrder,
+    environment: Environment,
+    config_path: &Path,
+) -> Result<(), Box<dyn Error>> {
+    let orchestrator = OrchestratorClient::new(environment.clone());
+    let config = Config::resolve(None, config_path, DEFAULT_PROFILE, &orchestrator).await?;
+    let node_id = config.node_id.parse::<u64>()?;
+
+    let metadata_paths = SavedProofMetadata::list(dir)?;
+    if metadata_paths.is_empty() {
+        print_cmd_info!("Submit saved", "No saved proofs found in {}", dir.display());
+        return Ok(());
+    }
+
+    // Only surface problems; per-file results are printed below
+    let (sender, mut receiver) = mpsc::channel::<Event>(EVENT_QUEUE_SIZE);
+    let printer = tokio::spawn(async move {
+        while let Some(event) = receiver.recv().await {
+            if event.log_level >= LogLevel::Warn && event.should_display() {
+                println!("{}", event);
+            }
+        }
+    });
+    let event_sender = EventSender::new(sender);
+
+    let mut worker_config = WorkerConfig::new(environment, config.user_id);
+    worker_config.save_proofs_dir = Some(dir.to_path_buf());
+    worker_config.rejections_path = get_rejections_path().ok();
+    worker_config.request_timer_state_dir = get_request_timer_state_dir(node_id).ok();
+    // The orchestrator only accepts a proof signed with the key its task was fetched
+    // with, so this key is replaced by the saved one before each submission
+    let mut submitter = ProofSubmitter::new(
+        node_id,
+        SigningKey::generate(&mut rand_core::OsRng),
+        Box::new(orchestrator),
+        event_sender.clone(),
+        &worker_config,
+    );
+
+    let (mut submitted, mut skipped, mut failed) = (0, 0, 0);
+    let mut pending = Vec::new();
+    for path in metadata_paths {
+        match SavedProofMetadata::load(&path) {
+            Ok(metadata) if metadata.submitted => skipped += 1,
+            Ok(metadata) => pending.push((path, metadata)),
+            Err(e) => {
+                print_cmd_error!(&path.display().to_string(), &e.to_string());
+                failed += 1;
+            }
+        }
+    }
+
+    let pending = order_for_submission(pending, order, |(_, metadata)| {
+        TaskDifficulty::from_str_name(&metadata.difficulty)
+    });
+    for (path, metadata) in pending {
+        let result = match load_saved_proof(dir, &metadata) {
+            Ok((task, proof_result, signing_key)) => {
+                submitter.set_signing_key(signing_key);
+                submitter
+                    .submit_proof(&task, &proof_result)
+                    .await
+                    .map_err(|e| e.to_string())
+            }
+            Err(e) => Err(e.to_string()),
+        };
+        match result {
+            Ok(()) => {
+                print_cmd_info!("Submitted", "{}", path.display());
+                submitted += 1;
+            }
+            Err(e) => {
+                print_cmd_error!(&format!("Failed {}", path.display()), &e);
+                failed += 1;
+            }
+        }
+    }
+
+    drop(submitter);
+    drop(event_sender);
+    let _ = printer.await;
+
+    print_cmd_info!(
+        "Submit saved",
+        "{} submitted, {} already submitted, {} failed",
+        submitted,
+        skipped,
+        failed
+    );
+    if failed > 0 {
+        return Err(format!("{} saved proofs could not be submitted", failed).into());
+    }
+    Ok(())
+}
@@ -0,0 +1,158 @@
+//! End-of-session summary
+
+use crate::consts::cli_consts::{
+    FAIL_FAST_PROVING_EXIT_CODE, FAIL_FAST_SUBMISSION_EXIT_CODE, VERSION_BLOCKED_EXIT_CODE,
+};
+use crate::ui::ZkVMMetrics;
+use serde::Serialize;
+use std::fmt::{self, Display};
+use std::time::Duration;
+
+/// Why a session ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, strum::Display)]
+#[serde(rename_all = "snake_case")]
+pub enum ShutdownReason {
+    /// Ctrl+C, `q`/`Esc` in the dashboard, or another shutdown request
+    #[strum(serialize = "user requested")]
+    UserRequested,
+    /// `--max-tasks` tasks were completed
+    #[strum(serialize = "max tasks reached")]
+    MaxTasksReached,
+    /// The proving workers kept dying and `--on-worker-death` gave up
+    #[strum(serialize = "proving workers died")]
+    WorkersDied,
+    /// `--fail-fast` and a task failed to prove
+    #[strum(serialize = "proving failed")]
+    ProvingFailed,
+    /// `--fail-fast` and a proof failed to submit
+    #[strum(serialize = "submission failed")]
+    SubmissionFailed,
+    /// `--max-uptime` was reached
+    #[strum(serialize = "max uptime reached")]
+    MaxUptimeReached,
+    /// A blocking version constraint was published for this CLI version
+    #[strum(serialize = "version blocked")]
+    VersionBlocked,
+}
+
+impl ShutdownReason {
+    /// Process exit code for a session that ended for this reason; non-zero on failure
+    pub fn exit_code(self) -> i32 {
+        match self {
+            Self::UserRequested | Self::MaxTasksReached | Self::MaxUptimeReached => 0,
+            Self::WorkersDied => 1,
+            Self::ProvingFailed => FAIL_FAST_PROVING_EXIT_CODE,
+            Self::SubmissionFailed => FAIL_FAST_SUBMISSION_EXIT_CODE,
+            Self::VersionBlocked => VERSION_BLOCKED_EXIT_CODE,
+        }
+    }
+}
+
+/// Outcome of a session, printed when the process ends
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionSummary {
+    pub uptime_secs: u64,
+    pub tasks_fetched: usize,
+    pub tasks_submitted: usize,
+    /// Submitted tasks as a percentage of fetched tasks
+    pub success_rate: f64,
+    /// Total time spent proving
+    pub zkvm_runtime_secs: u64,
+    /// Mean time from fetching a task to its proof being accepted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub average_latency_secs: Option<f64>,
+    pub reason: ShutdownReason,
+}
+
+impl SessionSummary {
+    pub fn new(uptime: Duration, metrics: &ZkVMMetrics, reason: ShutdownReason) -> Self {
+        Self {
+            uptime_secs: uptime.as_secs(),
+            tasks_fetched: metrics.tasks_fetched,
+            tasks_submitted: metrics.tasks_submitted,
+            success_rate: metrics.success_rate(),
+            zkvm_runtime_secs: metrics.zkvm_runtime_secs,
+            average_latency_secs: metrics.average_latency_secs(),
+            reason,
+        }
+    }
+
+    /// Print the summary to stdout, as a single JSON object if `json` is set
+    pub fn print(&self, json: bool) {
+        if json {
+            match serde_json::to_string(self) {
+                Ok(line) => println!("{}", line),
+                Err(e) => eprintln!("Failed to serialize session summary: {}", e),
+            }
+        } else {
+            println!("{}", self);
+        }
+    }
+}
+
+impl Display for SessionSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Session summary: uptime {}s, tasks fetched {}, tasks submitted {}, success rate {:.1}%, zkVM runtime {}s",
+            self.uptime_secs,
+            self.tasks_fetched,
+            self.tasks_submitted,
+            self.success_rate,
+            self.zkvm_runtime_secs
+        )?;
+        if let Some(latency) = self.average_latency_secs {
+            write!(f, ", average latency {:.1}s", latency)?;
+        }
+        write!(f, ", reason: {}", self.reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_json_is_single_object() {
+        let metrics = ZkVMMetrics {
+            tasks_fetched: 4,
+            tasks_submitted: 3,
+            zkvm_runtime_secs: 40,
+            ..Default::default()
+        };
+        let summary = SessionSummary::new(
+            Duration::from_secs(90),
+            &metrics,
+            ShutdownReason::MaxTasksReached,
+        );
+
+        let json = serde_json::to_string(&summary).unwrap();
+        assert!(!json.contains('\n'));
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["uptime_secs"], 90);
+        // Nothing was timed, so no latency is reported
+        assert!(value.get("average_latency_secs").is_none());
+        assert_eq!(value["tasks_fetched"], 4);
+        assert_eq!(value["tasks_submitted"], 3);
+        assert_eq!(value["success_rate"], 75.0);
+        assert_eq!(value["zkvm_runtime_secs"], 40);
+        assert_eq!(value["reason"], "max_tasks_reached");
+
+        assert!(summary.to_string().ends_with("reason: max tasks reached"));
+    }
+
+    #[test]
+    fn test_exit_code_identifies_failed_stage() {
+        assert_eq!(ShutdownReason::UserRequested.exit_code(), 0);
+        assert_eq!(ShutdownReason::MaxTasksReached.exit_code(), 0);
+        assert_eq!(ShutdownReason::MaxUptimeReached.exit_code(), 0);
+        let proving = ShutdownReason::ProvingFailed.exit_code();
+        let submission = ShutdownReason::SubmissionFailed.exit_code();
+        assert_ne!(proving, 0);
+        assert_ne!(submission, 0);
+        assert_ne!(proving, submission);
+        assert_ne!(proving, ShutdownReason::WorkersDied.exit_code());
+        let blocked = ShutdownReason::VersionBlocked.exit_code();
+        assert!(![0, 1, proving, submission].contains(&blocked));
+    }
+}
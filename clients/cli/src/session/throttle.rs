@@ -0,0 +1,231 @@
+//! Coalescing of repeated events
+//!
+//! When the orchestrator is down the same error can be reported many times a minute.
+//! Identical errors and warnings (same worker, message and level) seen again within the
+//! window are suppressed, and a single "(repeated N times)" summary is emitted once the
+//! window ends. The summary carries the count, so session statistics stay accurate.
+
+use crate::events::{Event, EventType, TIMESTAMP_FORMAT, Worker};
+use crate::logging::LogLevel;
+use chrono::Local;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+/// How often expired windows are checked for pending summaries
+const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+type EventKey = (Worker, String, LogLevel);
+
+#[derive(Debug)]
+struct Window {
+    started_at: Instant,
+    suppressed: u32,
+    event: Event,
+}
+
+/// Suppresses identical events within a time window
+#[derive(Debug)]
+pub struct EventThrottle {
+    window: Duration,
+    seen: HashMap<EventKey, Window>,
+}
+
+impl EventThrottle {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            seen: HashMap::new(),
+        }
+    }
+
+    /// Returns the events to forward for an incoming event at time `now`
+    pub fn filter(&mut self, event: Event, now: Instant) -> Vec<Event> {
+        // Only errors and warnings repeat fast enough to flood the output; progress events
+        // and state changes drive the UI and are never coalesced
+        let noisy = event.event_type == EventType::Error || event.log_level >= LogLevel::Warn;
+        if self.window.is_zero() || event.event_type == EventType::StateChange || !noisy {
+            return vec![event];
+        }
+
+        let key = (event.worker, event.msg.clone(), event.log_level);
+        let mut out = Vec::new();
+        match self.seen.get_mut(&key) {
+            Some(window) if now.duration_since(window.started_at) < self.window => {
+                window.suppressed += 1;
+                return out;
+            }
+            Some(window) => {
+                out.extend(summary(window));
+                window.started_at = now;
+                window.suppressed = 0;
+                window.event = event.clone();
+            }
+            None => {
+                self.seen.insert(
+                    key,
+                    Window {
+                        started_at: now,
+                        suppressed: 0,
+                        event: event.clone(),
+                    },
+                );
+            }
+        }
+        out.push(event);
+        out
+    }
+
+    /// Returns summaries for windows that ended before `now`, forgetting those windows
+    pub fn flush_expired(&mut self, now: Instant) -> Vec<Event> {
+        let mut out = Vec::new();
+        self.seen.retain(|_, window| {
+            if now.duration_since(window.started_at) < self.window {
+                return true;
+            }
+            out.extend(summary(window));
+            false
+        });
+        out
+    }
+}
+
+/// A summary event for a window, if anything was suppressed in it
+fn summary(window: &Window) -> Option<Event> {
+    if window.suppressed == 0 {
+        return None;
+    }
+    let mut event = window.event.clone();
+    event.msg = format!(
+        "{} (repeated {} times)",
+        event.msg.trim_end(),
+        window.suppressed
+    );
+    event.timestamp = Local::now().format(TIMESTAMP_FORMAT).to_string();
+    event.repeated = Some(window.suppressed);
+    Some(event)
+}
+
+/// Forward events from `receiver`, coalescing repeats within `window`. With a zero window
+/// `receiver` is returned as is.
+pub fn throttle_events(
+    mut receiver: mpsc::Receiver<Event>,
+    window: Duration,
+) -> mpsc::Receiver<Event> {
+    if window.is_zero() {
+        return receiver;
+    }
+
+    let (sender, forwarded) = mpsc::channel::<Event>(crate::consts::cli_consts::EVENT_QUEUE_SIZE);
+    tokio::spawn(async move {
+        let mut throttle = EventThrottle::new(window);
+        let mut flush = tokio::time::interval(FLUSH_INTERVAL);
+        loop {
+            let events = tokio::select! {
+                event = receiver.recv() => match event {
+                    Some(event) => throttle.filter(event, Instant::now()),
+                    // Report what was suppressed in the windows still open before exiting
+                    None => {
+                        for event in throttle.flush_expired(Instant::now() + window) {
+                            let _ = sender.send(event).await;
+                        }
+                        break;
+                    }
+                },
+                _ = flush.tick() => throttle.flush_expired(Instant::now()),
+            };
+            for event in events {
+                if sender.send(event).await.is_err() {
+                    return;
+                }
+            }
+        }
+    });
+
+    forwarded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error_event(msg: &str) -> Event {
+        Event::task_fetcher_with_level(msg.to_string(), EventType::Error, LogLevel::Error)
+    }
+
+    #[test]
+    fn test_repeats_within_window_are_suppressed() {
+        let mut throttle = EventThrottle::new(Duration::from_secs(60));
+        let now = Instant::now();
+
+        assert_eq!(throttle.filter(error_event("down"), now).len(), 1);
+        assert!(throttle.filter(error_event("down"), now).is_empty());
+        assert!(throttle.filter(error_event("down"), now).is_empty());
+        // A different message is not affected
+        assert_eq!(throttle.filter(error_event("other"), now).len(), 1);
+
+        let summaries = throttle.flush_expired(now + Duration::from_secs(60));
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].msg, "down (repeated 2 times)");
+        assert_eq!(summaries[0].repeated, Some(2));
+    }
+
+    #[test]
+    fn test_repeat_after_window_emits_summary_and_event() {
+        let mut throttle = EventThrottle::new(Duration::from_secs(10));
+        let now = Instant::now();
+
+        throttle.filter(error_event("down"), now);
+        throttle.filter(error_event("down"), now);
+        let out = throttle.filter(error_event("down"), now + Duration::from_secs(10));
+        assert_eq!(out.len(), 2);
+        assert_eq!(out[0].msg, "down (repeated 1 times)");
+        assert_eq!(out[1].msg, "down");
+    }
+
+    #[test]
+    fn test_state_changes_pass_through() {
+        let mut throttle = EventThrottle::new(Duration::from_secs(60));
+        let now = Instant::now();
+        let event = Event::state_change(crate::events::ProverState::Waiting, "idle".to_string());
+        assert_eq!(throttle.filter(event.clone(), now).len(), 1);
+        assert_eq!(throttle.filter(event, now).len(), 1);
+    }
+
+    #[test]
+    fn test_progress_events_pass_through() {
+        let mut throttle = EventThrottle::new(Duration::from_secs(60));
+        let now = Instant::now();
+        let event = Event::proof_submitter_with_level(
+            "Step 4 of 4: Proof submitted successfully".to_string(),
+            EventType::Success,
+            LogLevel::Info,
+        );
+        assert_eq!(throttle.filter(event.clone(), now).len(), 1);
+        assert_eq!(throttle.filter(event, now).len(), 1);
+    }
+
+    #[test]
+    fn test_zero_window_disables_throttling() {
+        let mut throttle = EventThrottle::new(Duration::ZERO);
+        let now = Instant::now();
+        assert_eq!(throttle.filter(error_event("down"), now).len(), 1);
+        assert_eq!(throttle.filter(error_event("down"), now).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_open_windows_are_flushed_on_close() {
+        let (sender, receiver) = mpsc::channel(8);
+        let mut forwarded = throttle_events(receiver, Duration::from_secs(60));
+        for _ in 0..3 {
+            sender.send(error_event("down")).await.unwrap();
+        }
+        drop(sender);
+
+        let mut messages = Vec::new();
+        while let Some(event) = forwarded.recv().await {
+            messages.push(event.msg);
+        }
+        assert_eq!(messages, ["down", "down (repeated 2 times)"]);
+    }
+}
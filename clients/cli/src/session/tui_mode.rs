@@ -1,19 +1,35 @@
 //! TUI mode execution
 
+use super::headless_mode::{OutputFormat, finish_workers, run_event_loop, run_headless_mode};
+use super::signals::{RuntimeSignal, RuntimeSignals};
+use super::stats::SessionStats;
+use super::summary::SessionSummary;
 use super::{
     SessionData,
     messages::{print_session_exit_success, print_session_shutdown, print_session_starting},
 };
+use crate::logging::cycle_log_level;
 use crate::orchestrator::Orchestrator;
+use crate::ui::dashboard::theme::Theme;
 use crate::ui::{self, UIConfig};
 use crate::version::checker::check_for_new_version;
+use crate::{print_cmd_info, print_cmd_warn};
 use crossterm::{
     event::{DisableMouseCapture, EnableMouseCapture},
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
 use ratatui::{Terminal, backend::CrosstermBackend};
-use std::{error::Error, io};
+use std::{
+    error::Error,
+    io::{self, IsTerminal},
+};
+
+/// Whether the dashboard can run: it reads keys from stdin and draws to stdout, so both must
+/// be interactive terminals
+fn can_run_dashboard(stdin_is_terminal: bool, stdout_is_terminal: bool) -> bool {
+    stdin_is_terminal && stdout_is_terminal
+}
 
 /// Runs the application in TUI mode
 ///
@@ -21,18 +37,31 @@ use std::{error::Error, io};
 /// 1. Terminal setup and cleanup
 /// 2. UI application initialization and execution
 /// 3. Proper shutdown handling
+/// 4. Falling back to headless output if the terminal fails, so proving continues
+/// 5. Running headless from the start when stdin or stdout isn't a terminal
 ///
 /// # Arguments
 /// * `session` - Session data from setup
 /// * `with_background` - Whether to enable background colors
+/// * `theme` - Dashboard color theme
 ///
 /// # Returns
-/// * `Ok(())` - TUI mode completed successfully
+/// * `Ok(SessionSummary)` - TUI mode completed successfully
 /// * `Err` - TUI mode failed
 pub async fn run_tui_mode(
     session: SessionData,
     with_background: bool,
-) -> Result<(), Box<dyn Error>> {
+    theme: Theme,
+) -> Result<SessionSummary, Box<dyn Error>> {
+    // Piped output or a supervisor without a terminal would get garbled escape sequences
+    if !can_run_dashboard(io::stdin().is_terminal(), io::stdout().is_terminal()) {
+        print_cmd_info!(
+            "No terminal",
+            "stdin or stdout is not a terminal, running in headless mode. Pass --headless to skip this check."
+        );
+        return run_headless_mode(session, OutputFormat::Text).await;
+    }
+
     // Print session start message
     print_session_starting("TUI", session.node_id);
 
@@ -50,6 +79,16 @@ pub async fn run_tui_mode(
             (false, None)
         };
 
+    // SIGUSR1 toggles the log level; the dashboard already shows metrics, so SIGUSR2 is ignored
+    let mut signals = RuntimeSignals::install();
+    let signal_handle = tokio::spawn(async move {
+        loop {
+            if signals.recv().await == RuntimeSignal::CycleLogLevel {
+                cycle_log_level();
+            }
+        }
+    });
+
     // Terminal setup
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -65,9 +104,11 @@ pub async fn run_tui_mode(
         session.num_workers,
         version_update_available,
         latest_version,
-    );
+    )
+    .with_task_type_filter(session.task_type_filter)
+    .with_theme(theme);
 
-    let app = ui::App::new(
+    let mut app = ui::App::new(
         Some(session.node_id),
         session.orchestrator.environment().clone(),
         session.event_receiver,
@@ -76,26 +117,80 @@ pub async fn run_tui_mode(
         ui_config,
     );
 
-    let result = ui::run(&mut terminal, app).await;
+    let result = ui::run(&mut terminal, &mut app).await;
+    signal_handle.abort();
 
-    // Clean up the terminal after running the application
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
-    terminal.show_cursor()?;
+    // Clean up the terminal after running the application. If the terminal itself failed,
+    // cleanup may fail too; don't let that take the prover down.
+    let cleanup = restore_terminal(&mut terminal);
 
-    // Handle the result
-    result?;
+    // Counts events the dashboard no longer sees: after a terminal failure and during shutdown
+    let mut stats = SessionStats::new();
+    let reason = match result.and_then(|reason| cleanup.map(|()| reason)) {
+        Ok(reason) => reason,
+        Err(e) => {
+            // Keep proving and fall back to plain event output
+            print_cmd_warn!(
+                "Terminal error",
+                "{}. Continuing in headless mode; press Ctrl+C to exit.",
+                e
+            );
+            run_event_loop(
+                app.event_receiver_mut(),
+                &session.shutdown_sender,
+                &session.max_tasks_shutdown_sender,
+                session.num_workers,
+                &mut stats,
+                OutputFormat::Text,
+            )
+            .await
+        }
+    };
 
     // Wait for workers to finish
     print_session_shutdown();
-    for handle in session.join_handles {
-        let _ = handle.await;
-    }
+    finish_workers(
+        session.join_handles,
+        app.event_receiver_mut(),
+        &mut stats,
+        OutputFormat::Text,
+    )
+    .await;
     print_session_exit_success();
 
-    Ok(())
+    // Combine what the dashboard saw with what was seen after it closed
+    let mut metrics = app.zkvm_metrics();
+    let after_dashboard = stats.zkvm_metrics();
+    metrics.tasks_fetched += after_dashboard.tasks_fetched;
+    metrics.tasks_submitted += after_dashboard.tasks_submitted;
+    metrics.tasks_skipped += after_dashboard.tasks_skipped;
+    metrics.zkvm_runtime_secs += after_dashboard.zkvm_runtime_secs;
+    Ok(SessionSummary::new(app.uptime(), &metrics, reason))
+}
+
+/// Leave raw mode and the alternate screen, attempting every step even if one fails
+fn restore_terminal(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> io::Result<()> {
+    let raw = disable_raw_mode();
+    let screen = execute!(
+        terminal.backend_mut(),
+        LeaveAlternateScreen,
+        DisableMouseCapture
+    );
+    let cursor = terminal.show_cursor();
+    raw.and(screen).and(cursor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dashboard_needs_both_terminals() {
+        assert!(can_run_dashboard(true, true));
+        // Piped output, e.g. `nexus-cli start | tee log`
+        assert!(!can_run_dashboard(true, false));
+        // No stdin, e.g. under a supervisor
+        assert!(!can_run_dashboard(false, true));
+        assert!(!can_run_dashboard(false, false));
+    }
 }
@@ -0,0 +1,100 @@
+//! Local file helpers: rotation, caches and reporting failed writes
+//!
+//! A full disk shouldn't stop the prover: state that can't be persisted is kept in memory
+//! and the user is told what couldn't be saved and why.
+
+use serde::Serialize;
+use std::ffi::OsString;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Whether a write failed because the disk (or the user's quota) is full
+pub fn is_disk_full(error: &io::Error) -> bool {
+    error.kind() == io::ErrorKind::StorageFull
+}
+
+/// An actionable message for a failed write of `what`, e.g. "the difficulty state"
+pub fn describe_write_error(what: &str, error: &io::Error) -> String {
+    if is_disk_full(error) {
+        format!("Disk full, cannot persist {}. Free up disk space.", what)
+    } else {
+        format!("Cannot persist {}: {}", what, error)
+    }
+}
+
+/// Write `value` to `path` as JSON, creating the parent directory. For caches that can
+/// be rebuilt, so any failure is ignored and the next run simply starts without it.
+pub fn save_json_best_effort<T: Serialize>(path: &Path, value: &T) {
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    if let Ok(json) = serde_json::to_string(value) {
+        let _ = fs::write(path, json);
+    }
+}
+
+/// `path` with `.index` appended, e.g. `nexus.log.2`
+pub fn rotated_path(path: &Path, index: usize) -> PathBuf {
+    let mut name = OsString::from(path.as_os_str());
+    name.push(format!(".{}", index));
+    PathBuf::from(name)
+}
+
+/// Move the file at `path` aside, keeping `count` files in total counting `path` itself:
+/// `path.1`.. shift up by one, the oldest is dropped and `path` becomes `path.1`
+pub fn rotate_file(path: &Path, count: usize) -> io::Result<()> {
+    if count <= 1 {
+        return fs::remove_file(path);
+    }
+    for index in (1..count - 1).rev() {
+        let from = rotated_path(path, index);
+        if from.exists() {
+            fs::rename(&from, rotated_path(path, index + 1))?;
+        }
+    }
+    fs::rename(path, rotated_path(path, 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_save_json_best_effort_creates_parent() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("cache").join("country");
+        save_json_best_effort(&path, &vec!["US"]);
+        assert_eq!(fs::read_to_string(&path).unwrap(), r#"["US"]"#);
+
+        // A path that can't be written is ignored
+        save_json_best_effort(dir.path(), &vec!["US"]);
+    }
+
+    #[test]
+    fn test_rotate_file_keeps_count_files() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("nexus.log");
+        for index in 0..4 {
+            fs::write(&path, index.to_string()).unwrap();
+            rotate_file(&path, 3).unwrap();
+        }
+
+        assert!(!path.exists());
+        assert_eq!(fs::read_to_string(rotated_path(&path, 1)).unwrap(), "3");
+        assert_eq!(fs::read_to_string(rotated_path(&path, 2)).unwrap(), "2");
+        assert!(!rotated_path(&path, 3).exists());
+    }
+
+    #[test]
+    fn test_disk_full_message_is_actionable() {
+        let full = io::Error::from(io::ErrorKind::StorageFull);
+        assert!(is_disk_full(&full));
+        assert!(describe_write_error("the event recording", &full).starts_with("Disk full"));
+
+        let denied = io::Error::from(io::ErrorKind::PermissionDenied);
+        assert!(!is_disk_full(&denied));
+        assert!(describe_write_error("the event recording", &denied).starts_with("Cannot persist"));
+    }
+}
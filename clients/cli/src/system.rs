@@ -64,6 +64,71 @@ fn flops_per_cycle_per_core() -> u32 {
     }
 }
 
+/// A CPU feature the prover can use, and whether this CPU has it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CpuFeature {
+    pub name: &'static str,
+    pub available: bool,
+    /// Proving is noticeably slower without it
+    pub recommended: bool,
+}
+
+/// Detect, at run time, the SIMD features relevant to the prover. Empty on architectures
+/// where detection isn't meaningful.
+pub fn prover_cpu_features() -> Vec<CpuFeature> {
+    cfg_if! {
+        if #[cfg(target_arch = "x86_64")] {
+            vec![
+                CpuFeature {
+                    name: "avx2",
+                    available: std::arch::is_x86_feature_detected!("avx2"),
+                    recommended: true,
+                },
+                CpuFeature {
+                    name: "avx512f",
+                    available: std::arch::is_x86_feature_detected!("avx512f"),
+                    recommended: false,
+                },
+            ]
+        } else if #[cfg(target_arch = "aarch64")] {
+            vec![CpuFeature {
+                name: "neon",
+                available: std::arch::is_aarch64_feature_detected!("neon"),
+                recommended: true,
+            }]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+/// Names of recommended features missing from `features`
+pub fn missing_recommended_features(features: &[CpuFeature]) -> Vec<&'static str> {
+    features
+        .iter()
+        .filter(|feature| feature.recommended && !feature.available)
+        .map(|feature| feature.name)
+        .collect()
+}
+
+/// One-line summary of `features`, e.g. "avx2 yes, avx512f no"
+pub fn describe_cpu_features(features: &[CpuFeature]) -> String {
+    if features.is_empty() {
+        return "not checked on this architecture".to_string();
+    }
+    features
+        .iter()
+        .map(|feature| {
+            format!(
+                "{} {}",
+                feature.name,
+                if feature.available { "yes" } else { "no" }
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 /// Estimate peak FLOPS (in GFLOP/s) from the number of prover threads and clock speed.
 pub fn estimate_peak_gflops(num_provers: usize) -> f64 {
     let (_cores, mhz) = cpu_stats();
@@ -135,7 +200,6 @@ pub fn total_memory_gb() -> f64 {
 }
 
 /// Memory used by the current process, in GB.
-#[allow(unused)]
 pub fn process_memory_gb() -> f64 {
     let mut sys = System::new();
     sys.refresh_all();
@@ -149,6 +213,23 @@ pub fn process_memory_gb() -> f64 {
     memory as f64 / 1024.0 / 1024.0 / 1024.0 // Convert to GB (binary)
 }
 
+/// Memory used by the current process and its direct children (prover subprocesses), in bytes.
+pub fn process_tree_memory_bytes() -> u64 {
+    let mut sys = System::new();
+    sys.refresh_processes_specifics(
+        sysinfo::ProcessesToUpdate::All,
+        true,
+        sysinfo::ProcessRefreshKind::nothing().with_memory(),
+    );
+
+    let current_pid = sysinfo::Pid::from(process::id() as usize);
+    sys.processes()
+        .values()
+        .filter(|p| p.pid() == current_pid || p.parent() == Some(current_pid))
+        .map(|p| p.memory())
+        .sum()
+}
+
 // We encode the memory usage to i32 type at client
 fn bytes_to_mb_i32(bytes: u64) -> i32 {
     // Convert to MB with 3 decimal places of precision
@@ -158,6 +239,32 @@ fn bytes_to_mb_i32(bytes: u64) -> i32 {
 
 #[cfg(test)]
 mod tests {
+    use super::CpuFeature;
+
+    #[test]
+    fn test_missing_recommended_features() {
+        let features = [
+            CpuFeature {
+                name: "avx2",
+                available: false,
+                recommended: true,
+            },
+            CpuFeature {
+                name: "avx512f",
+                available: false,
+                recommended: false,
+            },
+        ];
+        assert_eq!(super::missing_recommended_features(&features), vec!["avx2"]);
+        assert_eq!(
+            super::describe_cpu_features(&features),
+            "avx2 no, avx512f no"
+        );
+        assert_eq!(
+            super::describe_cpu_features(&[]),
+            "not checked on this architecture"
+        );
+    }
 
     #[test]
     fn test_estimate_peak_gflops() {
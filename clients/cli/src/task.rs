@@ -6,6 +6,56 @@
 
 use sha3::{Digest, Keccak256};
 use std::fmt::Display;
+use std::time::Instant;
+use thiserror::Error;
+
+/// Reasons an orchestrator task can't be converted into a [`Task`]
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum TaskConversionError {
+    /// The response did not contain a task
+    #[error("response contains no task")]
+    MissingTask,
+
+    /// A task type this client doesn't know about (e.g. added by a newer orchestrator)
+    #[error("unknown task type {0}")]
+    UnknownTaskType(i32),
+
+    /// A difficulty this client doesn't know about (e.g. added by a newer orchestrator)
+    #[error("unknown task difficulty {0}")]
+    UnknownDifficulty(i32),
+
+    /// Neither `public_inputs_list` nor the legacy `public_inputs` is set
+    #[error("task has no inputs")]
+    NoInputs,
+}
+
+/// Which kinds of task a node is willing to prove.
+///
+/// The orchestrator can't be asked for a task type, so unwanted tasks are skipped
+/// client-side and another task is fetched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum, strum::Display)]
+#[strum(serialize_all = "kebab-case")]
+pub enum TaskTypeFilter {
+    /// Accept every task
+    #[default]
+    Any,
+    /// Only tasks submitted as hashes (no proof upload)
+    ProofHash,
+    /// Only tasks that require uploading the proof
+    ProofRequired,
+}
+
+impl TaskTypeFilter {
+    /// Whether a task of the given type should be proved
+    pub fn accepts(&self, task_type: crate::nexus_orchestrator::TaskType) -> bool {
+        use crate::nexus_orchestrator::TaskType;
+        match self {
+            Self::Any => true,
+            Self::ProofHash => matches!(task_type, TaskType::ProofHash | TaskType::AllProofHashes),
+            Self::ProofRequired => task_type == TaskType::ProofRequired,
+        }
+    }
+}
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Task {
@@ -15,10 +65,13 @@ pub struct Task {
     /// ID of the program to be executed
     pub program_id: String,
 
-    /// Public inputs for the task (legacy field for backward compatibility)
+    /// Public inputs for the task (legacy field for backward compatibility).
+    /// Always the first entry of `public_inputs_list`; never read it for proving.
     pub public_inputs: Vec<u8>,
 
-    /// Multiple public inputs for the task (new field)
+    /// Multiple public inputs for the task (new field). This is the input set that is
+    /// proved and reported; see [`resolve_inputs`] for how it's built from an
+    /// orchestrator task.
     pub public_inputs_list: Vec<Vec<u8>>,
 
     /// The type of task (proof required or only hash)
@@ -28,6 +81,10 @@ pub struct Task {
     /// This accounts for reputation-based gating and allows clients to track
     /// the actual difficulty they're receiving vs what they requested.
     pub difficulty: crate::nexus_orchestrator::TaskDifficulty,
+
+    /// When the task was received from the orchestrator, used to measure
+    /// end-to-end latency through to an accepted submission.
+    pub fetched_at: Instant,
 }
 
 impl Task {
@@ -47,6 +104,7 @@ impl Task {
             public_inputs_list: vec![public_inputs],
             task_type,
             difficulty,
+            fetched_at: Instant::now(),
         }
     }
 
@@ -68,10 +126,15 @@ impl Task {
         format!("{:x}", hash)
     }
 
-    /// Get all inputs for the task
+    /// Get all inputs for the task. Proving and analytics both use this input set.
     pub fn all_inputs(&self) -> &[Vec<u8>] {
         &self.public_inputs_list
     }
+
+    /// Whether the task has more inputs than `max_inputs` (no limit when `None`)
+    pub fn exceeds_input_limit(&self, max_inputs: Option<usize>) -> bool {
+        max_inputs.is_some_and(|max| self.public_inputs_list.len() > max)
+    }
 }
 
 // Display
@@ -87,26 +150,61 @@ impl Display for Task {
     }
 }
 
-// From Task
-impl From<&crate::nexus_orchestrator::Task> for Task {
-    fn from(task: &crate::nexus_orchestrator::Task) -> Self {
-        Task {
+/// The input set of an orchestrator task.
+///
+/// `public_inputs_list` takes precedence whenever it is non-empty, and the legacy
+/// `public_inputs` field is then ignored even if it differs. Older orchestrators only set
+/// `public_inputs`, which becomes a single input. A task with neither is rejected, so it
+/// fails before proving rather than in the prover.
+pub fn resolve_inputs(
+    public_inputs: &[u8],
+    public_inputs_list: &[Vec<u8>],
+) -> Result<Vec<Vec<u8>>, TaskConversionError> {
+    if !public_inputs_list.is_empty() {
+        Ok(public_inputs_list.to_vec())
+    } else if !public_inputs.is_empty() {
+        Ok(vec![public_inputs.to_vec()])
+    } else {
+        Err(TaskConversionError::NoInputs)
+    }
+}
+
+// From Task. Unknown enum values are rejected rather than mapped to a default,
+// since prost keeps them as raw integers.
+impl TryFrom<&crate::nexus_orchestrator::Task> for Task {
+    type Error = TaskConversionError;
+
+    fn try_from(task: &crate::nexus_orchestrator::Task) -> Result<Self, Self::Error> {
+        let task_type = crate::nexus_orchestrator::TaskType::try_from(task.task_type)
+            .map_err(|_| TaskConversionError::UnknownTaskType(task.task_type))?;
+        let difficulty = crate::nexus_orchestrator::TaskDifficulty::try_from(task.difficulty)
+            .map_err(|_| TaskConversionError::UnknownDifficulty(task.difficulty))?;
+        let public_inputs_list = resolve_inputs(&task.public_inputs, &task.public_inputs_list)?;
+        Ok(Task {
             task_id: task.task_id.clone(),
             program_id: task.program_id.clone(),
-            public_inputs: task.public_inputs_list.first().cloned().unwrap_or_default(),
-            public_inputs_list: task.public_inputs_list.clone(),
-            task_type: crate::nexus_orchestrator::TaskType::try_from(task.task_type).unwrap(),
-            difficulty: crate::nexus_orchestrator::TaskDifficulty::try_from(task.difficulty)
-                .unwrap_or_default(),
-        }
+            public_inputs: public_inputs_list[0].clone(),
+            public_inputs_list,
+            task_type,
+            difficulty,
+            fetched_at: Instant::now(),
+        })
     }
 }
 
 // From GetProofTaskResponse
-impl From<&crate::nexus_orchestrator::GetProofTaskResponse> for Task {
-    fn from(response: &crate::nexus_orchestrator::GetProofTaskResponse) -> Self {
+impl TryFrom<&crate::nexus_orchestrator::GetProofTaskResponse> for Task {
+    type Error = TaskConversionError;
+
+    fn try_from(
+        response: &crate::nexus_orchestrator::GetProofTaskResponse,
+    ) -> Result<Self, Self::Error> {
         // Use the task field instead of deprecated fields
-        Task::from(response.task.as_ref().unwrap())
+        let task = response
+            .task
+            .as_ref()
+            .ok_or(TaskConversionError::MissingTask)?;
+        Task::try_from(task)
     }
 }
 
@@ -231,4 +329,125 @@ mod tests {
 
         println!("Backward compatibility test passed");
     }
+
+    #[test]
+    fn test_task_type_filter() {
+        use crate::nexus_orchestrator::TaskType;
+
+        for task_type in [
+            TaskType::ProofRequired,
+            TaskType::ProofHash,
+            TaskType::AllProofHashes,
+        ] {
+            assert!(TaskTypeFilter::Any.accepts(task_type));
+        }
+        assert!(TaskTypeFilter::ProofHash.accepts(TaskType::ProofHash));
+        assert!(TaskTypeFilter::ProofHash.accepts(TaskType::AllProofHashes));
+        assert!(!TaskTypeFilter::ProofHash.accepts(TaskType::ProofRequired));
+        assert!(TaskTypeFilter::ProofRequired.accepts(TaskType::ProofRequired));
+        assert!(!TaskTypeFilter::ProofRequired.accepts(TaskType::ProofHash));
+        assert_eq!(TaskTypeFilter::ProofHash.to_string(), "proof-hash");
+    }
+
+    #[test]
+    fn test_exceeds_input_limit() {
+        let mut task = Task::new(
+            "task".to_string(),
+            "fib_input_initial".to_string(),
+            vec![1, 2, 3],
+            crate::nexus_orchestrator::TaskType::ProofHash,
+            crate::nexus_orchestrator::TaskDifficulty::Small,
+        );
+        task.public_inputs_list = vec![vec![0]; 3];
+
+        assert!(!task.exceeds_input_limit(None));
+        assert!(!task.exceeds_input_limit(Some(3)));
+        assert!(task.exceeds_input_limit(Some(2)));
+    }
+
+    #[test]
+    fn test_unknown_enum_values_are_rejected() {
+        let proto_task = crate::nexus_orchestrator::Task {
+            task_id: "test_task".to_string(),
+            program_id: "fib_input_initial".to_string(),
+            public_inputs_list: vec![vec![1, 2, 3]],
+            task_type: crate::nexus_orchestrator::TaskType::ProofHash as i32,
+            difficulty: crate::nexus_orchestrator::TaskDifficulty::Large as i32,
+            ..Default::default()
+        };
+        let task = Task::try_from(&proto_task).unwrap();
+        assert_eq!(
+            task.difficulty,
+            crate::nexus_orchestrator::TaskDifficulty::Large
+        );
+
+        let unknown_difficulty = crate::nexus_orchestrator::Task {
+            difficulty: 99,
+            ..proto_task.clone()
+        };
+        assert_eq!(
+            Task::try_from(&unknown_difficulty),
+            Err(TaskConversionError::UnknownDifficulty(99))
+        );
+
+        let unknown_type = crate::nexus_orchestrator::Task {
+            task_type: -1,
+            ..proto_task
+        };
+        assert_eq!(
+            Task::try_from(&unknown_type),
+            Err(TaskConversionError::UnknownTaskType(-1))
+        );
+
+        let empty_response = crate::nexus_orchestrator::GetProofTaskResponse::default();
+        assert_eq!(
+            Task::try_from(&empty_response),
+            Err(TaskConversionError::MissingTask)
+        );
+    }
+
+    fn proto_task_with_inputs(
+        public_inputs: Vec<u8>,
+        public_inputs_list: Vec<Vec<u8>>,
+    ) -> crate::nexus_orchestrator::Task {
+        crate::nexus_orchestrator::Task {
+            task_id: "test_task".to_string(),
+            program_id: "fib_input_initial".to_string(),
+            public_inputs,
+            public_inputs_list,
+            task_type: crate::nexus_orchestrator::TaskType::ProofHash as i32,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_legacy_single_input() {
+        let task = Task::try_from(&proto_task_with_inputs(vec![1, 2, 3], vec![])).unwrap();
+        assert_eq!(task.all_inputs(), &[vec![1, 2, 3]]);
+        assert_eq!(task.public_inputs, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_input_list_only() {
+        let task = Task::try_from(&proto_task_with_inputs(vec![], vec![vec![1], vec![2]])).unwrap();
+        assert_eq!(task.all_inputs(), &[vec![1], vec![2]]);
+        assert_eq!(task.public_inputs, vec![1]);
+    }
+
+    #[test]
+    fn test_input_list_takes_precedence_over_legacy_input() {
+        let task =
+            Task::try_from(&proto_task_with_inputs(vec![9, 9], vec![vec![1], vec![2]])).unwrap();
+        assert_eq!(task.all_inputs(), &[vec![1], vec![2]]);
+        // The legacy field mirrors the list, so every reader sees the same first input
+        assert_eq!(task.public_inputs, vec![1]);
+    }
+
+    #[test]
+    fn test_task_without_inputs_is_rejected() {
+        assert_eq!(
+            Task::try_from(&proto_task_with_inputs(vec![], vec![])),
+            Err(TaskConversionError::NoInputs)
+        );
+    }
 }
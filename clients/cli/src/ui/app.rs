@@ -4,8 +4,12 @@
 
 use crate::environment::Environment;
 use crate::events::Event as WorkerEvent;
+use crate::session::summary::ShutdownReason;
+use crate::task::TaskTypeFilter;
+use crate::ui::dashboard::theme::Theme;
 use crate::ui::dashboard::{DashboardState, render_dashboard};
 use crate::ui::login::render_login;
+use crate::ui::metrics::ZkVMMetrics;
 use crate::ui::splash::render_splash;
 use crossterm::event::{self, Event, KeyCode};
 use ratatui::{Frame, Terminal, backend::Backend};
@@ -19,6 +23,8 @@ pub struct UIConfig {
     pub num_threads: usize,
     pub update_available: bool,
     pub latest_version: Option<String>,
+    pub task_type_filter: TaskTypeFilter,
+    pub theme: Theme,
 }
 
 impl UIConfig {
@@ -33,8 +39,22 @@ impl UIConfig {
             num_threads,
             update_available,
             latest_version,
+            task_type_filter: TaskTypeFilter::default(),
+            theme: Theme::default(),
         }
     }
+
+    /// Show which task types are being proved
+    pub fn with_task_type_filter(mut self, task_type_filter: TaskTypeFilter) -> Self {
+        self.task_type_filter = task_type_filter;
+        self
+    }
+
+    /// Use the given color theme
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
 }
 
 /// The different screens in the application.
@@ -71,7 +91,7 @@ pub struct App {
     shutdown_sender: broadcast::Sender<()>,
 
     /// Receives max tasks completion signal.
-    max_tasks_shutdown_receiver: broadcast::Receiver<()>,
+    max_tasks_shutdown_receiver: broadcast::Receiver<ShutdownReason>,
 
     /// Whether to disable background colors
     with_background_color: bool,
@@ -84,6 +104,12 @@ pub struct App {
 
     /// Latest version available, if any.
     latest_version: Option<String>,
+
+    /// Task types being proved.
+    task_type_filter: TaskTypeFilter,
+
+    /// Dashboard color theme.
+    theme: Theme,
 }
 
 impl App {
@@ -93,7 +119,7 @@ impl App {
         environment: Environment,
         event_receiver: mpsc::Receiver<WorkerEvent>,
         shutdown_sender: broadcast::Sender<()>,
-        max_tasks_shutdown_receiver: broadcast::Receiver<()>,
+        max_tasks_shutdown_receiver: broadcast::Receiver<ShutdownReason>,
         ui_config: UIConfig,
     ) -> Self {
         Self {
@@ -108,9 +134,29 @@ impl App {
             num_threads: ui_config.num_threads,
             version_update_available: ui_config.update_available,
             latest_version: ui_config.latest_version,
+            task_type_filter: ui_config.task_type_filter,
+            theme: ui_config.theme,
+        }
+    }
+
+    /// Receiver for worker events, e.g. to keep consuming them after the UI has stopped.
+    pub fn event_receiver_mut(&mut self) -> &mut mpsc::Receiver<WorkerEvent> {
+        &mut self.event_receiver
+    }
+
+    /// Task metrics shown on the dashboard (empty before the dashboard is displayed)
+    pub fn zkvm_metrics(&self) -> ZkVMMetrics {
+        match &self.current_screen {
+            Screen::Dashboard(state) => state.zkvm_metrics.clone(),
+            _ => ZkVMMetrics::default(),
         }
     }
 
+    /// Time since the application started
+    pub fn uptime(&self) -> Duration {
+        self.start_time.elapsed()
+    }
+
     /// Handles a complete login process, transitioning to the dashboard screen.
     #[allow(unused)]
     pub fn login(&mut self) {
@@ -120,7 +166,9 @@ impl App {
             self.num_threads,
             self.version_update_available,
             self.latest_version.clone(),
-        );
+        )
+        .with_task_type_filter(self.task_type_filter)
+        .with_theme(self.theme);
         let state = DashboardState::new(
             node_id,
             self.environment.clone(),
@@ -132,17 +180,23 @@ impl App {
 }
 
 /// Runs the application UI in a loop, handling events and rendering the appropriate screen.
-pub async fn run<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> std::io::Result<()> {
+///
+/// Returns an error if the terminal fails (e.g. it was disconnected); the caller keeps
+/// ownership of `app` so the session can continue without the UI.
+pub async fn run<B: Backend>(
+    terminal: &mut Terminal<B>,
+    app: &mut App,
+) -> std::io::Result<ShutdownReason> {
     let splash_start = Instant::now();
     let splash_duration = Duration::from_secs(2);
 
     // UI event loop
     loop {
-        // Check for max tasks completion signal (non-blocking)
-        if app.max_tasks_shutdown_receiver.try_recv().is_ok() {
+        // Check for the worker ending the session (non-blocking)
+        if let Ok(reason) = app.max_tasks_shutdown_receiver.try_recv() {
             // Send shutdown signal to workers and exit
             let _ = app.shutdown_sender.send(());
-            return Ok(());
+            return Ok(reason);
         }
 
         // Queue all incoming events for processing
@@ -172,7 +226,9 @@ pub async fn run<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> std::i
                     app.num_threads,
                     app.version_update_available,
                     app.latest_version.clone(),
-                );
+                )
+                .with_task_type_filter(app.task_type_filter)
+                .with_theme(app.theme);
                 app.current_screen = Screen::Dashboard(Box::new(DashboardState::new(
                     app.node_id,
                     app.environment.clone(),
@@ -195,7 +251,7 @@ pub async fn run<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> std::i
                 if matches!(key.code, KeyCode::Esc | KeyCode::Char('q')) {
                     // Send shutdown signal to workers
                     let _ = app.shutdown_sender.send(());
-                    return Ok(());
+                    return Ok(ShutdownReason::UserRequested);
                 }
 
                 match &mut app.current_screen {
@@ -207,7 +263,9 @@ pub async fn run<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> std::i
                                 app.num_threads,
                                 app.version_update_available,
                                 app.latest_version.clone(),
-                            );
+                            )
+                            .with_task_type_filter(app.task_type_filter)
+                            .with_theme(app.theme);
                             app.current_screen = Screen::Dashboard(Box::new(DashboardState::new(
                                 app.node_id,
                                 app.environment.clone(),
@@ -2,16 +2,17 @@
 //!
 //! Renders footer with quit instructions and version info
 
+use super::super::state::DashboardState;
 use ratatui::Frame;
 use ratatui::layout::Alignment;
-use ratatui::prelude::{Color, Modifier, Style};
+use ratatui::prelude::{Modifier, Style};
 use ratatui::widgets::{Block, BorderType, Borders, Paragraph};
 
 /// Render enhanced footer.
-pub fn render_footer(f: &mut Frame, area: ratatui::layout::Rect) {
+pub fn render_footer(f: &mut Frame, area: ratatui::layout::Rect, state: &DashboardState) {
     let footer_text = "[Q] Quit | Nexus Prover Dashboard".to_string();
 
-    let footer_color = Color::Cyan;
+    let footer_color = state.palette().primary;
 
     let footer = Paragraph::new(footer_text)
         .alignment(Alignment::Center)
@@ -4,10 +4,11 @@
 
 use super::super::state::DashboardState;
 use crate::events::ProverState;
+use crate::network::WaitReason;
 
 use ratatui::Frame;
 use ratatui::layout::{Alignment, Constraint, Direction, Layout};
-use ratatui::prelude::{Color, Modifier, Style};
+use ratatui::prelude::{Modifier, Style};
 use ratatui::widgets::{Block, BorderType, Borders, Gauge, Paragraph};
 
 /// Render enhanced header with title and stage progress.
@@ -16,6 +17,7 @@ pub fn render_header(f: &mut Frame, area: ratatui::layout::Rect, state: &Dashboa
         .direction(Direction::Vertical)
         .constraints([Constraint::Length(2), Constraint::Length(2)])
         .split(area);
+    let palette = state.palette();
 
     // Title section with enhanced version display
     let version = env!("CARGO_PKG_VERSION");
@@ -30,9 +32,9 @@ pub fn render_header(f: &mut Frame, area: ratatui::layout::Rect, state: &Dashboa
     };
 
     let title_color = if state.update_available {
-        Color::LightYellow
+        palette.highlight
     } else {
-        Color::Cyan
+        palette.primary
     };
 
     let title = Paragraph::new(title_text)
@@ -58,7 +60,7 @@ pub fn render_header(f: &mut Frame, area: ratatui::layout::Rect, state: &Dashboa
                 let progress = ((state.tick % 20) as f64 / 20.0 * 100.0) as u16;
                 (
                     "PROVING - Generating proof".to_string(),
-                    Color::LightGreen,
+                    palette.active,
                     progress,
                 )
             }
@@ -76,20 +78,35 @@ pub fn render_header(f: &mut Frame, area: ratatui::layout::Rect, state: &Dashboa
                     } else {
                         100
                     };
+                    let label = match fetch_info.wait_reason {
+                        WaitReason::Pacing => "WAITING - Ready for next task",
+                        WaitReason::Backoff => "BACKING OFF - Retrying after an error",
+                        WaitReason::RateLimited => "RATE LIMITED by server - Retrying",
+                        WaitReason::Idle => "IDLE - No tasks available",
+                    };
                     let display_text = if remaining_secs > 0 {
-                        format!("WAITING - Ready for next task ({}s)", remaining_secs)
+                        format!("{} ({}s)", label, remaining_secs)
                     } else {
-                        "WAITING - Ready for next task".to_string()
+                        label.to_string()
+                    };
+                    let color = match fetch_info.wait_reason {
+                        WaitReason::Pacing | WaitReason::Idle => palette.secondary,
+                        WaitReason::Backoff | WaitReason::RateLimited => palette.warning,
                     };
-                    (display_text, Color::LightBlue, progress.min(100))
+                    (display_text, color, progress.min(100))
                 } else {
                     (
                         "WAITING - Ready for next task".to_string(),
-                        Color::LightBlue,
+                        palette.secondary,
                         100,
                     )
                 }
             }
+            ProverState::Paused => (
+                "PAUSED - Outside proving schedule".to_string(),
+                palette.muted,
+                0,
+            ),
         }
     };
 
@@ -97,7 +114,7 @@ pub fn render_header(f: &mut Frame, area: ratatui::layout::Rect, state: &Dashboa
         .block(
             Block::default()
                 .borders(Borders::BOTTOM)
-                .border_style(Style::default().fg(Color::DarkGray)),
+                .border_style(Style::default().fg(palette.muted)),
         )
         .gauge_style(
             Style::default()
@@ -5,14 +5,16 @@
 use crate::environment::Environment;
 
 use super::super::state::DashboardState;
+use super::super::utils::format_time_ago;
 use ratatui::Frame;
-use ratatui::prelude::{Color, Style};
+use ratatui::prelude::Style;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, BorderType, Borders, Padding, Paragraph, Wrap};
 
 /// Render enhanced info panel with better styling.
 pub fn render_info_panel(f: &mut Frame, area: ratatui::layout::Rect, state: &DashboardState) {
     let mut info_lines = Vec::new();
+    let palette = state.palette();
 
     // Node information with enhanced formatting
     let node_text = if let Some(id) = state.node_id {
@@ -22,15 +24,15 @@ pub fn render_info_panel(f: &mut Frame, area: ratatui::layout::Rect, state: &Das
     };
     info_lines.push(Line::from(vec![Span::styled(
         node_text,
-        Style::default().fg(Color::LightBlue),
+        Style::default().fg(palette.secondary),
     )]));
 
     // Environment with color coding
     let env_color = match state.environment {
-        Environment::Production => Color::Green,
+        Environment::Production => palette.success,
         Environment::Custom {
             orchestrator_url: _,
-        } => Color::Yellow,
+        } => palette.warning,
     };
     info_lines.push(Line::from(vec![Span::styled(
         format!("Env: {}", state.environment),
@@ -41,7 +43,7 @@ pub fn render_info_panel(f: &mut Frame, area: ratatui::layout::Rect, state: &Das
     let version = env!("CARGO_PKG_VERSION");
     info_lines.push(Line::from(vec![Span::styled(
         format!("Version: {}", version),
-        Style::default().fg(Color::Cyan),
+        Style::default().fg(palette.primary),
     )]));
 
     // Uptime with better formatting
@@ -69,19 +71,29 @@ pub fn render_info_panel(f: &mut Frame, area: ratatui::layout::Rect, state: &Das
     };
     info_lines.push(Line::from(vec![Span::styled(
         uptime_string,
-        Style::default().fg(Color::LightGreen),
+        Style::default().fg(palette.active),
+    )]));
+
+    // Time since the last accepted proof, so stalls stand out
+    let last_submit = state
+        .last_submission_at()
+        .map(|submitted_at| format_time_ago(submitted_at.elapsed().unwrap_or_default()))
+        .unwrap_or_else(|| "never".to_string());
+    info_lines.push(Line::from(vec![Span::styled(
+        format!("Last submit: {}", last_submit),
+        Style::default().fg(palette.warning),
     )]));
 
     // Threads info
     info_lines.push(Line::from(vec![Span::styled(
         format!("Threads: {}", state.num_threads),
-        Style::default().fg(Color::LightYellow),
+        Style::default().fg(palette.highlight),
     )]));
 
     // Total memory
     info_lines.push(Line::from(vec![Span::styled(
         format!("Memory: {:.1} GB", state.total_ram_gb),
-        Style::default().fg(Color::LightCyan),
+        Style::default().fg(palette.tertiary),
     )]));
 
     // Note: Task ID removed from system info as requested
@@ -90,7 +102,7 @@ pub fn render_info_panel(f: &mut Frame, area: ratatui::layout::Rect, state: &Das
         .title("SYSTEM INFO")
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
-        .border_style(Style::default().fg(Color::Cyan))
+        .border_style(Style::default().fg(palette.primary))
         .padding(Padding::uniform(1));
 
     let info_paragraph = Paragraph::new(info_lines)
@@ -7,7 +7,7 @@ use super::super::utils::{clean_http_error_message, format_compact_timestamp, ge
 use crate::events::EventType;
 use crate::logging::LogLevel;
 use ratatui::Frame;
-use ratatui::prelude::{Color, Style};
+use ratatui::prelude::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, BorderType, Borders, Padding, Paragraph, Wrap};
 
@@ -17,6 +17,7 @@ pub fn render_logs_panel(f: &mut Frame, area: ratatui::layout::Rect, state: &Das
     // Account for borders and padding (subtract 3 for top/bottom borders + padding)
     let max_logs = (area.height.saturating_sub(3)) as usize;
     let log_count = if max_logs > 0 { max_logs } else { 1 };
+    let palette = state.palette();
 
     let log_lines: Vec<Line> = state
         .activity_logs
@@ -32,21 +33,33 @@ pub fn render_logs_panel(f: &mut Frame, area: ratatui::layout::Rect, state: &Das
                 (EventType::Error, _) => "❌",
                 (EventType::Refresh, _) => "",
                 (EventType::Waiting, _) => "",
+                (EventType::Backoff, _) => "⏳",
                 (EventType::StateChange, _) => "", // StateChange events shouldn't be displayed, but add for completeness
+                (EventType::Milestone, _) => "",
+                (EventType::Skipped, _) => "",
             };
 
-            let worker_color = get_worker_color(&event.worker);
+            let worker_color = get_worker_color(&event.worker, &palette);
             let compact_time = format_compact_timestamp(&event.timestamp);
             let cleaned_msg = clean_http_error_message(&event.msg);
+            let msg_style = if event.event_type == EventType::Milestone {
+                Style::default()
+                    .fg(palette.highlight)
+                    .add_modifier(Modifier::BOLD)
+            } else if event.event_type == EventType::Backoff {
+                Style::default().fg(palette.warning)
+            } else {
+                Style::default().fg(worker_color)
+            };
 
             // Don't truncate - let ratatui handle wrapping naturally
             Line::from(vec![
                 Span::raw(format!("{} ", status_icon)),
                 Span::styled(
                     format!("{} ", compact_time),
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(palette.muted),
                 ),
-                Span::styled(cleaned_msg, Style::default().fg(worker_color)),
+                Span::styled(cleaned_msg, msg_style),
             ])
         })
         .collect();
@@ -61,7 +74,7 @@ pub fn render_logs_panel(f: &mut Frame, area: ratatui::layout::Rect, state: &Das
         .title("ACTIVITY LOG")
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
-        .border_style(Style::default().fg(Color::Cyan))
+        .border_style(Style::default().fg(palette.primary))
         .padding(Padding::uniform(1));
 
     let log_widget = log_paragraph.block(logs_block).wrap(Wrap { trim: true });
@@ -3,10 +3,10 @@
 //! Renders system and zkVM metrics
 
 use super::super::state::DashboardState;
-use super::super::utils::format_compact_timestamp;
+use crate::task::TaskTypeFilter;
 use ratatui::Frame;
 use ratatui::layout::{Constraint, Direction, Layout};
-use ratatui::prelude::{Color, Modifier, Style};
+use ratatui::prelude::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, BorderType, Borders, Gauge, Padding, Paragraph, Wrap};
 
@@ -14,16 +14,22 @@ use ratatui::widgets::{Block, BorderType, Borders, Gauge, Padding, Paragraph, Wr
 pub fn render_metrics_section(f: &mut Frame, area: ratatui::layout::Rect, state: &DashboardState) {
     let metrics_chunks = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .constraints([
+            Constraint::Percentage(40),
+            Constraint::Percentage(35),
+            Constraint::Percentage(25),
+        ])
         .split(area);
 
     render_system_metrics(f, metrics_chunks[0], state);
     render_zkvm_metrics(f, metrics_chunks[1], state);
+    render_rate_limit(f, metrics_chunks[2], state);
 }
 
 /// Render enhanced system metrics with better gauges.
 pub fn render_system_metrics(f: &mut Frame, area: ratatui::layout::Rect, state: &DashboardState) {
     let metrics = &state.system_metrics;
+    let palette = state.palette();
 
     // Responsive gauge layout - each gauge gets equal space
     let gauge_chunks = Layout::default()
@@ -42,11 +48,11 @@ pub fn render_system_metrics(f: &mut Frame, area: ratatui::layout::Rect, state:
                 .title("CPU Usage")
                 .borders(Borders::ALL)
                 .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(metrics.cpu_color())),
+                .border_style(Style::default().fg(metrics.cpu_color(&palette))),
         )
         .gauge_style(
             Style::default()
-                .fg(metrics.cpu_color())
+                .fg(metrics.cpu_color(&palette))
                 .add_modifier(Modifier::BOLD),
         )
         .percent((metrics.cpu_percent as u16).min(100))
@@ -59,11 +65,11 @@ pub fn render_system_metrics(f: &mut Frame, area: ratatui::layout::Rect, state:
                 .title("RAM Usage")
                 .borders(Borders::ALL)
                 .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(metrics.ram_color())),
+                .border_style(Style::default().fg(metrics.ram_color(&palette))),
         )
         .gauge_style(
             Style::default()
-                .fg(metrics.ram_color())
+                .fg(metrics.ram_color(&palette))
                 .add_modifier(Modifier::BOLD),
         )
         .percent((metrics.ram_ratio() * 100.0) as u16)
@@ -80,11 +86,11 @@ pub fn render_system_metrics(f: &mut Frame, area: ratatui::layout::Rect, state:
                 .title("Peak RAM")
                 .borders(Borders::ALL)
                 .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(Color::LightBlue)),
+                .border_style(Style::default().fg(palette.secondary)),
         )
         .gauge_style(
             Style::default()
-                .fg(Color::LightBlue)
+                .fg(palette.secondary)
                 .add_modifier(Modifier::BOLD),
         )
         .percent((metrics.peak_ram_ratio() * 100.0) as u16)
@@ -98,12 +104,13 @@ pub fn render_system_metrics(f: &mut Frame, area: ratatui::layout::Rect, state:
 /// Render enhanced zkVM metrics panel.
 pub fn render_zkvm_metrics(f: &mut Frame, area: ratatui::layout::Rect, state: &DashboardState) {
     let metrics = &state.zkvm_metrics;
+    let palette = state.palette();
     let mut zkvm_lines = Vec::new();
 
     // // Points display - most prominent metric
     // TODO: Add points display back in when we have a way to get points
     // zkvm_lines.push(Line::from(vec![
-    //     Span::styled("Points: ", Style::default().fg(Color::Gray)),
+    //     Span::styled("Points: ", Style::default().fg(palette.label)),
     //     Span::styled(
     //         metrics.format_points(),
     //         Style::default()
@@ -116,21 +123,21 @@ pub fn render_zkvm_metrics(f: &mut Frame, area: ratatui::layout::Rect, state: &D
 
     // Tasks statistics
     zkvm_lines.push(Line::from(vec![
-        Span::styled("Tasks: ", Style::default().fg(Color::Gray)),
+        Span::styled("Tasks: ", Style::default().fg(palette.label)),
         Span::styled(
             format!("{}", metrics.tasks_fetched),
             Style::default()
-                .fg(Color::White)
+                .fg(palette.value)
                 .add_modifier(Modifier::BOLD),
         ),
     ]));
 
     zkvm_lines.push(Line::from(vec![
-        Span::styled("Completed: ", Style::default().fg(Color::Gray)),
+        Span::styled("Completed: ", Style::default().fg(palette.label)),
         Span::styled(
             format!("{} / {}", metrics.tasks_submitted, metrics.tasks_fetched),
             Style::default()
-                .fg(Color::Green)
+                .fg(palette.success)
                 .add_modifier(Modifier::BOLD),
         ),
     ]));
@@ -138,48 +145,94 @@ pub fn render_zkvm_metrics(f: &mut Frame, area: ratatui::layout::Rect, state: &D
     // Success rate with color coding
     let success_text = format!("{:.1}%", metrics.success_rate());
     zkvm_lines.push(Line::from(vec![
-        Span::styled("Success: ", Style::default().fg(Color::Gray)),
+        Span::styled("Success: ", Style::default().fg(palette.label)),
         Span::styled(
             success_text,
             Style::default()
-                .fg(metrics.success_rate_color())
+                .fg(metrics.success_rate_color(&palette))
                 .add_modifier(Modifier::BOLD),
         ),
     ]));
 
     // Runtime information
     zkvm_lines.push(Line::from(vec![
-        Span::styled("Runtime: ", Style::default().fg(Color::Gray)),
-        Span::styled(metrics.format_runtime(), Style::default().fg(Color::Cyan)),
+        Span::styled("Runtime: ", Style::default().fg(palette.label)),
+        Span::styled(
+            metrics.format_runtime(),
+            Style::default().fg(palette.primary),
+        ),
     ]));
 
+    // Mean time from fetching a task to its proof being accepted
+    if let Some(latency) = metrics.average_latency_secs() {
+        zkvm_lines.push(Line::from(vec![
+            Span::styled("Latency: ", Style::default().fg(palette.label)),
+            Span::styled(
+                format!("{:.1}s avg", latency),
+                Style::default().fg(palette.primary),
+            ),
+        ]));
+    }
+
+    // Active task type filter, if any
+    if state.task_type_filter != TaskTypeFilter::Any {
+        zkvm_lines.push(Line::from(vec![
+            Span::styled("Task type: ", Style::default().fg(palette.label)),
+            Span::styled(
+                format!(
+                    "{} ({} skipped)",
+                    state.task_type_filter, metrics.tasks_skipped
+                ),
+                Style::default().fg(palette.accent),
+            ),
+        ]));
+    }
+
     // Last task info
     let status_color = match metrics.last_task_status.as_str() {
-        "Success" => Color::Green,
-        "Failed" => Color::Red,
-        _ => Color::Gray,
+        "Success" => palette.success,
+        "Failed" => palette.error,
+        _ => palette.label,
     };
     zkvm_lines.push(Line::from(vec![
-        Span::styled("Last: ", Style::default().fg(Color::Gray)),
+        Span::styled("Last: ", Style::default().fg(palette.label)),
         Span::styled(&metrics.last_task_status, Style::default().fg(status_color)),
     ]));
 
     // Show timestamp of last successful submission instead of duration
-    let last_submission_text = if let Some(timestamp) = state.last_submission_timestamp() {
-        format_compact_timestamp(timestamp)
+    let last_submission_text = if let Some(submitted_at) = state.last_submission_at() {
+        chrono::DateTime::<chrono::Local>::from(submitted_at)
+            .format("%m-%d %H:%M")
+            .to_string()
     } else {
         "Never".to_string()
     };
     zkvm_lines.push(Line::from(vec![
-        Span::styled("Last Proof: ", Style::default().fg(Color::Gray)),
-        Span::styled(last_submission_text, Style::default().fg(Color::Yellow)),
+        Span::styled("Last Proof: ", Style::default().fg(palette.label)),
+        Span::styled(last_submission_text, Style::default().fg(palette.warning)),
     ]));
 
+    // Per-node counts when proving for several nodes
+    if state.node_stats.len() > 1 {
+        for (node_id, stats) in &state.node_stats {
+            zkvm_lines.push(Line::from(vec![
+                Span::styled(
+                    format!("Node {}: ", node_id),
+                    Style::default().fg(palette.label),
+                ),
+                Span::styled(
+                    format!("{} / {}", stats.tasks_submitted, stats.tasks_fetched),
+                    Style::default().fg(palette.value),
+                ),
+            ]));
+        }
+    }
+
     let zkvm_block = Block::default()
         .title("zkVM STATS")
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
-        .border_style(Style::default().fg(Color::Cyan))
+        .border_style(Style::default().fg(palette.primary))
         .padding(Padding::uniform(1));
 
     let zkvm_paragraph = Paragraph::new(zkvm_lines)
@@ -187,3 +240,70 @@ pub fn render_zkvm_metrics(f: &mut Frame, area: ratatui::layout::Rect, state: &D
         .wrap(Wrap { trim: true });
     f.render_widget(zkvm_paragraph, area);
 }
+
+/// Render how much of the server's task fetch rate limit is used, so pacing is explained.
+pub fn render_rate_limit(f: &mut Frame, area: ratatui::layout::Rect, state: &DashboardState) {
+    let palette = state.palette();
+    let block = Block::default()
+        .title("FETCH RATE LIMIT")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(palette.primary))
+        .padding(Padding::horizontal(1));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let Some((usage, reported_at)) = state.fetch_rate_limit else {
+        f.render_widget(
+            Paragraph::new("Waiting for first fetch").style(Style::default().fg(palette.muted)),
+            inner,
+        );
+        return;
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Fill(1)])
+        .split(inner);
+
+    let color = if usage.used >= usage.max {
+        palette.error
+    } else if usage.ratio() >= 0.5 {
+        palette.warning
+    } else {
+        palette.success
+    };
+    let gauge = Gauge::default()
+        .gauge_style(Style::default().fg(color).add_modifier(Modifier::BOLD))
+        .percent((usage.ratio() * 100.0) as u16)
+        .label(format!("{} / {}", usage.used, usage.max));
+    f.render_widget(gauge, chunks[0]);
+
+    // Count down from the last report rather than waiting for the next event
+    let resets_in = usage
+        .resets_in_secs
+        .saturating_sub(reported_at.elapsed().as_secs());
+    let mut lines = vec![Line::from(vec![
+        Span::styled("Requests: ", Style::default().fg(palette.label)),
+        Span::styled(
+            format!("{} this window", usage.used),
+            Style::default().fg(palette.value),
+        ),
+    ])];
+    if usage.used > 0 && resets_in > 0 {
+        lines.push(Line::from(vec![
+            Span::styled("Resets in: ", Style::default().fg(palette.label)),
+            Span::styled(
+                format!("{}s", resets_in),
+                Style::default().fg(palette.value),
+            ),
+        ]));
+    }
+    if usage.used >= usage.max && resets_in > 0 {
+        lines.push(Line::from(Span::styled(
+            "Limit reached, waiting for the window",
+            Style::default().fg(palette.warning),
+        )));
+    }
+    f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: true }), chunks[1]);
+}
@@ -5,6 +5,7 @@
 pub mod components;
 pub mod renderer;
 pub mod state;
+pub mod theme;
 pub mod updaters;
 pub mod utils;
 
@@ -4,13 +4,13 @@ use super::components::{footer, header, info_panel, logs, metrics};
 use super::state::DashboardState;
 use ratatui::Frame;
 use ratatui::layout::{Constraint, Direction, Layout};
-use ratatui::prelude::{Color, Style};
+use ratatui::prelude::Style;
 use ratatui::widgets::Block;
 
 pub fn render_dashboard(f: &mut Frame, state: &DashboardState) {
     if state.with_background_color {
         f.render_widget(
-            Block::default().style(Style::default().bg(Color::Rgb(16, 20, 24))),
+            Block::default().style(Style::default().bg(state.palette().background)),
             f.area(),
         );
     }
@@ -36,5 +36,5 @@ pub fn render_dashboard(f: &mut Frame, state: &DashboardState) {
     info_panel::render_info_panel(f, content_chunks[0], state);
     logs::render_logs_panel(f, content_chunks[1], state);
     metrics::render_metrics_section(f, main_chunks[2], state);
-    footer::render_footer(f, main_chunks[3]);
+    footer::render_footer(f, main_chunks[3], state);
 }
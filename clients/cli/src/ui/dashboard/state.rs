@@ -5,11 +5,14 @@
 use crate::consts::cli_consts::MAX_ACTIVITY_LOGS;
 use crate::environment::Environment;
 use crate::events::{Event as WorkerEvent, ProverState};
+use crate::network::{RateLimitUsage, WaitReason};
+use crate::task::TaskTypeFilter;
 use crate::ui::app::UIConfig;
+use crate::ui::dashboard::theme::{Palette, Theme};
 use crate::ui::metrics::{SystemMetrics, TaskFetchInfo, ZkVMMetrics};
 
-use std::collections::VecDeque;
-use std::time::Instant;
+use std::collections::{BTreeMap, VecDeque};
+use std::time::{Instant, SystemTime};
 use sysinfo::System;
 
 /// State for tracking fetching operations
@@ -20,6 +23,13 @@ pub enum FetchingState {
     Timeout,
 }
 
+/// Task counts of one node when a process proves for several
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NodeStats {
+    pub tasks_fetched: u32,
+    pub tasks_submitted: u32,
+}
+
 /// Enhanced dashboard state with real-time metrics and animations.
 #[derive(Debug)]
 pub struct DashboardState {
@@ -47,6 +57,10 @@ pub struct DashboardState {
     pub latest_version: Option<String>,
     /// Whether to enable background colors
     pub with_background_color: bool,
+    /// Task types being proved
+    pub task_type_filter: TaskTypeFilter,
+    /// Color theme
+    pub theme: Theme,
 
     /// System metrics (CPU, RAM, etc.)
     pub system_metrics: SystemMetrics,
@@ -54,11 +68,17 @@ pub struct DashboardState {
     pub zkvm_metrics: ZkVMMetrics,
     /// Task fetch information for accurate timing
     pub task_fetch_info: TaskFetchInfo,
+    /// Latest task fetch rate-limit usage and when it was reported
+    pub fetch_rate_limit: Option<(RateLimitUsage, Instant)>,
     /// Animation tick counter
     pub tick: usize,
+    /// Whether the first-proof milestone has been shown this run
+    pub first_proof_done: bool,
+    /// Task counts per node, from events attributed to a node
+    pub node_stats: BTreeMap<u64, NodeStats>,
 
-    /// Timestamp of last successful proof submission
-    last_submission_timestamp: Option<String>,
+    /// Time of last successful proof submission
+    last_submission_at: Option<SystemTime>,
     /// Current fetching state (active, timeout, idle)
     fetching_state: FetchingState,
     /// Persistent system info instance for accurate CPU measurements
@@ -67,8 +87,8 @@ pub struct DashboardState {
     current_prover_state: ProverState,
     /// Track when Step 2 started for current task
     pub step2_start_time: Option<Instant>,
-    /// Track the start time and original wait duration for current waiting period
-    pub waiting_start_info: Option<(Instant, u64)>, // (start_time, original_wait_secs)
+    /// Track the start time, original wait duration and reason for current waiting period
+    pub waiting_start_info: Option<(Instant, u64, WaitReason)>, // (start_time, original_wait_secs, reason)
 }
 
 impl DashboardState {
@@ -92,12 +112,17 @@ impl DashboardState {
             update_available: ui_config.update_available,
             latest_version: ui_config.latest_version,
             with_background_color: ui_config.with_background_color,
+            task_type_filter: ui_config.task_type_filter,
+            theme: ui_config.theme,
 
             system_metrics: SystemMetrics::default(),
             zkvm_metrics: ZkVMMetrics::default(),
             task_fetch_info: TaskFetchInfo::default(),
+            fetch_rate_limit: None,
             tick: 0,
-            last_submission_timestamp: None,
+            first_proof_done: false,
+            node_stats: BTreeMap::new(),
+            last_submission_at: None,
             fetching_state: FetchingState::Idle,
             sysinfo: System::new_all(), // Initialize with all data for first refresh
             current_prover_state: ProverState::Waiting,
@@ -105,13 +130,18 @@ impl DashboardState {
             waiting_start_info: None,
         }
     }
+    /// Colors of the selected theme
+    pub fn palette(&self) -> Palette {
+        self.theme.palette()
+    }
+
     // Getter methods for private fields
     pub fn fetching_state(&self) -> &FetchingState {
         &self.fetching_state
     }
 
-    pub fn last_submission_timestamp(&self) -> &Option<String> {
-        &self.last_submission_timestamp
+    pub fn last_submission_at(&self) -> Option<SystemTime> {
+        self.last_submission_at
     }
 
     // Setter methods for private fields (for updaters)
@@ -127,8 +157,8 @@ impl DashboardState {
         self.current_prover_state = state;
     }
 
-    pub fn set_last_submission_timestamp(&mut self, timestamp: Option<String>) {
-        self.last_submission_timestamp = timestamp;
+    pub fn set_last_submission_at(&mut self, submitted_at: Option<SystemTime>) {
+        self.last_submission_at = submitted_at;
     }
 
     pub fn get_sysinfo_mut(&mut self) -> &mut System {
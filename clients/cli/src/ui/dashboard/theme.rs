@@ -0,0 +1,130 @@
+//! Dashboard color themes
+//!
+//! Components take their colors from the [`Palette`] of the selected [`Theme`] instead of
+//! naming colors directly, so every panel switches together.
+
+use ratatui::prelude::Color;
+
+/// Color theme for the dashboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum Theme {
+    /// Bright colors for dark terminal backgrounds
+    #[default]
+    Dark,
+    /// Darker colors for light terminal backgrounds
+    Light,
+    /// Bright, bold colors only, with no dim text
+    HighContrast,
+}
+
+/// Colors used by the dashboard, by role
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Palette {
+    /// Panel borders, titles and the footer
+    pub primary: Color,
+    /// Node ID, peak RAM and the waiting gauge
+    pub secondary: Color,
+    /// Memory info
+    pub tertiary: Color,
+    /// Uptime and the proving gauge
+    pub active: Color,
+    /// Available updates and thread count
+    pub highlight: Color,
+    /// Active task type filter
+    pub accent: Color,
+    /// Good status and proof submission logs
+    pub success: Color,
+    /// Middling status and proving logs
+    pub warning: Color,
+    /// Bad status
+    pub error: Color,
+    /// Labels in front of values
+    pub label: Color,
+    /// Prominent values
+    pub value: Color,
+    /// Timestamps, secondary borders and paused state
+    pub muted: Color,
+    /// Background fill used with `--with-background`
+    pub background: Color,
+}
+
+impl Theme {
+    pub fn palette(&self) -> Palette {
+        match self {
+            Theme::Dark => Palette {
+                primary: Color::Cyan,
+                secondary: Color::LightBlue,
+                tertiary: Color::LightCyan,
+                active: Color::LightGreen,
+                highlight: Color::LightYellow,
+                accent: Color::LightMagenta,
+                success: Color::Green,
+                warning: Color::Yellow,
+                error: Color::Red,
+                label: Color::Gray,
+                value: Color::White,
+                muted: Color::DarkGray,
+                background: Color::Rgb(16, 20, 24),
+            },
+            Theme::Light => Palette {
+                primary: Color::Blue,
+                secondary: Color::Rgb(0, 90, 160),
+                tertiary: Color::Rgb(0, 110, 110),
+                active: Color::Rgb(0, 120, 0),
+                highlight: Color::Rgb(150, 80, 0),
+                accent: Color::Magenta,
+                success: Color::Rgb(0, 120, 0),
+                warning: Color::Rgb(150, 80, 0),
+                error: Color::Rgb(170, 0, 0),
+                label: Color::DarkGray,
+                value: Color::Black,
+                muted: Color::Rgb(100, 100, 100),
+                background: Color::Rgb(245, 245, 245),
+            },
+            Theme::HighContrast => Palette {
+                primary: Color::White,
+                secondary: Color::LightCyan,
+                tertiary: Color::LightCyan,
+                active: Color::LightGreen,
+                highlight: Color::LightYellow,
+                accent: Color::LightMagenta,
+                success: Color::LightGreen,
+                warning: Color::LightYellow,
+                error: Color::LightRed,
+                label: Color::White,
+                value: Color::White,
+                muted: Color::White,
+                background: Color::Black,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_high_contrast_has_no_dim_colors() {
+        let palette = Theme::HighContrast.palette();
+        for color in [
+            palette.primary,
+            palette.secondary,
+            palette.tertiary,
+            palette.active,
+            palette.highlight,
+            palette.accent,
+            palette.success,
+            palette.warning,
+            palette.error,
+            palette.label,
+            palette.value,
+            palette.muted,
+        ] {
+            assert!(!matches!(
+                color,
+                Color::DarkGray | Color::Gray | Color::Black
+            ));
+        }
+    }
+}
@@ -5,9 +5,10 @@
 use super::state::{DashboardState, FetchingState};
 
 use crate::events::{Event as WorkerEvent, EventType, Worker};
+use crate::network::WaitReason;
 use crate::ui::metrics::{SystemMetrics, TaskFetchInfo};
 
-use std::time::Instant;
+use std::time::{Instant, SystemTime};
 
 impl DashboardState {
     /// Update the dashboard state with new tick and metrics.
@@ -25,6 +26,14 @@ impl DashboardState {
 
         // Process all queued events one by one
         while let Some(event) = self.pending_events.pop_front() {
+            // Celebrate the first proof only once per run
+            if event.event_type == EventType::Milestone && event.worker == Worker::ProofSubmitter {
+                if self.first_proof_done {
+                    continue;
+                }
+                self.first_proof_done = true;
+            }
+
             // Add to activity logs for display
             self.add_to_activity_log(event.clone());
 
@@ -47,6 +56,10 @@ impl DashboardState {
             Worker::ProofSubmitter => self.handle_proof_submitter_event(event),
         }
 
+        if let Some(node_id) = event.node_id {
+            self.update_node_stats(node_id, event);
+        }
+
         // Handle state changes regardless of worker
         if event.event_type == EventType::StateChange {
             if let Some(state) = event.prover_state {
@@ -55,6 +68,27 @@ impl DashboardState {
         }
     }
 
+    /// Count fetched and submitted tasks for the node an event belongs to
+    fn update_node_stats(&mut self, node_id: u64, event: &WorkerEvent) {
+        if event.event_type != EventType::Success {
+            return;
+        }
+        let stats = self.node_stats.entry(node_id).or_default();
+        match event.worker {
+            Worker::TaskFetcher if event.msg.contains("Step 1 of 4: Got task") => {
+                stats.tasks_fetched += 1;
+            }
+            Worker::ProofSubmitter
+                if event
+                    .msg
+                    .contains("Step 4 of 4: Proof submitted successfully") =>
+            {
+                stats.tasks_submitted += 1;
+            }
+            _ => {}
+        }
+    }
+
     /// Handle TaskFetcher events
     fn handle_task_fetcher_event(&mut self, event: &WorkerEvent) {
         // Handle task ID extraction from "Got task" success events
@@ -72,6 +106,16 @@ impl DashboardState {
             }
         }
 
+        // Keep the latest rate-limit usage for the rate limit panel
+        if let Some(usage) = event.rate_limit {
+            self.fetch_rate_limit = Some((usage, Instant::now()));
+        }
+
+        // Count tasks skipped by the task type filter
+        if event.event_type == EventType::Skipped {
+            self.zkvm_metrics.tasks_skipped += 1;
+        }
+
         // Handle fetching state changes
         if Self::is_completion_event(event) {
             self.set_fetching_state(FetchingState::Idle);
@@ -83,17 +127,29 @@ impl DashboardState {
             });
         }
 
-        // Handle waiting messages for task fetch info
-        if event.msg.contains("ready for next task") {
-            if let Some(seconds) = Self::extract_wait_seconds(&event.msg) {
-                let is_same_message = match &self.waiting_start_info {
-                    Some((_, prev_wait)) => *prev_wait == seconds,
-                    None => false,
-                };
-
-                if !is_same_message {
-                    self.waiting_start_info = Some((Instant::now(), seconds));
+        // Handle waiting messages for task fetch info. Events recorded before waits were
+        // attached only carry the pacing wait in the message.
+        let wait = event
+            .fetch_wait
+            .map(|wait| (wait.secs, wait.reason))
+            .or_else(|| {
+                event
+                    .msg
+                    .contains("ready for next task")
+                    .then(|| Self::extract_wait_seconds(&event.msg))
+                    .flatten()
+                    .map(|seconds| (seconds, WaitReason::Pacing))
+            });
+        if let Some((seconds, reason)) = wait {
+            let is_same_message = match &self.waiting_start_info {
+                Some((_, prev_wait, prev_reason)) => {
+                    *prev_wait == seconds && *prev_reason == reason
                 }
+                None => false,
+            };
+
+            if !is_same_message {
+                self.waiting_start_info = Some((Instant::now(), seconds, reason));
             }
         }
     }
@@ -130,8 +186,14 @@ impl DashboardState {
                 .tasks_fetched
                 .max(self.zkvm_metrics.tasks_submitted);
 
+            if let Some(latency_ms) = event.latency_ms {
+                self.zkvm_metrics.total_latency_ms += latency_ms;
+                self.zkvm_metrics.timed_submissions += 1;
+            }
+
             self.zkvm_metrics.last_task_status = "Success".to_string();
-            self.set_last_submission_timestamp(Some(event.timestamp.clone()));
+            // Replayed events report when they were recorded, not when they were read
+            self.set_last_submission_at(Some(event.time().unwrap_or_else(SystemTime::now)));
 
             // Update total points
             self.zkvm_metrics._total_points = (self.zkvm_metrics.tasks_submitted as u64) * 300;
@@ -142,7 +204,7 @@ impl DashboardState {
 
     /// Update task fetch countdown based on current waiting state
     fn update_task_fetch_countdown(&mut self) {
-        if let Some((start_time, original_secs)) = &self.waiting_start_info {
+        if let Some((start_time, original_secs, reason)) = &self.waiting_start_info {
             let elapsed_secs = start_time.elapsed().as_secs();
             let remaining_secs = original_secs.saturating_sub(elapsed_secs);
 
@@ -150,6 +212,7 @@ impl DashboardState {
                 backoff_duration_secs: *original_secs,
                 time_since_last_fetch_secs: elapsed_secs,
                 can_fetch_now: remaining_secs == 0,
+                wait_reason: *reason,
             };
 
             // Clear expired countdown
@@ -158,11 +221,7 @@ impl DashboardState {
             }
         } else {
             // No active countdown, assume we can fetch
-            self.task_fetch_info = TaskFetchInfo {
-                backoff_duration_secs: 0,
-                time_since_last_fetch_secs: 0,
-                can_fetch_now: true,
-            };
+            self.task_fetch_info = TaskFetchInfo::default();
         }
     }
 
@@ -2,15 +2,17 @@
 //!
 //! Contains helper functions used across dashboard components
 
+use super::theme::Palette;
 use crate::events::Worker;
 use ratatui::prelude::Color;
+use std::time::Duration;
 
 /// Get a ratatui color for a worker based on its type
-pub fn get_worker_color(worker: &Worker) -> Color {
+pub fn get_worker_color(worker: &Worker, palette: &Palette) -> Color {
     match worker {
-        Worker::TaskFetcher => Color::Cyan,
-        Worker::Prover(_) => Color::Yellow,
-        Worker::ProofSubmitter => Color::Green,
+        Worker::TaskFetcher => palette.primary,
+        Worker::Prover(_) => palette.warning,
+        Worker::ProofSubmitter => palette.success,
     }
 }
 
@@ -33,6 +35,18 @@ pub fn format_compact_timestamp(timestamp: &str) -> String {
     timestamp.to_string()
 }
 
+/// Format an elapsed duration as a short relative time, e.g. "42s ago" or "3m 5s ago"
+pub fn format_time_ago(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    if secs < 60 {
+        format!("{}s ago", secs)
+    } else if secs < 3600 {
+        format!("{}m {}s ago", secs / 60, secs % 60)
+    } else {
+        format!("{}h {}m ago", secs / 3600, (secs % 3600) / 60)
+    }
+}
+
 /// Clean HTTP error messages
 pub fn clean_http_error_message(msg: &str) -> String {
     // Replace verbose HTTP error patterns with cleaner messages
@@ -48,3 +62,16 @@ pub fn clean_http_error_message(msg: &str) -> String {
     // Return original message if no HTTP error pattern detected
     msg.to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_time_ago() {
+        assert_eq!(format_time_ago(Duration::from_secs(0)), "0s ago");
+        assert_eq!(format_time_ago(Duration::from_secs(59)), "59s ago");
+        assert_eq!(format_time_ago(Duration::from_secs(185)), "3m 5s ago");
+        assert_eq!(format_time_ago(Duration::from_secs(7_380)), "2h 3m ago");
+    }
+}
@@ -1,5 +1,7 @@
 //! System metrics collection and display.
 
+use crate::network::WaitReason;
+use crate::ui::dashboard::theme::Palette;
 use std::time::Instant;
 use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
 
@@ -161,27 +163,25 @@ impl SystemMetrics {
     }
 
     /// Get CPU gauge color based on usage.
-    pub fn cpu_color(&self) -> ratatui::prelude::Color {
-        use ratatui::prelude::Color;
+    pub fn cpu_color(&self, palette: &Palette) -> ratatui::prelude::Color {
         if self.cpu_percent >= 80.0 {
-            Color::Red
+            palette.error
         } else if self.cpu_percent >= 60.0 {
-            Color::Yellow
+            palette.warning
         } else {
-            Color::Green
+            palette.success
         }
     }
 
     /// Get RAM gauge color based on usage.
-    pub fn ram_color(&self) -> ratatui::prelude::Color {
-        use ratatui::prelude::Color;
+    pub fn ram_color(&self, palette: &Palette) -> ratatui::prelude::Color {
         let ratio = self.ram_ratio();
         if ratio >= 0.8 {
-            Color::Red
+            palette.error
         } else if ratio >= 0.6 {
-            Color::Yellow
+            palette.warning
         } else {
-            Color::Green
+            palette.success
         }
     }
 }
@@ -193,8 +193,14 @@ pub struct ZkVMMetrics {
     pub tasks_fetched: usize,
     /// Number of tasks successfully proved.
     pub tasks_submitted: usize,
+    /// Number of fetched tasks skipped by the `--task-type` filter.
+    pub tasks_skipped: usize,
     /// Total zkVM runtime in seconds.
     pub zkvm_runtime_secs: u64,
+    /// Sum of fetch-to-acceptance latencies of submitted proofs, in milliseconds.
+    pub total_latency_ms: u64,
+    /// Number of submissions included in `total_latency_ms`.
+    pub timed_submissions: usize,
     /// Status of the last task.
     pub last_task_status: String,
     /// Total points earned from successful proofs (300 points each).
@@ -206,7 +212,10 @@ impl Default for ZkVMMetrics {
         Self {
             tasks_fetched: 0,
             tasks_submitted: 0,
+            tasks_skipped: 0,
             zkvm_runtime_secs: 0,
+            total_latency_ms: 0,
+            timed_submissions: 0,
             last_task_status: "None".to_string(),
             _total_points: 0,
         }
@@ -215,14 +224,22 @@ impl Default for ZkVMMetrics {
 
 impl ZkVMMetrics {
     /// Calculate success rate as a percentage.
+    /// Skipped tasks are not counted as attempts.
     pub fn success_rate(&self) -> f64 {
-        if self.tasks_fetched == 0 {
+        let attempted = self.tasks_fetched.saturating_sub(self.tasks_skipped);
+        if attempted == 0 {
             0.0
         } else {
-            (self.tasks_submitted as f64 / self.tasks_fetched as f64) * 100.0
+            (self.tasks_submitted as f64 / attempted as f64) * 100.0
         }
     }
 
+    /// Mean fetch-to-acceptance latency in seconds, if any submission has been timed.
+    pub fn average_latency_secs(&self) -> Option<f64> {
+        (self.timed_submissions > 0)
+            .then(|| self.total_latency_ms as f64 / 1000.0 / self.timed_submissions as f64)
+    }
+
     /// Format total points with commas for better readability.
     pub fn _format_points(&self) -> String {
         let points = self._total_points;
@@ -236,15 +253,14 @@ impl ZkVMMetrics {
     }
 
     /// Get success rate color based on performance.
-    pub fn success_rate_color(&self) -> ratatui::prelude::Color {
-        use ratatui::prelude::Color;
+    pub fn success_rate_color(&self, palette: &Palette) -> ratatui::prelude::Color {
         let rate = self.success_rate();
         if rate >= 75.0 {
-            Color::Green
+            palette.success
         } else if rate >= 50.0 {
-            Color::Yellow
+            palette.warning
         } else {
-            Color::Red
+            palette.error
         }
     }
 
@@ -273,6 +289,8 @@ pub struct TaskFetchInfo {
     pub time_since_last_fetch_secs: u64,
     /// Whether we can fetch now (no backoff).
     pub can_fetch_now: bool,
+    /// Why the fetcher is waiting.
+    pub wait_reason: WaitReason,
 }
 
 impl Default for TaskFetchInfo {
@@ -281,6 +299,7 @@ impl Default for TaskFetchInfo {
             backoff_duration_secs: 0,
             time_since_last_fetch_secs: 0,
             can_fetch_now: true,
+            wait_reason: WaitReason::Pacing,
         }
     }
 }
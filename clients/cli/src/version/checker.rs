@@ -74,10 +74,17 @@
 //! 4. **Test timing**: Use configurable intervals for faster tests
 //! 5. **Clean shutdown**: Always test graceful shutdown scenarios
 
-use reqwest::{Client, ClientBuilder};
+use crate::events::{Event, EventType};
+use crate::logging::LogLevel;
+use crate::storage::save_json_best_effort;
+use reqwest::header::{ETAG, HeaderMap, IF_NONE_MATCH};
+use reqwest::{Client, StatusCode};
 use semver::Version;
 use serde::{Deserialize, Serialize};
-use std::time::{Duration, Instant};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 #[cfg(test)]
 use mockall::{automock, predicate::*};
@@ -86,6 +93,86 @@ use mockall::{automock, predicate::*};
 const GITHUB_RELEASES_URL: &str =
     "https://api.github.com/repos/nexus-xyz/nexus-cli/releases/latest";
 
+/// Environment variable that replaces [`GITHUB_RELEASES_URL`], e.g. with an internal mirror.
+/// It must serve the same JSON as the GitHub "latest release" endpoint.
+pub const RELEASES_URL_ENV: &str = "NEXUS_RELEASES_URL";
+
+/// Backoff used when GitHub signals a rate limit without saying when it resets
+const DEFAULT_RATE_LIMIT_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Until when GitHub API requests are skipped after a rate-limit response.
+/// Many nodes behind one IP share GitHub's per-IP quota, so we wait for the reset.
+static RATE_LIMITED_UNTIL: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// The GitHub API rate limit was hit; no request is made until it resets
+#[derive(Debug, thiserror::Error)]
+#[error("GitHub API rate limit exceeded, retrying in {}s", retry_after.as_secs())]
+pub struct RateLimited {
+    pub retry_after: Duration,
+}
+
+/// How long to back off if `status` and `headers` describe a GitHub rate-limit response.
+///
+/// GitHub answers with 403 (primary limit, `X-RateLimit-Remaining: 0`) or 403/429 with
+/// `Retry-After` (secondary limit). `Retry-After` wins; otherwise `X-RateLimit-Reset`
+/// (epoch seconds) is used relative to `now_unix`.
+fn rate_limit_backoff(status: StatusCode, headers: &HeaderMap, now_unix: u64) -> Option<Duration> {
+    if status != StatusCode::FORBIDDEN && status != StatusCode::TOO_MANY_REQUESTS {
+        return None;
+    }
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::trim)
+    };
+
+    if let Some(secs) = header("retry-after").and_then(|v| v.parse::<u64>().ok()) {
+        return Some(Duration::from_secs(secs));
+    }
+    if header("x-ratelimit-remaining") == Some("0") {
+        let wait = header("x-ratelimit-reset")
+            .and_then(|v| v.parse::<u64>().ok())
+            .map(|reset| Duration::from_secs(reset.saturating_sub(now_unix)))
+            .unwrap_or(DEFAULT_RATE_LIMIT_BACKOFF);
+        return Some(wait);
+    }
+    // A 429 is always a rate limit, even without headers
+    (status == StatusCode::TOO_MANY_REQUESTS).then_some(DEFAULT_RATE_LIMIT_BACKOFF)
+}
+
+/// `X-RateLimit-Remaining` from the last GitHub API response, if it had one
+static RATE_LIMIT_REMAINING: Mutex<Option<u64>> = Mutex::new(None);
+
+/// The `X-RateLimit-Remaining` header of a response, if present and numeric
+fn parse_rate_limit_remaining(headers: &HeaderMap) -> Option<u64> {
+    headers
+        .get("x-ratelimit-remaining")?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// A debug event reporting how many GitHub API requests this IP has left, if the last
+/// release check saw the `X-RateLimit-Remaining` header
+pub fn rate_limit_remaining_event() -> Option<Event> {
+    let remaining = (*RATE_LIMIT_REMAINING.lock().ok()?)?;
+    Some(Event::task_fetcher_with_level(
+        format!("GitHub API requests remaining: {}", remaining),
+        EventType::Refresh,
+        LogLevel::Debug,
+    ))
+}
+
+/// Remaining backoff from an earlier rate-limit response, if any
+fn rate_limit_remaining() -> Option<Duration> {
+    let until = (*RATE_LIMITED_UNTIL.lock().ok()?)?;
+    let remaining = until.saturating_duration_since(Instant::now());
+    (!remaining.is_zero()).then_some(remaining)
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GitHubRelease {
     pub tag_name: String,
@@ -95,6 +182,33 @@ pub struct GitHubRelease {
     pub prerelease: bool,
 }
 
+/// Get the path to the latest release cache, typically ~/.nexus/latest_release.json.
+fn get_release_cache_path() -> Option<PathBuf> {
+    Some(home::home_dir()?.join(".nexus").join("latest_release.json"))
+}
+
+/// The last release fetched, as saved to disk with the `ETag` it was served with
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedRelease {
+    /// Endpoint the release came from, so a changed `NEXUS_RELEASES_URL` isn't answered
+    /// from another endpoint's cache
+    url: String,
+    etag: String,
+    release: GitHubRelease,
+}
+
+impl CachedRelease {
+    /// The cached release for `url`, if one was saved
+    fn load(path: &Path, url: &str) -> Option<Self> {
+        let cached: Self = serde_json::from_slice(&fs::read(path).ok()?).ok()?;
+        (cached.url == url).then_some(cached)
+    }
+
+    fn save(&self, path: &Path) {
+        save_json_best_effort(path, self);
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct VersionInfo {
     pub current_version: String,
@@ -150,39 +264,109 @@ pub trait VersionCheckable: Send + Sync {
 /// Version checker client for making GitHub API requests
 pub struct VersionChecker {
     client: Client,
+    releases_url: String,
+    /// Where the last release and its `ETag` are kept for conditional requests
+    cache_path: Option<PathBuf>,
 }
 
 impl VersionChecker {
     pub fn new(current_version: String) -> Self {
-        let client = ClientBuilder::new()
+        let client = crate::network::proxy::client_builder()
             .timeout(Duration::from_secs(10))
             .user_agent(format!("nexus-cli/{}", current_version))
             .build()
             .expect("Failed to create HTTP client for version checker");
 
-        Self { client }
+        Self {
+            client,
+            releases_url: releases_url(std::env::var(RELEASES_URL_ENV).ok()),
+            cache_path: get_release_cache_path(),
+        }
     }
 }
 
+/// The latest-release endpoint to query: the override if it is non-empty, otherwise GitHub
+fn releases_url(override_url: Option<String>) -> String {
+    override_url
+        .map(|url| url.trim().to_string())
+        .filter(|url| !url.is_empty())
+        .unwrap_or_else(|| GITHUB_RELEASES_URL.to_string())
+}
+
 #[async_trait::async_trait]
 impl VersionCheckable for VersionChecker {
-    /// Check for latest version from GitHub API
+    /// Check for latest version from GitHub API. The request is conditional on the `ETag`
+    /// of the last release seen, so an unchanged release costs no rate-limit quota.
     async fn check_latest_version(
         &self,
     ) -> Result<GitHubRelease, Box<dyn std::error::Error + Send + Sync>> {
-        let response = self.client.get(GITHUB_RELEASES_URL).send().await?;
+        if let Some(retry_after) = rate_limit_remaining() {
+            return Err(RateLimited { retry_after }.into());
+        }
+
+        let cached = self
+            .cache_path
+            .as_deref()
+            .and_then(|path| CachedRelease::load(path, &self.releases_url));
+        let mut request = self.client.get(&self.releases_url);
+        if let Some(cached) = &cached {
+            request = request.header(IF_NONE_MATCH, &cached.etag);
+        }
+        let response = request.send().await?;
+
+        if let Some(remaining) = parse_rate_limit_remaining(response.headers()) {
+            if let Ok(mut last) = RATE_LIMIT_REMAINING.lock() {
+                *last = Some(remaining);
+            }
+        }
+
+        let now_unix = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        if let Some(retry_after) =
+            rate_limit_backoff(response.status(), response.headers(), now_unix)
+        {
+            if let Ok(mut until) = RATE_LIMITED_UNTIL.lock() {
+                *until = Some(Instant::now() + retry_after);
+            }
+            return Err(RateLimited { retry_after }.into());
+        }
+
+        if response.status() == StatusCode::NOT_MODIFIED {
+            if let Some(cached) = cached {
+                return Ok(cached.release);
+            }
+        }
 
         if !response.status().is_success() {
             return Err(format!("GitHub API returned status: {}", response.status()).into());
         }
 
+        let etag = response
+            .headers()
+            .get(ETAG)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
         let release: GitHubRelease = response.json().await?;
+        if let (Some(etag), Some(path)) = (etag, &self.cache_path) {
+            CachedRelease {
+                url: self.releases_url.clone(),
+                etag,
+                release: release.clone(),
+            }
+            .save(path);
+        }
         Ok(release)
     }
 }
 
-/// Check if a new version is available and return notification message
+/// Check if a new version is available and return notification message. Returns `None`
+/// without a request when version checks are disabled.
 pub async fn check_for_new_version(current_version: &str) -> Option<String> {
+    if !super::manager::version_checks_enabled() {
+        return None;
+    }
     let version_checker = VersionChecker::new(current_version.to_string());
 
     if let Ok(release) = version_checker.check_latest_version().await {
@@ -204,6 +388,52 @@ pub async fn check_for_new_version(current_version: &str) -> Option<String> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_cached_release_is_keyed_by_url() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("latest_release.json");
+        CachedRelease {
+            url: GITHUB_RELEASES_URL.to_string(),
+            etag: "\"abc\"".to_string(),
+            release: GitHubRelease {
+                tag_name: "v0.9.1".to_string(),
+                name: "Release v0.9.1".to_string(),
+                published_at: "2024-01-01T00:00:00Z".to_string(),
+                html_url: "https://github.com/nexus-xyz/nexus-cli/releases/tag/v0.9.1".to_string(),
+                prerelease: false,
+            },
+        }
+        .save(&path);
+
+        let cached = CachedRelease::load(&path, GITHUB_RELEASES_URL).unwrap();
+        assert_eq!(cached.etag, "\"abc\"");
+        assert_eq!(cached.release.tag_name, "v0.9.1");
+        assert!(CachedRelease::load(&path, "https://mirror.example.com/latest").is_none());
+        assert!(
+            CachedRelease::load(&dir.path().join("missing.json"), GITHUB_RELEASES_URL).is_none()
+        );
+    }
+
+    #[test]
+    fn test_parse_rate_limit_remaining() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(parse_rate_limit_remaining(&headers), None);
+        headers.insert("x-ratelimit-remaining", "57".parse().unwrap());
+        assert_eq!(parse_rate_limit_remaining(&headers), Some(57));
+        headers.insert("x-ratelimit-remaining", "lots".parse().unwrap());
+        assert_eq!(parse_rate_limit_remaining(&headers), None);
+    }
+
+    #[test]
+    fn test_releases_url_override() {
+        assert_eq!(releases_url(None), GITHUB_RELEASES_URL);
+        assert_eq!(releases_url(Some("  ".to_string())), GITHUB_RELEASES_URL);
+        assert_eq!(
+            releases_url(Some("https://mirror.example.com/latest".to_string())),
+            "https://mirror.example.com/latest"
+        );
+    }
+
     #[test]
     fn test_version_comparison() {
         // Test version comparison logic
@@ -244,6 +474,52 @@ mod tests {
         assert_eq!(info.latest_version, Some("v0.9.1".to_string()));
     }
 
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.insert(*name, value.parse().unwrap());
+        }
+        map
+    }
+
+    #[test]
+    fn test_rate_limit_backoff() {
+        let now = 1_700_000_000;
+
+        // Primary rate limit: wait until the reset time
+        let primary = headers(&[
+            ("x-ratelimit-remaining", "0"),
+            ("x-ratelimit-reset", "1700000300"),
+        ]);
+        assert_eq!(
+            rate_limit_backoff(StatusCode::FORBIDDEN, &primary, now),
+            Some(Duration::from_secs(300))
+        );
+
+        // Secondary rate limit: Retry-After takes precedence
+        let secondary = headers(&[("retry-after", "42"), ("x-ratelimit-remaining", "0")]);
+        assert_eq!(
+            rate_limit_backoff(StatusCode::FORBIDDEN, &secondary, now),
+            Some(Duration::from_secs(42))
+        );
+
+        // 429 without headers still backs off
+        assert_eq!(
+            rate_limit_backoff(StatusCode::TOO_MANY_REQUESTS, &HeaderMap::new(), now),
+            Some(DEFAULT_RATE_LIMIT_BACKOFF)
+        );
+
+        // Other 403s and statuses are not rate limits
+        assert_eq!(
+            rate_limit_backoff(StatusCode::FORBIDDEN, &HeaderMap::new(), now),
+            None
+        );
+        assert_eq!(
+            rate_limit_backoff(StatusCode::NOT_FOUND, &primary, now),
+            None
+        );
+    }
+
     #[test]
     fn test_edge_case_version_comparisons() {
         // Test various edge cases with semver
@@ -1,21 +1,67 @@
 //! Version management and validation with improved error messages
 use super::{ConstraintType, VersionRequirements};
+use crate::consts::cli_consts::{
+    VERSION_BLOCKED_EXIT_CODE, VERSION_RECHECK_INTERVAL_SECS, VERSION_RECHECK_JITTER,
+};
+use crate::events::EventType;
+use crate::logging::LogLevel;
+use crate::session::summary::ShutdownReason;
+use crate::workers::core::EventSender;
+use rand::Rng;
 use std::error::Error;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tokio::sync::broadcast;
 
-/// Validates version requirements before application startup
+/// Where to download a CLI version that meets the requirements
+const RELEASES_URL: &str = "https://github.com/nexus-xyz/nexus-cli/releases";
+
+/// Setting this to any value has the same effect as `--no-version-check`
+pub const NO_VERSION_CHECK_ENV: &str = "NEXUS_NO_VERSION_CHECK";
+
+/// Whether `--no-version-check` turned off version checks
+static VERSION_CHECKS_DISABLED: AtomicBool = AtomicBool::new(false);
+
+/// Skip version checks for the rest of the process
+pub fn disable_version_checks() {
+    VERSION_CHECKS_DISABLED.store(true, Ordering::Relaxed);
+}
+
+/// Whether to check for new releases and enforce the published version requirements
+pub fn version_checks_enabled() -> bool {
+    !VERSION_CHECKS_DISABLED.load(Ordering::Relaxed)
+        && std::env::var_os(NO_VERSION_CHECK_ENV).is_none()
+}
+
+/// Validates version requirements before application startup. With version checks
+/// disabled the requirements are still fetched for the OFAC country check, but the
+/// version constraints are not evaluated and a failed fetch doesn't stop startup.
 pub async fn validate_version_requirements() -> Result<(), Box<dyn Error>> {
     // Single attempt since VersionRequirements::fetch already tries multiple hostnames
     let requirements = match VersionRequirements::fetch().await {
         Ok(requirements) => requirements,
+        Err(e) if !version_checks_enabled() => {
+            eprintln!(
+                "⚠️  Unable to fetch version requirements, continuing: {}",
+                e
+            );
+            return Ok(());
+        }
         Err(e) => {
             handle_fetch_error(&e);
             std::process::exit(1);
         }
     };
 
+    // A bad entry in the published file shouldn't stop the node; say which one was skipped
+    for malformed in &requirements.malformed_constraints {
+        eprintln!("⚠️  Skipping {}", malformed);
+    }
+
     let current_version = env!("CARGO_PKG_VERSION");
 
-    // Early OFAC block from server-provided list, if present
+    // Early OFAC block from server-provided list, if present. Uses --country when given,
+    // which --no-geo requires, so no lookup is made then
     let country = crate::orchestrator::client::detect_country_once().await;
 
     // Restriction check is against keys; printed names come from non-null values
@@ -36,6 +82,10 @@ pub async fn validate_version_requirements() -> Result<(), Box<dyn Error>> {
         std::process::exit(1);
     }
 
+    if !version_checks_enabled() {
+        return Ok(());
+    }
+
     match requirements.check_version_constraints(current_version, None, None) {
         Ok(Some(violation)) => {
             handle_version_violation(&violation.constraint_type, &violation.message);
@@ -71,8 +121,8 @@ fn handle_version_violation(constraint_type: &ConstraintType, message: &str) {
             eprintln!("{}\n", message);
             eprintln!("To resolve this issue:");
             eprintln!("  • Download the latest version from:");
-            eprintln!("    https://github.com/nexus-xyz/nexus-cli/releases");
-            std::process::exit(1);
+            eprintln!("    {}", RELEASES_URL);
+            std::process::exit(VERSION_BLOCKED_EXIT_CODE);
         }
         ConstraintType::Warning => {
             eprintln!("⚠️  Version Warning");
@@ -85,3 +135,72 @@ fn handle_version_violation(constraint_type: &ConstraintType, message: &str) {
         }
     }
 }
+
+/// Message of the blocking constraint this CLI version violates, if any. A failed fetch or
+/// unparsable requirements are not treated as blocking; they were checked at startup.
+async fn blocking_violation() -> Option<String> {
+    let requirements = VersionRequirements::fetch().await.ok()?;
+    match requirements.check_version_constraints(env!("CARGO_PKG_VERSION"), None, None) {
+        Ok(Some(violation)) if violation.constraint_type == ConstraintType::Blocking => {
+            Some(violation.message)
+        }
+        _ => None,
+    }
+}
+
+/// The re-check interval shifted by `jitter`, a fraction in
+/// [-[`VERSION_RECHECK_JITTER`], [`VERSION_RECHECK_JITTER`]]
+fn recheck_interval(jitter: f64) -> Duration {
+    Duration::from_secs(VERSION_RECHECK_INTERVAL_SECS).mul_f64(1.0 + jitter)
+}
+
+/// Re-check the version requirements every [`VERSION_RECHECK_INTERVAL_SECS`], give or take
+/// [`VERSION_RECHECK_JITTER`] picked once per process, while the session runs. When a
+/// blocking constraint is published for this version, report it as an error event and end
+/// the session with [`ShutdownReason::VersionBlocked`]. Warnings and notices are only shown
+/// at startup.
+pub async fn watch_version_requirements(
+    event_sender: EventSender,
+    shutdown_sender: broadcast::Sender<ShutdownReason>,
+    mut shutdown: broadcast::Receiver<()>,
+) {
+    let interval = recheck_interval(
+        rand::thread_rng().gen_range(-VERSION_RECHECK_JITTER..=VERSION_RECHECK_JITTER),
+    );
+    loop {
+        tokio::select! {
+            _ = shutdown.recv() => return,
+            _ = tokio::time::sleep(interval) => {}
+        }
+        if let Some(message) = blocking_violation().await {
+            event_sender
+                .send_task_event(
+                    format!(
+                        "Version requirement not met, stopping: {}. Download the latest version from {}",
+                        message, RELEASES_URL
+                    ),
+                    EventType::Error,
+                    LogLevel::Error,
+                )
+                .await;
+            let _ = shutdown_sender.send(ShutdownReason::VersionBlocked);
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recheck_interval_jitter() {
+        let base = Duration::from_secs(VERSION_RECHECK_INTERVAL_SECS);
+        assert_eq!(recheck_interval(0.0), base);
+        assert_eq!(
+            recheck_interval(-VERSION_RECHECK_JITTER),
+            base.mul_f64(1.0 - VERSION_RECHECK_JITTER)
+        );
+        assert!(recheck_interval(VERSION_RECHECK_JITTER) > base);
+    }
+}
@@ -11,6 +11,17 @@ const FALLBACK_CONFIG_URL: &str =
     "https://raw.githubusercontent.com/nexus-xyz/nexus-cli/refs/heads/main/public/version.json";
 const CONFIG_TIMEOUT: Duration = Duration::from_secs(10);
 
+/// Environment variable holding a version config URL to use instead of the built-in ones,
+/// e.g. an internal mirror. When set, only that URL is tried.
+pub const VERSION_CONFIG_URL_ENV: &str = "NEXUS_VERSION_CONFIG_URL";
+
+/// The version config URL set in [`VERSION_CONFIG_URL_ENV`], if it is non-empty
+fn config_url_override(value: Option<String>) -> Option<String> {
+    value
+        .map(|url| url.trim().to_string())
+        .filter(|url| !url.is_empty())
+}
+
 #[derive(Error, Debug)]
 pub enum VersionRequirementsError {
     #[error("Failed to fetch config: {0}")]
@@ -24,11 +35,59 @@ pub enum VersionRequirementsError {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(from = "RawVersionRequirements")]
 pub struct VersionRequirements {
+    /// Valid constraints; malformed entries are moved to `malformed_constraints`
     pub version_constraints: Vec<VersionConstraint>,
     /// Map of OFAC-restricted country/region codes to display names.
-    #[serde(default, alias = "ofac_restricted_map")]
     pub ofac_country_names: HashMap<String, Option<String>>,
+    /// Entries of `version_constraints` that were skipped because they are malformed
+    #[serde(skip_serializing)]
+    pub malformed_constraints: Vec<MalformedConstraint>,
+}
+
+/// Version requirements as published, before each constraint is validated
+#[derive(Deserialize)]
+struct RawVersionRequirements {
+    version_constraints: Vec<serde_json::Value>,
+    #[serde(default, alias = "ofac_restricted_map")]
+    ofac_country_names: HashMap<String, Option<String>>,
+}
+
+/// A `version_constraints` entry that can't be used, e.g. with an unknown `type` or a
+/// `version` that isn't valid semver
+#[derive(Error, Debug, Clone, PartialEq)]
+#[error("version_constraints[{index}] is malformed: {reason}")]
+pub struct MalformedConstraint {
+    /// Position of the entry in `version_constraints`
+    pub index: usize,
+    pub reason: String,
+}
+
+/// Validate one published constraint
+fn parse_constraint(value: serde_json::Value) -> Result<VersionConstraint, String> {
+    let constraint: VersionConstraint = serde_json::from_value(value).map_err(|e| e.to_string())?;
+    Version::parse(&constraint.version)
+        .map_err(|e| format!("invalid version '{}': {}", constraint.version, e))?;
+    Ok(constraint)
+}
+
+impl From<RawVersionRequirements> for VersionRequirements {
+    fn from(raw: RawVersionRequirements) -> Self {
+        let mut version_constraints = Vec::new();
+        let mut malformed_constraints = Vec::new();
+        for (index, value) in raw.version_constraints.into_iter().enumerate() {
+            match parse_constraint(value) {
+                Ok(constraint) => version_constraints.push(constraint),
+                Err(reason) => malformed_constraints.push(MalformedConstraint { index, reason }),
+            }
+        }
+        Self {
+            version_constraints,
+            ofac_country_names: raw.ofac_country_names,
+            malformed_constraints,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -55,16 +114,37 @@ pub struct VersionCheckResult {
     pub message: String,
 }
 
+/// Current time in seconds since the UNIX epoch.
+///
+/// A clock set before the epoch is treated as "now = 0", so every constraint with a
+/// start date is consistently considered not yet active instead of failing the check.
+fn unix_now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
 impl VersionRequirements {
     /// Fetch version requirements from remote config with multiple fallbacks
-    /// Priority: Firebase Hosting -> Cloud Function Cache -> GitHub
+    /// Priority: Firebase Hosting -> Cloud Function Cache -> GitHub, unless
+    /// [`VERSION_CONFIG_URL_ENV`] names a URL to fetch from instead
     pub async fn fetch() -> Result<Self, VersionRequirementsError> {
-        let client = Client::builder()
+        let client = crate::network::proxy::client_builder()
             .timeout(CONFIG_TIMEOUT)
             .user_agent("nexus-cli/version-checker")
             .build()
             .expect("Failed to create HTTP client");
 
+        if let Some(url) = config_url_override(std::env::var(VERSION_CONFIG_URL_ENV).ok()) {
+            return Self::fetch_from_url(&client, &url).await.map_err(|e| {
+                VersionRequirementsError::Fetch(format!(
+                    "Failed to fetch from {} ({}): {}",
+                    VERSION_CONFIG_URL_ENV, url, e
+                ))
+            });
+        }
+
         // Try primary URL first (Firebase Hosting)
         match Self::fetch_from_url(&client, PRIMARY_CONFIG_URL).await {
             Ok(config) => Ok(config),
@@ -119,18 +199,31 @@ impl VersionRequirements {
         Ok(config)
     }
 
-    /// Check all version constraints and return the most severe violation
+    /// Check all version constraints and return the most severe violation. Constraints
+    /// with an unparseable version are skipped.
     pub fn check_version_constraints(
         &self,
         current_version: &str,
         latest_version: Option<&str>,
         release_url: Option<&str>,
+    ) -> Result<Option<VersionCheckResult>, VersionRequirementsError> {
+        self.check_version_constraints_at(
+            current_version,
+            latest_version,
+            release_url,
+            unix_now_secs(),
+        )
+    }
+
+    /// Check all version constraints as of `now` (seconds since the UNIX epoch)
+    fn check_version_constraints_at(
+        &self,
+        current_version: &str,
+        latest_version: Option<&str>,
+        release_url: Option<&str>,
+        now: u64,
     ) -> Result<Option<VersionCheckResult>, VersionRequirementsError> {
         let current = Version::parse(current_version.strip_prefix('v').unwrap_or(current_version))?;
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .map_err(|e| VersionRequirementsError::Fetch(format!("System time error: {}", e)))?
-            .as_secs();
 
         let mut most_severe_violation: Option<VersionCheckResult> = None;
 
@@ -142,7 +235,10 @@ impl VersionRequirements {
                 }
             }
 
-            let min_version = Version::parse(&constraint.version)?;
+            // Malformed entries are reported when fetched; don't let one fail the check
+            let Ok(min_version) = Version::parse(&constraint.version) else {
+                continue;
+            };
 
             if current < min_version {
                 // This constraint is violated
@@ -209,6 +305,18 @@ impl VersionRequirements {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_config_url_override() {
+        assert_eq!(config_url_override(None), None);
+        assert_eq!(config_url_override(Some(String::new())), None);
+        assert_eq!(
+            config_url_override(Some(
+                " https://mirror.example.com/version.json ".to_string()
+            )),
+            Some("https://mirror.example.com/version.json".to_string())
+        );
+    }
+
     #[test]
     fn test_version_comparison() {
         let config = VersionRequirements {
@@ -227,6 +335,7 @@ mod tests {
                 },
             ],
             ofac_country_names: Default::default(),
+            malformed_constraints: Vec::new(),
         };
 
         // Test constraint checking
@@ -272,6 +381,7 @@ mod tests {
                 },
             ],
             ofac_country_names: Default::default(),
+            malformed_constraints: Vec::new(),
         };
 
         // Test that versions with 'v' prefix are handled correctly
@@ -305,6 +415,7 @@ mod tests {
                 },
             ],
             ofac_country_names: Default::default(),
+            malformed_constraints: Vec::new(),
         };
 
         // Test that blocking takes precedence over warning and notice
@@ -329,6 +440,7 @@ mod tests {
                 start_date: None,
             }],
             ofac_country_names: Default::default(),
+            malformed_constraints: Vec::new(),
         };
 
         let result = config
@@ -341,4 +453,100 @@ mod tests {
         assert!(message.contains("1.1.0"));
         assert!(message.contains("https://example.com"));
     }
+
+    #[test]
+    fn test_clock_before_epoch_treats_now_as_zero() {
+        let config = VersionRequirements {
+            version_constraints: vec![
+                VersionConstraint {
+                    version: "1.0.0".to_string(),
+                    constraint_type: ConstraintType::Blocking,
+                    message: "Blocking: {current} < {version}".to_string(),
+                    start_date: Some(1),
+                },
+                VersionConstraint {
+                    version: "1.0.0".to_string(),
+                    constraint_type: ConstraintType::Notice,
+                    message: "Notice: {current} < {version}".to_string(),
+                    start_date: None,
+                },
+            ],
+            ofac_country_names: Default::default(),
+            malformed_constraints: Vec::new(),
+        };
+
+        // Dated constraints are not yet active at now = 0; undated ones always are
+        let result = config
+            .check_version_constraints_at("0.9.0", None, None, 0)
+            .unwrap();
+        assert!(matches!(
+            result.unwrap().constraint_type,
+            ConstraintType::Notice
+        ));
+    }
+
+    #[test]
+    fn test_malformed_constraints_are_reported_and_skipped() {
+        let json = r#"{
+            "version_constraints": [
+                {"version": "not-a-version", "type": "blocking", "message": "Bad version"},
+                {"version": "1.0.0", "type": "fatal", "message": "Bad type"},
+                {"version": "1.0.0", "type": "warning", "message": "Update to {version}"}
+            ]
+        }"#;
+        let config: VersionRequirements = serde_json::from_str(json).unwrap();
+
+        assert_eq!(config.version_constraints.len(), 1);
+        let indices: Vec<usize> = config
+            .malformed_constraints
+            .iter()
+            .map(|malformed| malformed.index)
+            .collect();
+        assert_eq!(indices, vec![0, 1]);
+        assert!(
+            config.malformed_constraints[0]
+                .to_string()
+                .contains("invalid version 'not-a-version'")
+        );
+        assert!(config.malformed_constraints[1].reason.contains("fatal"));
+
+        // The valid constraint still applies
+        let result = config
+            .check_version_constraints("0.9.0", None, None)
+            .unwrap();
+        assert!(matches!(
+            result.unwrap().constraint_type,
+            ConstraintType::Warning
+        ));
+    }
+
+    #[test]
+    fn test_check_skips_unparseable_version() {
+        let config = VersionRequirements {
+            version_constraints: vec![
+                VersionConstraint {
+                    version: "1.x".to_string(),
+                    constraint_type: ConstraintType::Blocking,
+                    message: "Blocking: {current} < {version}".to_string(),
+                    start_date: None,
+                },
+                VersionConstraint {
+                    version: "1.0.0".to_string(),
+                    constraint_type: ConstraintType::Notice,
+                    message: "Notice: {current} < {version}".to_string(),
+                    start_date: None,
+                },
+            ],
+            ofac_country_names: Default::default(),
+            malformed_constraints: Vec::new(),
+        };
+
+        let result = config
+            .check_version_constraints("0.9.0", None, None)
+            .unwrap();
+        assert!(matches!(
+            result.unwrap().constraint_type,
+            ConstraintType::Notice
+        ));
+    }
 }
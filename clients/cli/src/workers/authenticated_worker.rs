@@ -1,15 +1,27 @@
 //! Single authenticated worker that orchestrates fetch→prove→submit
 
-use super::core::{EventSender, WorkerConfig};
+use super::core::{EventSender, OnSubmitFailure, OnWorkerDeath, WorkerConfig};
 use super::fetcher::TaskFetcher;
-use super::prover::TaskProver;
+use super::prefetch::{FetchMode, PrefetchController};
+use super::prover::{ProveError, TaskProver};
+use super::schedule::ProvingSchedule;
 use super::submitter::ProofSubmitter;
-use crate::events::{Event, ProverState};
+use crate::consts::cli_consts::{
+    MAX_WORKER_RESTARTS, WORKER_DEATH_THRESHOLD, WORKER_RESTART_BACKOFF_SECS, task_fetching,
+};
+use crate::events::{Event, EventType, ProverState};
+use crate::logging::LogLevel;
 use crate::orchestrator::OrchestratorClient;
+use crate::prover::ProverResult;
+use crate::prover::format::{get_unsubmitted_proofs_dir, save_proof_result};
+use crate::session::summary::ShutdownReason;
+use crate::storage::describe_write_error;
+use crate::task::{Task, TaskTypeFilter};
 
 use ed25519_dalek::SigningKey;
-use std::time::Duration;
-use tokio::sync::{broadcast, mpsc};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
 use tokio::task::JoinHandle;
 
 /// Single authenticated worker that handles the complete task lifecycle
@@ -20,7 +32,26 @@ pub struct AuthenticatedWorker {
     event_sender: EventSender,
     max_tasks: Option<u32>,
     tasks_completed: u32,
-    shutdown_sender: broadcast::Sender<()>,
+    shutdown_sender: broadcast::Sender<ShutdownReason>,
+    schedule: Option<ProvingSchedule>,
+    /// Whether fetching is currently paused by the schedule
+    paused: bool,
+    task_type_filter: TaskTypeFilter,
+    /// Tasks skipped because of `task_type_filter`
+    tasks_skipped: u32,
+    /// Config used to start a fresh prover after the workers die
+    config: WorkerConfig,
+    /// Session key tasks are fetched with; saved with proofs so they can be submitted later
+    signing_key: SigningKey,
+    /// Consecutive tasks on which the proving workers died
+    consecutive_worker_deaths: u32,
+    /// Prover restarts since the last successful proof
+    worker_restarts: u32,
+    prefetch: PrefetchController,
+    /// Tasks fetched while earlier ones were being proved, oldest first
+    prefetched: VecDeque<Task>,
+    /// When the worker started, for `--max-uptime`
+    started_at: Instant,
 }
 
 impl AuthenticatedWorker {
@@ -29,12 +60,10 @@ impl AuthenticatedWorker {
         signing_key: SigningKey,
         orchestrator: OrchestratorClient,
         config: WorkerConfig,
-        event_sender: mpsc::Sender<Event>,
+        event_sender_helper: EventSender,
         max_tasks: Option<u32>,
-        shutdown_sender: broadcast::Sender<()>,
+        shutdown_sender: broadcast::Sender<ShutdownReason>,
     ) -> Self {
-        let event_sender_helper = EventSender::new(event_sender);
-
         // Create the 3 specialized components
         let fetcher = TaskFetcher::new(
             node_id,
@@ -44,10 +73,14 @@ impl AuthenticatedWorker {
             &config,
         );
 
-        let prover = TaskProver::new(event_sender_helper.clone(), config.clone());
+        let schedule = config.schedule;
+        let task_type_filter = config.task_type_filter;
+        let prover = TaskProver::new(event_sender_helper.clone(), config.clone())
+            .with_signing_key(signing_key.clone());
 
         let submitter = ProofSubmitter::new(
-            signing_key,
+            node_id,
+            signing_key.clone(),
             Box::new(orchestrator),
             event_sender_helper.clone(),
             &config,
@@ -61,6 +94,20 @@ impl AuthenticatedWorker {
             max_tasks,
             tasks_completed: 0,
             shutdown_sender,
+            schedule,
+            paused: false,
+            task_type_filter,
+            tasks_skipped: 0,
+            config,
+            signing_key,
+            consecutive_worker_deaths: 0,
+            worker_restarts: 0,
+            prefetch: PrefetchController::new(
+                task_fetching::rate_limit_interval(),
+                config.queue_size,
+            ),
+            prefetched: VecDeque::new(),
+            started_at: Instant::now(),
         }
     }
 
@@ -96,39 +143,346 @@ impl AuthenticatedWorker {
         join_handles
     }
 
+    /// Pause while outside the proving schedule, reporting transitions.
+    /// Returns true if the worker is paused and should not fetch this cycle.
+    async fn wait_for_schedule(&mut self) -> bool {
+        let Some(schedule) = self.schedule else {
+            return false;
+        };
+        let until_active = schedule.time_until_active(chrono::Local::now().time());
+        if until_active.is_zero() {
+            if self.paused {
+                self.paused = false;
+                self.event_sender
+                    .send_event(Event::state_change(
+                        ProverState::Waiting,
+                        format!("Inside proving schedule {}, resuming", schedule),
+                    ))
+                    .await;
+            }
+            return false;
+        }
+
+        if !self.paused {
+            self.paused = true;
+            self.event_sender
+                .send_event(Event::state_change(
+                    ProverState::Paused,
+                    format!(
+                        "Outside proving schedule {}, pausing for {}m",
+                        schedule,
+                        until_active.as_secs().div_ceil(60)
+                    ),
+                ))
+                .await;
+        }
+        // Re-check periodically so clock changes are picked up
+        tokio::time::sleep(until_active.min(Duration::from_secs(60))).await;
+        true
+    }
+
+    /// Apply `--on-worker-death` once the proving workers have died on several consecutive
+    /// tasks, so the node doesn't keep fetching tasks it can't prove.
+    /// Returns true if the worker should exit.
+    async fn handle_worker_death(&mut self) -> bool {
+        let deaths = self.consecutive_worker_deaths;
+        self.consecutive_worker_deaths = 0;
+
+        if self.config.on_worker_death == OnWorkerDeath::Restart
+            && self.worker_restarts < MAX_WORKER_RESTARTS
+        {
+            self.worker_restarts += 1;
+            let backoff =
+                Duration::from_secs(WORKER_RESTART_BACKOFF_SECS * u64::from(self.worker_restarts));
+            self.event_sender
+                .send_prover_event(
+                    0,
+                    format!(
+                        "Proving workers died on {} consecutive tasks, restarting in {}s ({}/{})",
+                        deaths,
+                        backoff.as_secs(),
+                        self.worker_restarts,
+                        MAX_WORKER_RESTARTS
+                    ),
+                    EventType::Error,
+                    LogLevel::Warn,
+                )
+                .await;
+            tokio::time::sleep(backoff).await;
+            self.prover = TaskProver::new(self.event_sender.clone(), self.config.clone())
+                .with_signing_key(self.signing_key.clone());
+            return false;
+        }
+
+        self.event_sender
+            .send_prover_event(
+                0,
+                format!(
+                    "Fatal: proving workers died on {} consecutive tasks, shutting down instead of fetching tasks that can't be proved",
+                    deaths
+                ),
+                EventType::Error,
+                LogLevel::Error,
+            )
+            .await;
+        let _ = self.shutdown_sender.send(ShutdownReason::WorkersDied);
+        true
+    }
+
+    /// Report a `--fail-fast` failure and trigger shutdown with `reason`.
+    /// Returns true so the worker exits.
+    async fn fail_fast(&mut self, task: &Task, message: String, reason: ShutdownReason) -> bool {
+        self.event_sender
+            .send_event(
+                Event::prover_with_level(0, message, EventType::Error, LogLevel::Error)
+                    .with_task_id(&task.task_id),
+            )
+            .await;
+        let _ = self.shutdown_sender.send(reason);
+        true
+    }
+
+    /// Whether `--max-uptime` has been reached. Checked before fetching a new task, so the
+    /// task in hand is always finished first.
+    fn max_uptime_reached(&self) -> bool {
+        self.config
+            .max_uptime
+            .is_some_and(|max| self.started_at.elapsed() >= max)
+    }
+
+    /// Keep or report a proof whose submission failed, per `--on-submit-failure`, so
+    /// completed work is never dropped silently
+    async fn handle_unsubmitted_proof(&mut self, task: &Task, proof_result: &ProverResult) {
+        // `--save-proofs` already saved it when it was generated
+        let message = if let Some(dir) = &self.config.save_proofs_dir {
+            format!(
+                "Proof for task {} was not submitted; it is saved in {}, run `nexus-cli submit-saved --dir {}` to retry",
+                task.task_id,
+                dir.display(),
+                dir.display()
+            )
+        } else if self.config.on_submit_failure == OnSubmitFailure::Save {
+            let saved = get_unsubmitted_proofs_dir()
+                .map_err(Into::into)
+                .and_then(|dir| {
+                    save_proof_result(
+                        &dir,
+                        task,
+                        self.config.proof_format,
+                        proof_result,
+                        Some(&self.signing_key),
+                    )
+                    .map(|()| dir)
+                });
+            match saved {
+                Ok(dir) => format!(
+                    "Proof for task {} was not submitted; saved it to {}, run `nexus-cli submit-saved --dir {}` to retry",
+                    task.task_id,
+                    dir.display(),
+                    dir.display()
+                ),
+                Err(e) => format!(
+                    "Proof for task {} was not submitted and could not be saved, so it was discarded: {}",
+                    task.task_id, e
+                ),
+            }
+        } else {
+            format!(
+                "Proof for task {} was not submitted and was discarded; pass --on-submit-failure save to keep such proofs",
+                task.task_id
+            )
+        };
+        self.event_sender
+            .send_event(
+                Event::proof_submitter_with_level(message, EventType::Error, LogLevel::Error)
+                    .with_task_id(&task.task_id),
+            )
+            .await;
+    }
+
+    /// How many tasks to hold fetched ahead of the one being proved. Never queues a task
+    /// that `--max-tasks` would leave unproved.
+    fn tasks_to_queue(&self) -> usize {
+        if self.config.fetch_mode != FetchMode::Adaptive {
+            return 0;
+        }
+        let ahead = self.prefetch.max_ahead();
+        match self.max_tasks {
+            Some(max) => ahead.min(max.saturating_sub(self.tasks_completed + 1) as usize),
+            None => ahead,
+        }
+    }
+
     /// Complete work cycle: fetch→prove→submit
-    /// Returns true if the worker should exit (max tasks reached)
+    /// Returns true if the worker should exit (max tasks or max uptime reached, proving
+    /// workers died, or a task failed under `--fail-fast`)
     async fn work_cycle(&mut self) -> bool {
-        // Step 1: Fetch task
-        let task = match self.fetcher.fetch_task().await {
-            Ok(task) => task,
-            Err(_) => {
-                // Error already logged in fetcher, wait before retry
-                tokio::time::sleep(Duration::from_secs(1)).await;
-                return false; // Don't exit on fetch error, just retry
-            }
+        if self.prefetched.is_empty() && self.max_uptime_reached() {
+            self.event_sender
+                .send_event(Event::state_change(
+                    ProverState::Waiting,
+                    "Max uptime reached, shutting down".to_string(),
+                ))
+                .await;
+            let _ = self.shutdown_sender.send(ShutdownReason::MaxUptimeReached);
+            return true;
+        }
+
+        // Only fetch new tasks inside the proving schedule; an already fetched task is proved
+        if self.prefetched.is_empty() && self.wait_for_schedule().await {
+            return false;
+        }
+
+        // Step 1: Fetch task, unless one was fetched during the previous proof
+        let task = match self.prefetched.pop_front() {
+            Some(task) => task,
+            None => match self.fetcher.fetch_task().await {
+                Ok(task) => task,
+                Err(_) => {
+                    // Error already logged in fetcher, wait before retry
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                    return false; // Don't exit on fetch error, just retry
+                }
+            },
         };
 
-        // Time starts from successfully obtaining the task
-        let start_time = std::time::Instant::now();
+        // Skip task types this node doesn't want and fetch another
+        if !self.task_type_filter.accepts(task.task_type) {
+            self.tasks_skipped += 1;
+            self.event_sender
+                .send_event(
+                    Event::task_fetcher_with_level(
+                        format!(
+                            "Skipping task {} of type {} (--task-type {}), {} skipped so far",
+                            task.task_id,
+                            task.task_type.as_str_name(),
+                            self.task_type_filter,
+                            self.tasks_skipped
+                        ),
+                        EventType::Skipped,
+                        LogLevel::Info,
+                    )
+                    .with_task_id(&task.task_id),
+                )
+                .await;
+            return false;
+        }
+
+        // Don't tie up the worker on a task with an unreasonable number of inputs
+        if task.exceeds_input_limit(self.config.max_inputs_per_task) {
+            self.event_sender
+                .send_event(
+                    Event::task_fetcher_with_level(
+                        format!(
+                            "Rejecting task {} with {} inputs (--max-inputs-per-task {})",
+                            task.task_id,
+                            task.all_inputs().len(),
+                            self.config.max_inputs_per_task.unwrap_or_default()
+                        ),
+                        EventType::Skipped,
+                        LogLevel::Warn,
+                    )
+                    .with_task_id(&task.task_id),
+                )
+                .await;
+            return false;
+        }
 
         // Step 2: Prove task
         // Send state change to Proving
         self.event_sender
-            .send_event(Event::state_change(
-                ProverState::Proving,
-                format!("Step 2 of 4: Proving task {}", task.task_id),
-            ))
+            .send_event(
+                Event::state_change(
+                    ProverState::Proving,
+                    format!("Step 2 of 4: Proving task {}", task.task_id),
+                )
+                .with_task_id(&task.task_id),
+            )
             .await;
 
-        let proof_result = match self.prover.prove_task(&task).await {
-            Ok(proof_result) => proof_result,
-            Err(_) => {
+        // Fetch the next tasks alongside proving this one when that keeps the worker busy.
+        // Proving time is measured inside the proof future so fetch waits don't count.
+        self.prefetch
+            .set_max_in_flight(self.fetcher.max_concurrent_tasks());
+        let queue_target = self.tasks_to_queue();
+        let (proof_result, proving_time) = {
+            let prover = &mut self.prover;
+            let fetcher = &mut self.fetcher;
+            let prefetched = &mut self.prefetched;
+            let prove = async {
+                let started = Instant::now();
+                let result = prover.prove_task(&task).await;
+                (result, started.elapsed())
+            };
+            tokio::pin!(prove);
+            let mut proved = None;
+            // Top the queue up while the proof runs
+            while proved.is_none() && prefetched.len() < queue_target {
+                // Stop waiting on the rate limit as soon as the proof is done
+                tokio::select! {
+                    result = &mut prove => {
+                        proved = Some(result);
+                        break;
+                    }
+                    () = fetcher.wait_for_fetch_slot() => {}
+                }
+                // A request that has been sent is finished, so the assigned task isn't lost
+                let fetch = fetcher.fetch_ready_task();
+                tokio::pin!(fetch);
+                let fetched = tokio::select! {
+                    result = &mut prove => {
+                        proved = Some(result);
+                        fetch.await
+                    }
+                    fetched = &mut fetch => fetched,
+                };
+                match fetched {
+                    Ok(next_task) => prefetched.push_back(next_task),
+                    Err(_) => break,
+                }
+            }
+            match proved {
+                Some(proved) => proved,
+                None => prove.await,
+            }
+        };
+
+        let proof_result = match proof_result {
+            Ok(proof_result) => {
+                self.prefetch.record_proving_time(proving_time);
+                self.consecutive_worker_deaths = 0;
+                self.worker_restarts = 0;
+                proof_result
+            }
+            Err(e) => {
+                if e.is_worker_death() {
+                    self.consecutive_worker_deaths += 1;
+                    if self.consecutive_worker_deaths >= WORKER_DEATH_THRESHOLD {
+                        return self.handle_worker_death().await;
+                    }
+                } else {
+                    self.consecutive_worker_deaths = 0;
+                }
+
+                if self.config.fail_fast {
+                    return self
+                        .fail_fast(
+                            &task,
+                            format!("Fatal: proving task {} failed: {}", task.task_id, e),
+                            ShutdownReason::ProvingFailed,
+                        )
+                        .await;
+                }
+
+                let message = match e {
+                    ProveError::Unprovable { .. } => "Task unprovable, ready for next task",
+                    ProveError::Generation(_) => "Proof generation failed, ready for next task",
+                };
                 // Send state change back to Waiting on proof failure
                 self.event_sender
                     .send_event(Event::state_change(
                         ProverState::Waiting,
-                        "Proof generation failed, ready for next task".to_string(),
+                        message.to_string(),
                     ))
                     .await;
                 return false; // Don't exit on proof error, just retry
@@ -136,31 +490,60 @@ impl AuthenticatedWorker {
         };
 
         // Step 3: Submit proof
+        let submission_started = Instant::now();
         let submission_result = self.submitter.submit_proof(&task, &proof_result).await;
 
+        if let Err(e) = &submission_result {
+            self.handle_unsubmitted_proof(&task, &proof_result).await;
+            if self.config.fail_fast {
+                return self
+                    .fail_fast(
+                        &task,
+                        format!(
+                            "Fatal: submitting the proof for task {} failed: {}",
+                            task.task_id, e
+                        ),
+                        ShutdownReason::SubmissionFailed,
+                    )
+                    .await;
+            }
+        }
+
         // Only increment task counter on successful submission
         if submission_result.is_ok() {
             self.tasks_completed += 1;
 
-            // Update success tracking for difficulty promotion
-            let duration_secs = start_time.elapsed().as_secs();
-            self.fetcher.update_success_tracking(duration_secs);
+            // Update success tracking for difficulty promotion, leaving out time spent
+            // fetching the next tasks
+            let duration_secs = (proving_time + submission_started.elapsed()).as_secs();
+            if let Err(e) = self.fetcher.record_success(task.difficulty, duration_secs) {
+                self.event_sender
+                    .send_task_event(
+                        describe_write_error("the adaptive difficulty state", &e),
+                        EventType::Error,
+                        LogLevel::Warn,
+                    )
+                    .await;
+            }
 
             // Send information about completing the task
             self.event_sender
-                .send_event(Event::state_change(
-                    ProverState::Waiting,
-                    format!(
-                        "{} completed, Task size: {}, Duration: {}s, Difficulty: {}",
-                        task.task_id,
-                        task.public_inputs_list.len(),
-                        self.fetcher.last_success_duration_secs.unwrap_or(0),
-                        self.fetcher
-                            .last_success_difficulty
-                            .map(|difficulty| difficulty.as_str_name())
-                            .unwrap_or("Unknown")
-                    ),
-                ))
+                .send_event(
+                    Event::state_change(
+                        ProverState::Waiting,
+                        format!(
+                            "{} completed, Task size: {}, Duration: {}s, Difficulty: {}",
+                            task.task_id,
+                            task.public_inputs_list.len(),
+                            self.fetcher.last_success_duration_secs.unwrap_or(0),
+                            self.fetcher
+                                .last_success_difficulty
+                                .map(|difficulty| difficulty.as_str_name())
+                                .unwrap_or("Unknown")
+                        ),
+                    )
+                    .with_task_id(&task.task_id),
+                )
                 .await;
             // Check if we've reached the maximum number of tasks
             if let Some(max) = self.max_tasks {
@@ -177,7 +560,7 @@ impl AuthenticatedWorker {
                         .await;
 
                     // Send shutdown signal to trigger application exit
-                    let _ = self.shutdown_sender.send(());
+                    let _ = self.shutdown_sender.send(ShutdownReason::MaxTasksReached);
                     return true; // Signal to exit the worker loop
                 }
             }
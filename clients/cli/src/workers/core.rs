@@ -8,15 +8,30 @@ use tokio::sync::mpsc;
 #[derive(Clone)]
 pub struct EventSender {
     sender: mpsc::Sender<Event>,
+    /// Node every event is attributed to, if any
+    node_id: Option<u64>,
 }
 
 impl EventSender {
     pub fn new(sender: mpsc::Sender<Event>) -> Self {
-        Self { sender }
+        Self {
+            sender,
+            node_id: None,
+        }
+    }
+
+    /// Attribute every event sent to `node_id`
+    pub fn with_node_id(mut self, node_id: u64) -> Self {
+        self.node_id = Some(node_id);
+        self
     }
 
     /// Send a generic event
     pub async fn send_event(&self, event: Event) {
+        let event = match self.node_id {
+            Some(node_id) => event.with_node_id(node_id),
+            None => event,
+        };
         let _ = self.sender.send(event).await;
     }
 
@@ -26,12 +41,10 @@ impl EventSender {
         event_type: EventType,
         log_level: LogLevel,
     ) {
-        let _ = self
-            .sender
-            .send(Event::task_fetcher_with_level(
-                message, event_type, log_level,
-            ))
-            .await;
+        self.send_event(Event::task_fetcher_with_level(
+            message, event_type, log_level,
+        ))
+        .await;
     }
 
     pub async fn send_proof_event(
@@ -40,12 +53,10 @@ impl EventSender {
         event_type: EventType,
         log_level: LogLevel,
     ) {
-        let _ = self
-            .sender
-            .send(Event::proof_submitter_with_level(
-                message, event_type, log_level,
-            ))
-            .await;
+        self.send_event(Event::proof_submitter_with_level(
+            message, event_type, log_level,
+        ))
+        .await;
     }
 
     pub async fn send_prover_event(
@@ -55,22 +66,92 @@ impl EventSender {
         event_type: EventType,
         log_level: LogLevel,
     ) {
-        let _ = self
-            .sender
-            .send(Event::prover_with_level(
-                thread_id, message, event_type, log_level,
-            ))
-            .await;
+        self.send_event(Event::prover_with_level(
+            thread_id, message, event_type, log_level,
+        ))
+        .await;
     }
 }
 
+/// What to do when the proving workers die on several consecutive tasks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum OnWorkerDeath {
+    /// Back off and start a fresh prover, up to a limit, then shut down
+    #[default]
+    Restart,
+    /// Shut down immediately with a fatal error
+    Exit,
+}
+
+/// What to do with a proof whose submission failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum OnSubmitFailure {
+    /// Report that the proof was discarded
+    #[default]
+    Log,
+    /// Save the proof for `submit-saved`, to ~/.nexus/unsubmitted_proofs unless
+    /// `--save-proofs` is set
+    Save,
+}
+
 /// Worker configuration shared across all worker types
 #[derive(Clone)]
 pub struct WorkerConfig {
     pub environment: crate::environment::Environment,
     pub client_id: String,
     pub max_difficulty: Option<crate::nexus_orchestrator::TaskDifficulty>,
+    /// Floor for adaptive difficulty from `--min-difficulty`; also applies on the first fetch
+    pub min_difficulty: Option<crate::nexus_orchestrator::TaskDifficulty>,
+    /// Upper bound on adaptive difficulty calibrated by `--probe-difficulty`; ignored when
+    /// `max_difficulty` is set
+    pub difficulty_cap: Option<crate::nexus_orchestrator::TaskDifficulty>,
     pub num_workers: usize,
+    /// Failed proving attempts for the same task before it is reported as unprovable
+    pub max_proof_attempts: u32,
+    /// Diagnostic: attach full proofs even for `ProofHash` tasks
+    pub always_attach_proof: bool,
+    /// Submit proofs without verifying them locally first
+    pub skip_local_verify: bool,
+    /// Directory to save generated proofs to, if any
+    pub save_proofs_dir: Option<std::path::PathBuf>,
+    /// Format for proofs saved to disk
+    pub proof_format: crate::prover::format::ProofFormat,
+    /// File to persist adaptive difficulty state to, if any
+    pub difficulty_state_path: Option<std::path::PathBuf>,
+    /// File to append rejected submissions to, if any
+    pub rejections_path: Option<std::path::PathBuf>,
+    /// Local-time window during which new tasks are fetched; always on when `None`
+    pub schedule: Option<super::schedule::ProvingSchedule>,
+    /// Memory budget for proving in bytes; workers are reduced when it is exceeded
+    pub memory_budget_bytes: Option<u64>,
+    /// Task types to prove; others are skipped
+    pub task_type_filter: crate::task::TaskTypeFilter,
+    /// What to do when the proving workers keep dying
+    pub on_worker_death: OnWorkerDeath,
+    /// Whether to fetch the next task while proving the current one
+    pub fetch_mode: super::prefetch::FetchMode,
+    /// Most tasks held at once, counting the one being proved
+    pub queue_size: u32,
+    /// Tasks with more inputs than this are skipped; unlimited when `None`
+    pub max_inputs_per_task: Option<usize>,
+    /// Directory to persist request rate-limit state to; in-memory only when `None`
+    pub request_timer_state_dir: Option<std::path::PathBuf>,
+    /// Number of proof hashes to keep for reuse on identical tasks; disabled when `None`
+    pub proof_cache_size: Option<usize>,
+    /// Bytes of encoded proofs the proof cache may hold; beyond it only hashes are kept
+    pub proof_cache_max_bytes: u64,
+    /// Shut down on the first proving or submission failure instead of moving on
+    pub fail_fast: bool,
+    /// What to do with a proof whose submission failed
+    pub on_submit_failure: OnSubmitFailure,
+    /// Stop fetching new tasks and shut down once the worker has run this long
+    pub max_uptime: Option<std::time::Duration>,
+    /// Longest wait between fetches while the server keeps reporting no tasks
+    pub idle_interval: std::time::Duration,
+    /// Tasks completing faster than this, in seconds, promote adaptive difficulty
+    pub promotion_threshold_secs: u64,
+    /// Log the hardware telemetry sent with proof submissions once, at debug level
+    pub dump_telemetry: bool,
 }
 
 impl WorkerConfig {
@@ -79,7 +160,35 @@ impl WorkerConfig {
             environment,
             client_id,
             max_difficulty: None,
+            min_difficulty: None,
+            difficulty_cap: None,
             num_workers: 1,
+            max_proof_attempts: crate::consts::cli_consts::DEFAULT_MAX_PROOF_ATTEMPTS,
+            always_attach_proof: false,
+            skip_local_verify: false,
+            save_proofs_dir: None,
+            proof_format: crate::prover::format::ProofFormat::default(),
+            difficulty_state_path: None,
+            rejections_path: None,
+            schedule: None,
+            memory_budget_bytes: None,
+            task_type_filter: crate::task::TaskTypeFilter::default(),
+            on_worker_death: OnWorkerDeath::default(),
+            fetch_mode: super::prefetch::FetchMode::default(),
+            queue_size: crate::consts::cli_consts::DEFAULT_TASK_QUEUE_SIZE,
+            max_inputs_per_task: None,
+            request_timer_state_dir: None,
+            proof_cache_size: None,
+            proof_cache_max_bytes: crate::consts::cli_consts::DEFAULT_PROOF_CACHE_MB * 1024 * 1024,
+            fail_fast: false,
+            on_submit_failure: OnSubmitFailure::default(),
+            max_uptime: None,
+            idle_interval: std::time::Duration::from_secs(
+                crate::consts::cli_consts::task_fetching::DEFAULT_IDLE_INTERVAL_SECS,
+            ),
+            promotion_threshold_secs:
+                crate::consts::cli_consts::difficulty::PROMOTION_THRESHOLD_SECS,
+            dump_telemetry: false,
         }
     }
 }
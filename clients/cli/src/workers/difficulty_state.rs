@@ -0,0 +1,201 @@
+//! Persisted adaptive difficulty state
+//!
+//! The last successful difficulty and its duration are saved after each completed task,
+//! so a restarted node resumes where it left off instead of cold-booting. Each node has its
+//! own file, so processes for different nodes on one host don't overwrite each other.
+//!
+//! The highest difficulty found by `--probe-difficulty` is saved alongside it and caps
+//! adaptive promotion on later runs.
+
+use crate::nexus_orchestrator::TaskDifficulty;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Get the path to a node's difficulty state file, typically ~/.nexus/difficulty/<node_id>.json.
+pub fn get_difficulty_state_path(node_id: u64) -> Result<PathBuf, std::io::Error> {
+    let home_path = home::home_dir().ok_or(std::io::Error::new(
+        std::io::ErrorKind::NotFound,
+        "Home directory not found",
+    ))?;
+    Ok(home_path
+        .join(".nexus")
+        .join("difficulty")
+        .join(format!("{}.json", node_id)))
+}
+
+/// Get the path to a node's calibrated difficulty cap, typically
+/// ~/.nexus/difficulty/<node_id>.cap.json.
+pub fn get_difficulty_cap_path(node_id: u64) -> Result<PathBuf, std::io::Error> {
+    let state_path = get_difficulty_state_path(node_id)?;
+    Ok(state_path.with_file_name(format!("{}.cap.json", node_id)))
+}
+
+fn load_json<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<T, std::io::Error> {
+    let buf = fs::read(path)?;
+    serde_json::from_slice(&buf)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
+fn save_json<T: Serialize>(value: &T, path: &Path) -> Result<(), std::io::Error> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    fs::write(path, json)
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DifficultyState {
+    /// Difficulty of the last successfully completed task, e.g. "MEDIUM"
+    pub difficulty: String,
+
+    /// Duration of the last successfully completed task
+    pub last_duration_secs: u64,
+}
+
+impl DifficultyState {
+    pub fn new(difficulty: TaskDifficulty, last_duration_secs: u64) -> Self {
+        Self {
+            difficulty: difficulty.as_str_name().to_string(),
+            last_duration_secs,
+        }
+    }
+
+    /// The stored difficulty, if it names a known level
+    pub fn difficulty(&self) -> Option<TaskDifficulty> {
+        TaskDifficulty::from_str_name(&self.difficulty)
+    }
+
+    /// Loads the state from a JSON file at the given path.
+    pub fn load(path: &Path) -> Result<Self, std::io::Error> {
+        load_json(path)
+    }
+
+    /// Saves the state to a JSON file at the given path.
+    pub fn save(&self, path: &Path) -> Result<(), std::io::Error> {
+        save_json(self, path)
+    }
+
+    /// Removes the state file, returning the state it held (if it was readable).
+    pub fn clear(path: &Path) -> Result<Option<Self>, std::io::Error> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let previous = Self::load(path).ok();
+        fs::remove_file(path)?;
+        Ok(previous)
+    }
+}
+
+/// Highest difficulty `--probe-difficulty` found to finish within its time budget
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DifficultyCap {
+    /// Highest safe difficulty, e.g. "MEDIUM"
+    pub difficulty: String,
+
+    /// Time budget the probe measured against
+    pub budget_secs: u64,
+}
+
+impl DifficultyCap {
+    pub fn new(difficulty: TaskDifficulty, budget_secs: u64) -> Self {
+        Self {
+            difficulty: difficulty.as_str_name().to_string(),
+            budget_secs,
+        }
+    }
+
+    /// The stored difficulty, if it names a known level
+    pub fn difficulty(&self) -> Option<TaskDifficulty> {
+        TaskDifficulty::from_str_name(&self.difficulty)
+    }
+
+    /// Loads the cap from a JSON file at the given path.
+    pub fn load(path: &Path) -> Result<Self, std::io::Error> {
+        load_json(path)
+    }
+
+    /// Saves the cap to a JSON file at the given path.
+    pub fn save(&self, path: &Path) -> Result<(), std::io::Error> {
+        save_json(self, path)
+    }
+
+    /// Removes the cap file, returning whether there was one.
+    pub fn clear(path: &Path) -> Result<bool, std::io::Error> {
+        if !path.exists() {
+            return Ok(false);
+        }
+        fs::remove_file(path)?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("difficulty.json");
+
+        let state = DifficultyState::new(TaskDifficulty::Large, 120);
+        state.save(&path).unwrap();
+
+        let loaded = DifficultyState::load(&path).unwrap();
+        assert_eq!(loaded, state);
+        assert_eq!(loaded.difficulty(), Some(TaskDifficulty::Large));
+    }
+
+    #[test]
+    fn test_unknown_difficulty_is_ignored() {
+        let state = DifficultyState {
+            difficulty: "HUGE".to_string(),
+            last_duration_secs: 1,
+        };
+        assert_eq!(state.difficulty(), None);
+    }
+
+    #[test]
+    fn test_clear_returns_previous_state() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("difficulty.json");
+        assert_eq!(DifficultyState::clear(&path).unwrap(), None);
+
+        let state = DifficultyState::new(TaskDifficulty::Medium, 30);
+        state.save(&path).unwrap();
+        assert_eq!(DifficultyState::clear(&path).unwrap(), Some(state));
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_cap_round_trip() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("1.cap.json");
+        assert!(!DifficultyCap::clear(&path).unwrap());
+
+        let cap = DifficultyCap::new(TaskDifficulty::SmallMedium, 420);
+        cap.save(&path).unwrap();
+        assert_eq!(
+            DifficultyCap::load(&path).unwrap().difficulty(),
+            Some(TaskDifficulty::SmallMedium)
+        );
+        assert!(DifficultyCap::clear(&path).unwrap());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_state_path_is_per_node() {
+        assert_ne!(
+            get_difficulty_state_path(1).unwrap(),
+            get_difficulty_state_path(2).unwrap()
+        );
+        assert_ne!(
+            get_difficulty_cap_path(1).unwrap(),
+            get_difficulty_state_path(1).unwrap()
+        );
+    }
+}
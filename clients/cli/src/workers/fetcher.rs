@@ -1,14 +1,17 @@
 //! Task fetching with network retry logic
 
 use super::core::{EventSender, WorkerConfig};
+use super::difficulty_state::DifficultyState;
 use crate::analytics::track_got_task;
 use crate::consts::cli_consts::{difficulty, rate_limiting, task_fetching};
-use crate::events::EventType;
+use crate::difficulty::{DIFFICULTY_LEVELS, difficulty_rank};
+use crate::events::{Event, EventType};
 use crate::logging::LogLevel;
-use crate::network::{NetworkClient, RequestTimer, RequestTimerConfig};
+use crate::network::{NetworkClient, RequestTimer, RequestTimerConfig, WaitReason};
 use crate::orchestrator::Orchestrator;
 use crate::task::Task;
 use ed25519_dalek::VerifyingKey;
+use std::collections::VecDeque;
 use std::time::Duration;
 use thiserror::Error;
 use tokio::time::sleep;
@@ -29,7 +32,76 @@ pub struct TaskFetcher {
     config: WorkerConfig,
     pub last_success_duration_secs: Option<u64>,
     pub last_success_difficulty: Option<crate::nexus_orchestrator::TaskDifficulty>,
+    /// Durations of the most recent successes, newest last, at most `PROMOTION_WINDOW` long
+    recent_durations_secs: VecDeque<u64>,
+    /// Consecutive successes at or over the promotion threshold
+    slow_streak: u32,
     last_requested_difficulty: Option<crate::nexus_orchestrator::TaskDifficulty>,
+    /// Latest limit on tasks in flight reported by the server
+    max_concurrent_tasks: Option<u32>,
+}
+
+/// The level after `current` in `DIFFICULTY_LEVELS`, which is ordered from easiest to
+/// hardest. The hardest level is the ceiling, so new levels become reachable by adding
+/// them to that table.
+fn next_difficulty(
+    current: crate::nexus_orchestrator::TaskDifficulty,
+) -> crate::nexus_orchestrator::TaskDifficulty {
+    DIFFICULTY_LEVELS
+        .iter()
+        .position(|(_, level)| *level == current)
+        .and_then(|index| DIFFICULTY_LEVELS.get(index + 1))
+        .map_or(current, |(_, level)| *level)
+}
+
+/// The level before `current` in `DIFFICULTY_LEVELS`, or `current` if it is the easiest
+fn previous_difficulty(
+    current: crate::nexus_orchestrator::TaskDifficulty,
+) -> crate::nexus_orchestrator::TaskDifficulty {
+    DIFFICULTY_LEVELS
+        .iter()
+        .position(|(_, level)| *level == current)
+        .and_then(|index| index.checked_sub(1))
+        .and_then(|index| DIFFICULTY_LEVELS.get(index))
+        .map_or(current, |(_, level)| *level)
+}
+
+/// Mean of `durations` after dropping the fastest and slowest sample, once there are at
+/// least three; a plain mean otherwise
+fn trimmed_average_secs(durations: &VecDeque<u64>) -> Option<u64> {
+    if durations.is_empty() {
+        return None;
+    }
+    let mut sorted: Vec<u64> = durations.iter().copied().collect();
+    sorted.sort_unstable();
+    let kept = if sorted.len() >= 3 {
+        &sorted[1..sorted.len() - 1]
+    } else {
+        &sorted[..]
+    };
+    Some(kept.iter().sum::<u64>() / kept.len() as u64)
+}
+
+/// `desired`, lowered to `cap` when it is harder by position in `DIFFICULTY_LEVELS`
+fn apply_difficulty_cap(
+    desired: crate::nexus_orchestrator::TaskDifficulty,
+    cap: Option<crate::nexus_orchestrator::TaskDifficulty>,
+) -> crate::nexus_orchestrator::TaskDifficulty {
+    match cap {
+        Some(cap) if difficulty_rank(desired) > difficulty_rank(cap) => cap,
+        _ => desired,
+    }
+}
+
+/// `desired`, raised to `floor` when it is easier by position in `DIFFICULTY_LEVELS`
+fn apply_difficulty_floor(
+    desired: crate::nexus_orchestrator::TaskDifficulty,
+    floor: Option<crate::nexus_orchestrator::TaskDifficulty>,
+) -> crate::nexus_orchestrator::TaskDifficulty {
+    match floor {
+        Some(floor) if difficulty_rank(desired) < difficulty_rank(floor) => floor,
+        _ => desired,
+    }
 }
 
 impl TaskFetcher {
@@ -46,12 +118,29 @@ impl TaskFetcher {
             rate_limiting::TASK_FETCH_MAX_REQUESTS_PER_WINDOW,
             rate_limiting::task_fetch_window(),
             task_fetching::initial_backoff(), // Use as default retry delay
-        );
+        )
+        .with_state_path(
+            config
+                .request_timer_state_dir
+                .as_ref()
+                .map(|dir| dir.join("fetch.json")),
+        )
+        .with_idle_backoff(config.idle_interval);
         let request_timer = RequestTimer::new(timer_config);
 
         // Create network client with retry logic
         let network_client = NetworkClient::new(request_timer, task_fetching::MAX_RETRIES);
 
+        // Resume adaptive difficulty from the previous run, if any
+        let persisted = config
+            .difficulty_state_path
+            .as_deref()
+            .and_then(|path| DifficultyState::load(path).ok());
+        let last_success_difficulty = persisted.as_ref().and_then(|state| state.difficulty());
+        let last_success_duration_secs = last_success_difficulty
+            .and(persisted.as_ref())
+            .map(|state| state.last_duration_secs);
+
         Self {
             node_id,
             verifying_key,
@@ -59,14 +148,29 @@ impl TaskFetcher {
             network_client,
             event_sender,
             config: config.clone(),
-            last_success_duration_secs: None,
-            last_success_difficulty: None,
+            last_success_duration_secs,
+            last_success_difficulty,
+            recent_durations_secs: VecDeque::with_capacity(difficulty::PROMOTION_WINDOW),
+            slow_streak: 0,
             last_requested_difficulty: None,
+            max_concurrent_tasks: None,
         }
     }
 
+    /// Latest limit on tasks in flight reported by the server, if any
+    pub fn max_concurrent_tasks(&self) -> Option<u32> {
+        self.max_concurrent_tasks
+    }
+
     /// Fetch a single task with automatic retry and proper logging
     pub async fn fetch_task(&mut self) -> Result<Task, FetchError> {
+        self.wait_for_fetch_slot().await;
+        self.fetch_ready_task().await
+    }
+
+    /// Wait until the request timer allows the next fetch.
+    /// Nothing is requested from the server here, so this can be cancelled safely.
+    pub async fn wait_for_fetch_slot(&mut self) {
         // Check if we can proceed immediately
         let can_proceed_immediately = self.network_client.request_timer_mut().can_proceed();
 
@@ -80,25 +184,53 @@ impl TaskFetcher {
                 .await;
         }
 
-        // Wait until we can proceed with accurate timing
-        while !self.network_client.request_timer_mut().can_proceed() {
+        // Wait until we can proceed with accurate timing, telling rate-limit pacing apart
+        // from backing off after errors
+        while let Some(reason) = self.network_client.request_timer_mut().wait_reason() {
             let wait_time = self.network_client.request_timer_mut().time_until_next();
             if wait_time > Duration::ZERO {
                 // Log the accurate wait time here
-                self.event_sender
-                    .send_task_event(
+                let (message, event_type) = match reason {
+                    WaitReason::Pacing => (
                         format!(
                             "Step 1 of 4: Waiting - ready for next task ({}) seconds",
                             wait_time.as_secs()
                         ),
                         EventType::Waiting,
-                        LogLevel::Info,
-                    )
-                    .await;
+                    ),
+                    WaitReason::Backoff => (
+                        format!(
+                            "Step 1 of 4: Backing off after a failed request - retrying in {} seconds",
+                            wait_time.as_secs()
+                        ),
+                        EventType::Backoff,
+                    ),
+                    WaitReason::RateLimited => (
+                        format!(
+                            "Step 1 of 4: Rate limited by server - retrying in {} seconds",
+                            wait_time.as_secs()
+                        ),
+                        EventType::Backoff,
+                    ),
+                    WaitReason::Idle => (
+                        format!(
+                            "Step 1 of 4: Idle: no tasks available - checking again in {} seconds",
+                            wait_time.as_secs()
+                        ),
+                        EventType::Waiting,
+                    ),
+                };
+                let event = Event::task_fetcher_with_level(message, event_type, LogLevel::Info)
+                    .with_fetch_wait(reason, wait_time);
+                self.send_with_rate_limit(event).await;
                 sleep(wait_time).await;
             }
         }
+    }
 
+    /// Request a task once `wait_for_fetch_slot` has returned.
+    /// Not cancel-safe: the server may have assigned a task by the time it is dropped.
+    pub async fn fetch_ready_task(&mut self) -> Result<Task, FetchError> {
         // Attempt to fetch task through network client
         // Determine desired max difficulty
         let desired = if let Some(override_diff) = self.config.max_difficulty {
@@ -106,53 +238,49 @@ impl TaskFetcher {
         } else {
             // Adaptive difficulty system:
             // - Starts at SmallMedium by default
-            // - Promotes if previous task completed in < PROMOTION_THRESHOLD_SECS
-            // - Small difficulty does not auto-promote (manual override only)
-            if let Some(current) = self.last_success_difficulty {
-                // If last success took >= promotion threshold, don't increase difficulty
+            // - Promotes if recent tasks completed in < the promotion threshold on
+            //   average (--promotion-threshold, PROMOTION_THRESHOLD_SECS by default)
+            // - Promotes one level at a time, up to the hardest level in DIFFICULTY_LEVELS
+            //   or the cap calibrated by --probe-difficulty
+            // - Demotes one level after DEMOTION_SLOW_STREAK consecutive tasks at or over
+            //   the promotion threshold
+            // - Never requests below --min-difficulty, which wins over the cap
+            let adaptive = if let Some(current) = self.last_success_difficulty {
+                // If recent successes took >= promotion threshold, don't increase difficulty
                 let promote = !matches!(
-                    self.last_success_duration_secs,
-                    Some(secs) if secs >= difficulty::PROMOTION_THRESHOLD_SECS
+                    self.promotion_duration_secs(),
+                    Some(secs) if secs >= self.config.promotion_threshold_secs
                 );
-                if promote {
-                    match current {
-                        crate::nexus_orchestrator::TaskDifficulty::Small => {
-                            // Small promotes to SmallMedium
-                            crate::nexus_orchestrator::TaskDifficulty::SmallMedium
-                        }
-                        crate::nexus_orchestrator::TaskDifficulty::SmallMedium => {
-                            crate::nexus_orchestrator::TaskDifficulty::Medium
-                        }
-                        crate::nexus_orchestrator::TaskDifficulty::Medium => {
-                            crate::nexus_orchestrator::TaskDifficulty::Large
-                        }
-                        crate::nexus_orchestrator::TaskDifficulty::Large => {
-                            crate::nexus_orchestrator::TaskDifficulty::ExtraLarge
-                        }
-                        crate::nexus_orchestrator::TaskDifficulty::ExtraLarge => {
-                            crate::nexus_orchestrator::TaskDifficulty::ExtraLarge2
-                        }
-                        crate::nexus_orchestrator::TaskDifficulty::ExtraLarge2 => {
-                            crate::nexus_orchestrator::TaskDifficulty::ExtraLarge3
-                        }
-                        crate::nexus_orchestrator::TaskDifficulty::ExtraLarge3 => {
-                            crate::nexus_orchestrator::TaskDifficulty::ExtraLarge4
-                        }
-                        crate::nexus_orchestrator::TaskDifficulty::ExtraLarge4 => {
-                            crate::nexus_orchestrator::TaskDifficulty::ExtraLarge5
-                        }
-                        crate::nexus_orchestrator::TaskDifficulty::ExtraLarge5 => {
-                            // Already at maximum difficulty
-                            crate::nexus_orchestrator::TaskDifficulty::ExtraLarge5
-                        }
+                if self.slow_streak >= difficulty::DEMOTION_SLOW_STREAK {
+                    // Start a fresh streak at the lower level
+                    let slow_tasks = std::mem::take(&mut self.slow_streak);
+                    let lower = previous_difficulty(current);
+                    if lower != current {
+                        self.send_paced_event(
+                            format!(
+                                "Lowering difficulty to {} after {} slow tasks",
+                                lower.as_str_name(),
+                                slow_tasks
+                            ),
+                            EventType::Refresh,
+                            LogLevel::Info,
+                        )
+                        .await;
                     }
+                    lower
+                } else if promote {
+                    next_difficulty(current)
                 } else {
                     current
                 }
             } else {
                 // No previous success - start at SmallMedium
                 crate::nexus_orchestrator::TaskDifficulty::SmallMedium
-            }
+            };
+            apply_difficulty_floor(
+                apply_difficulty_cap(adaptive, self.config.difficulty_cap),
+                self.config.min_difficulty,
+            )
         };
 
         // Log the difficulty we're requesting vs what we receive
@@ -185,13 +313,14 @@ impl TaskFetcher {
                 }
 
                 // Log successful fetch
-                self.event_sender
-                    .send_task_event(
-                        format!("Step 1 of 4: Got task {}", proof_task_result.task.task_id),
-                        EventType::Success,
-                        LogLevel::Info,
-                    )
-                    .await;
+                let event = Event::task_fetcher_with_level(
+                    format!("Step 1 of 4: Got task {}", proof_task_result.task.task_id),
+                    EventType::Success,
+                    LogLevel::Info,
+                )
+                .with_difficulty(proof_task_result.actual_difficulty)
+                .with_task_id(&proof_task_result.task.task_id);
+                self.send_with_rate_limit(event).await;
 
                 // Track analytics for successful fetch
                 tokio::spawn(track_got_task(
@@ -203,30 +332,102 @@ impl TaskFetcher {
                 // Store the actual difficulty received from server for success tracking
                 self.last_requested_difficulty = Some(proof_task_result.actual_difficulty);
 
+                // Follow the server's latest limit on tasks in flight
+                if proof_task_result.max_concurrent_tasks != self.max_concurrent_tasks {
+                    self.max_concurrent_tasks = proof_task_result.max_concurrent_tasks;
+                    if let Some(max) = self.max_concurrent_tasks {
+                        self.event_sender
+                            .send_task_event(
+                                format!("Server limits this node to {} task(s) in flight", max),
+                                EventType::Refresh,
+                                LogLevel::Debug,
+                            )
+                            .await;
+                    }
+                }
+
                 Ok(proof_task_result.task)
             }
+            Err(e) if e.is_no_tasks_available() => {
+                self.send_paced_event(
+                    "Idle: no tasks available".to_string(),
+                    EventType::Waiting,
+                    LogLevel::Info,
+                )
+                .await;
+
+                Err(FetchError::Network(e))
+            }
             Err(e) => {
                 // Log fetch failure with appropriate level
                 let log_level = self.network_client.classify_error(&e);
-                self.event_sender
-                    .send_task_event(
-                        format!("Failed to fetch task: {}", e),
-                        EventType::Error,
-                        log_level,
-                    )
-                    .await;
+                self.send_paced_event(
+                    format!("Failed to fetch task: {}", e),
+                    EventType::Error,
+                    log_level,
+                )
+                .await;
 
                 Err(FetchError::Network(e))
             }
         }
     }
 
-    /// Update success tracking after completing a task
-    /// Uses the actual difficulty received from the server
-    pub fn update_success_tracking(&mut self, duration_secs: u64) {
-        if let Some(difficulty) = self.last_requested_difficulty {
-            self.last_success_difficulty = Some(difficulty);
-            self.last_success_duration_secs = Some(duration_secs);
+    /// Send a task fetcher event annotated with the current rate-limit usage, so the
+    /// dashboard can show why fetching is paced
+    async fn send_paced_event(
+        &mut self,
+        message: String,
+        event_type: EventType,
+        log_level: LogLevel,
+    ) {
+        let event = Event::task_fetcher_with_level(message, event_type, log_level);
+        self.send_with_rate_limit(event).await;
+    }
+
+    /// Send `event` annotated with the current rate-limit usage
+    async fn send_with_rate_limit(&mut self, mut event: Event) {
+        if let Some(usage) = self.network_client.request_timer_mut().usage() {
+            event = event.with_rate_limit(usage);
+        }
+        self.event_sender.send_event(event).await;
+    }
+
+    /// Duration the promotion decision is based on: the trimmed average of recent successes,
+    /// or the persisted last duration until this run has completed a task
+    fn promotion_duration_secs(&self) -> Option<u64> {
+        trimmed_average_secs(&self.recent_durations_secs).or(self.last_success_duration_secs)
+    }
+
+    /// Record a completed task of the given difficulty. Use this when another task may have
+    /// been fetched since, e.g. while prefetching.
+    ///
+    /// Returns an error if the state couldn't be persisted. Persisting is best-effort; the
+    /// in-memory state is updated either way and remains authoritative.
+    pub fn record_success(
+        &mut self,
+        difficulty: crate::nexus_orchestrator::TaskDifficulty,
+        duration_secs: u64,
+    ) -> Result<(), std::io::Error> {
+        // Durations at another level say nothing about this one
+        if self.last_success_difficulty != Some(difficulty) {
+            self.recent_durations_secs.clear();
+        }
+        self.last_success_difficulty = Some(difficulty);
+        self.last_success_duration_secs = Some(duration_secs);
+        if self.recent_durations_secs.len() == difficulty::PROMOTION_WINDOW {
+            self.recent_durations_secs.pop_front();
+        }
+        self.recent_durations_secs.push_back(duration_secs);
+        if duration_secs >= self.config.promotion_threshold_secs {
+            self.slow_streak += 1;
+        } else {
+            self.slow_streak = 0;
+        }
+
+        match &self.config.difficulty_state_path {
+            Some(path) => DifficultyState::new(difficulty, duration_secs).save(path),
+            None => Ok(()),
         }
     }
 }
@@ -266,11 +467,13 @@ mod tests {
                 public_inputs_list: vec![vec![1, 2, 3]],
                 task_type: crate::nexus_orchestrator::TaskType::ProofHash,
                 difficulty: crate::nexus_orchestrator::TaskDifficulty::Medium,
+                fetched_at: std::time::Instant::now(),
             };
 
             Ok(crate::orchestrator::client::ProofTaskResult {
                 task,
                 actual_difficulty: max_difficulty,
+                max_concurrent_tasks: None,
             })
         }
 
@@ -472,6 +675,102 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_promotion_threshold_is_configurable() {
+        let mut fetcher = create_test_fetcher();
+        // 9 minutes is past the default threshold but within a 10-minute one
+        fetcher.config.promotion_threshold_secs = 600;
+        fetcher.last_success_difficulty = Some(crate::nexus_orchestrator::TaskDifficulty::Medium);
+        fetcher.last_success_duration_secs = Some(540);
+
+        fetcher
+            .fetch_task()
+            .await
+            .expect("fetcher.fetch_task failed");
+        assert_eq!(
+            fetcher.last_requested_difficulty,
+            Some(crate::nexus_orchestrator::TaskDifficulty::Large)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_calibrated_cap_limits_promotion() {
+        let mut fetcher = create_test_fetcher();
+        fetcher.config.difficulty_cap = Some(crate::nexus_orchestrator::TaskDifficulty::Medium);
+        fetcher.last_success_difficulty = Some(crate::nexus_orchestrator::TaskDifficulty::Medium);
+        fetcher.last_success_duration_secs = Some(60);
+
+        fetcher
+            .fetch_task()
+            .await
+            .expect("fetcher.fetch_task failed");
+        assert_eq!(
+            fetcher.last_requested_difficulty,
+            Some(crate::nexus_orchestrator::TaskDifficulty::Medium)
+        );
+
+        // An explicit --max-difficulty takes precedence over the cap
+        let mut fetcher = create_test_fetcher();
+        fetcher.config.difficulty_cap = Some(crate::nexus_orchestrator::TaskDifficulty::Medium);
+        fetcher.config.max_difficulty = Some(crate::nexus_orchestrator::TaskDifficulty::Large);
+        fetcher
+            .fetch_task()
+            .await
+            .expect("fetcher.fetch_task failed");
+        assert_eq!(
+            fetcher.last_requested_difficulty,
+            Some(crate::nexus_orchestrator::TaskDifficulty::Large)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_min_difficulty_applies_on_first_fetch() {
+        let mut fetcher = create_test_fetcher();
+        fetcher.config.min_difficulty = Some(crate::nexus_orchestrator::TaskDifficulty::Large);
+
+        fetcher
+            .fetch_task()
+            .await
+            .expect("fetcher.fetch_task failed");
+        assert_eq!(
+            fetcher.last_requested_difficulty,
+            Some(crate::nexus_orchestrator::TaskDifficulty::Large)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_min_difficulty_holds_after_slow_task() {
+        let mut fetcher = create_test_fetcher();
+        fetcher.config.min_difficulty = Some(crate::nexus_orchestrator::TaskDifficulty::Medium);
+        fetcher.last_success_difficulty =
+            Some(crate::nexus_orchestrator::TaskDifficulty::SmallMedium);
+        fetcher.last_success_duration_secs = Some(480); // slow - would not promote
+
+        fetcher
+            .fetch_task()
+            .await
+            .expect("fetcher.fetch_task failed");
+        assert_eq!(
+            fetcher.last_requested_difficulty,
+            Some(crate::nexus_orchestrator::TaskDifficulty::Medium)
+        );
+
+        // Promotion still happens above the floor
+        let mut fetcher = create_test_fetcher();
+        fetcher.config.min_difficulty = Some(crate::nexus_orchestrator::TaskDifficulty::Medium);
+        fetcher.last_success_difficulty = Some(crate::nexus_orchestrator::TaskDifficulty::Medium);
+        fetcher.last_success_duration_secs = Some(60);
+
+        fetcher
+            .fetch_task()
+            .await
+            .expect("fetcher.fetch_task failed");
+        assert_eq!(
+            fetcher.last_requested_difficulty,
+            Some(crate::nexus_orchestrator::TaskDifficulty::Large)
+        );
+    }
+
     #[tokio::test]
     async fn test_manual_override_to_small() {
         let mut fetcher = create_test_fetcher();
@@ -492,6 +791,164 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_trimmed_average_secs() {
+        assert_eq!(trimmed_average_secs(&VecDeque::new()), None);
+        assert_eq!(trimmed_average_secs(&VecDeque::from([300])), Some(300));
+        assert_eq!(trimmed_average_secs(&VecDeque::from([100, 500])), Some(300));
+        // The fastest and slowest samples are dropped
+        assert_eq!(
+            trimmed_average_secs(&VecDeque::from([30, 500, 520, 540, 900])),
+            Some(520)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_single_fast_task_does_not_promote_after_slow_ones() {
+        let mut fetcher = create_test_fetcher();
+        for secs in [480, 500, 460, 490] {
+            fetcher
+                .record_success(crate::nexus_orchestrator::TaskDifficulty::Medium, secs)
+                .unwrap();
+        }
+        // One lucky fast task
+        fetcher
+            .record_success(crate::nexus_orchestrator::TaskDifficulty::Medium, 60)
+            .unwrap();
+
+        fetcher
+            .fetch_task()
+            .await
+            .expect("fetcher.fetch_task failed");
+        assert_eq!(
+            fetcher.last_requested_difficulty,
+            Some(crate::nexus_orchestrator::TaskDifficulty::Medium)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sustained_fast_tasks_promote() {
+        let mut fetcher = create_test_fetcher();
+        for secs in [300, 280, 900, 320, 310] {
+            fetcher
+                .record_success(crate::nexus_orchestrator::TaskDifficulty::Medium, secs)
+                .unwrap();
+        }
+
+        fetcher
+            .fetch_task()
+            .await
+            .expect("fetcher.fetch_task failed");
+        // The one slow outlier is trimmed
+        assert_eq!(
+            fetcher.last_requested_difficulty,
+            Some(crate::nexus_orchestrator::TaskDifficulty::Large)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_slow_task_after_promotion_does_not_promote_again() {
+        let mut fetcher = create_test_fetcher();
+        for secs in [60, 70, 80, 90, 100] {
+            fetcher
+                .record_success(crate::nexus_orchestrator::TaskDifficulty::Medium, secs)
+                .unwrap();
+        }
+        fetcher
+            .fetch_task()
+            .await
+            .expect("fetcher.fetch_task failed");
+        assert_eq!(
+            fetcher.last_requested_difficulty,
+            Some(crate::nexus_orchestrator::TaskDifficulty::Large)
+        );
+
+        // The first task at the new level is slow; the fast Medium samples don't count
+        fetcher
+            .record_success(crate::nexus_orchestrator::TaskDifficulty::Large, 600)
+            .unwrap();
+        fetcher
+            .fetch_task()
+            .await
+            .expect("fetcher.fetch_task failed");
+        assert_eq!(
+            fetcher.last_requested_difficulty,
+            Some(crate::nexus_orchestrator::TaskDifficulty::Large)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_repeated_slow_tasks_demote() {
+        let mut fetcher = create_test_fetcher();
+        for _ in 0..difficulty::DEMOTION_SLOW_STREAK {
+            fetcher
+                .record_success(crate::nexus_orchestrator::TaskDifficulty::Large, 600)
+                .unwrap();
+        }
+
+        fetcher
+            .fetch_task()
+            .await
+            .expect("fetcher.fetch_task failed");
+        assert_eq!(
+            fetcher.last_requested_difficulty,
+            Some(crate::nexus_orchestrator::TaskDifficulty::Medium)
+        );
+        // The streak starts over at the lower level
+        assert_eq!(fetcher.slow_streak, 0);
+    }
+
+    #[tokio::test]
+    async fn test_fast_task_resets_slow_streak() {
+        let mut fetcher = create_test_fetcher();
+        for secs in [600, 600, 60, 600] {
+            fetcher
+                .record_success(crate::nexus_orchestrator::TaskDifficulty::Large, secs)
+                .unwrap();
+        }
+
+        fetcher
+            .fetch_task()
+            .await
+            .expect("fetcher.fetch_task failed");
+        assert_eq!(
+            fetcher.last_requested_difficulty,
+            Some(crate::nexus_orchestrator::TaskDifficulty::Large)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_demotion_respects_min_difficulty() {
+        let mut fetcher = create_test_fetcher();
+        fetcher.config.min_difficulty = Some(crate::nexus_orchestrator::TaskDifficulty::Large);
+        for _ in 0..difficulty::DEMOTION_SLOW_STREAK {
+            fetcher
+                .record_success(crate::nexus_orchestrator::TaskDifficulty::Large, 600)
+                .unwrap();
+        }
+
+        fetcher
+            .fetch_task()
+            .await
+            .expect("fetcher.fetch_task failed");
+        assert_eq!(
+            fetcher.last_requested_difficulty,
+            Some(crate::nexus_orchestrator::TaskDifficulty::Large)
+        );
+    }
+
+    #[test]
+    fn test_previous_difficulty() {
+        assert_eq!(
+            previous_difficulty(crate::nexus_orchestrator::TaskDifficulty::Medium),
+            crate::nexus_orchestrator::TaskDifficulty::SmallMedium
+        );
+        assert_eq!(
+            previous_difficulty(crate::nexus_orchestrator::TaskDifficulty::Small),
+            crate::nexus_orchestrator::TaskDifficulty::Small
+        );
+    }
+
     #[tokio::test]
     async fn test_success_tracking_update() {
         let mut fetcher = create_test_fetcher();
@@ -500,11 +957,10 @@ mod tests {
         assert_eq!(fetcher.last_success_difficulty, None);
         assert_eq!(fetcher.last_success_duration_secs, None);
 
-        // Set a requested difficulty
-        fetcher.last_requested_difficulty = Some(crate::nexus_orchestrator::TaskDifficulty::Medium);
-
-        // Update success tracking
-        fetcher.update_success_tracking(300); // 5 minutes
+        // Record a completed task
+        fetcher
+            .record_success(crate::nexus_orchestrator::TaskDifficulty::Medium, 300) // 5 minutes
+            .unwrap();
 
         // Verify tracking was updated
         assert_eq!(
@@ -515,18 +971,22 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_success_tracking_without_requested_difficulty() {
+    async fn test_success_tracking_ignores_requested_difficulty() {
         let mut fetcher = create_test_fetcher();
 
-        // No requested difficulty set
-        fetcher.last_requested_difficulty = None;
+        // A prefetch has already requested the next level
+        fetcher.last_requested_difficulty = Some(crate::nexus_orchestrator::TaskDifficulty::Large);
 
-        // Update success tracking
-        fetcher.update_success_tracking(300);
+        fetcher
+            .record_success(crate::nexus_orchestrator::TaskDifficulty::Medium, 300)
+            .unwrap();
 
-        // Should not update tracking when no requested difficulty
-        assert_eq!(fetcher.last_success_difficulty, None);
-        assert_eq!(fetcher.last_success_duration_secs, None);
+        // The completed task's difficulty is tracked, not the one requested since
+        assert_eq!(
+            fetcher.last_success_difficulty,
+            Some(crate::nexus_orchestrator::TaskDifficulty::Medium)
+        );
+        assert_eq!(fetcher.last_success_duration_secs, Some(300));
     }
 
     #[tokio::test]
@@ -570,6 +1030,37 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_extra_large3_promotes_through_extra_large5() {
+        for (current, expected) in [
+            (
+                crate::nexus_orchestrator::TaskDifficulty::ExtraLarge3,
+                crate::nexus_orchestrator::TaskDifficulty::ExtraLarge4,
+            ),
+            (
+                crate::nexus_orchestrator::TaskDifficulty::ExtraLarge4,
+                crate::nexus_orchestrator::TaskDifficulty::ExtraLarge5,
+            ),
+        ] {
+            let mut fetcher = create_test_fetcher();
+            fetcher.last_success_difficulty = Some(current);
+            fetcher.last_success_duration_secs = Some(300);
+
+            fetcher.fetch_task().await.unwrap();
+            assert_eq!(fetcher.last_requested_difficulty, Some(expected));
+        }
+    }
+
+    #[test]
+    fn test_promotion_ceiling_is_last_difficulty_level() {
+        let (_, hardest) = DIFFICULTY_LEVELS.last().unwrap();
+        assert_eq!(next_difficulty(*hardest), *hardest);
+        // Every other level promotes to the next one in the table
+        for pair in DIFFICULTY_LEVELS.windows(2) {
+            assert_eq!(next_difficulty(pair[0].1), pair[1].1);
+        }
+    }
+
     #[tokio::test]
     async fn test_extra_large5_stays_at_maximum() {
         let mut fetcher = create_test_fetcher();
@@ -1,5 +1,11 @@
 pub mod authenticated_worker;
 pub mod core;
+pub mod difficulty_state;
 pub mod fetcher;
+pub mod prefetch;
+pub mod proof_cache;
 pub mod prover;
+pub mod rejections;
+pub mod schedule;
+pub mod submission_order;
 pub mod submitter;
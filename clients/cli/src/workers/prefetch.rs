@@ -0,0 +1,142 @@
+//! Fetching the next task while the current one is being proved
+
+use std::time::Duration;
+
+/// Weight of the newest sample in the proving-time moving average
+const PROVING_TIME_SMOOTHING: f64 = 0.3;
+
+/// Tasks in flight needed to prefetch: the one being proved and the next one
+const PREFETCH_IN_FLIGHT: u32 = 2;
+
+/// How the worker fetches tasks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum FetchMode {
+    /// Fetch the next task only after the current one has been submitted
+    Single,
+    /// Fetch the next task during proving when proofs finish faster than the fetch interval
+    #[default]
+    Adaptive,
+}
+
+/// Decides whether to fetch the next task while the current one is being proved.
+///
+/// The orchestrator allows one fetch per `fetch_interval`. When proofs finish faster
+/// than that, a single-fetch worker sits idle waiting on the rate limit; fetching during
+/// proving overlaps that wait with useful work. When proofs are slower, the wait is
+/// already hidden and a prefetched task would only sit unproved, so it doesn't.
+#[derive(Debug, Clone)]
+pub struct PrefetchController {
+    fetch_interval: Duration,
+    /// Moving average of recent proving times; `None` until a proof completes
+    average_proving_time: Option<Duration>,
+    /// Server limit on tasks in flight; local limits apply when `None`
+    max_in_flight: Option<u32>,
+    /// Local limit on tasks in flight from `--queue-size`
+    queue_size: u32,
+}
+
+impl PrefetchController {
+    pub fn new(fetch_interval: Duration, queue_size: u32) -> Self {
+        Self {
+            fetch_interval,
+            average_proving_time: None,
+            max_in_flight: None,
+            queue_size,
+        }
+    }
+
+    /// Honor the server's latest limit on tasks in flight, or fall back to local limits
+    pub fn set_max_in_flight(&mut self, max_in_flight: Option<u32>) {
+        self.max_in_flight = max_in_flight;
+    }
+
+    /// Record how long a proof took
+    pub fn record_proving_time(&mut self, duration: Duration) {
+        self.average_proving_time = Some(match self.average_proving_time {
+            Some(average) => Duration::from_secs_f64(
+                average.as_secs_f64() * (1.0 - PROVING_TIME_SMOOTHING)
+                    + duration.as_secs_f64() * PROVING_TIME_SMOOTHING,
+            ),
+            None => duration,
+        });
+    }
+
+    /// Whether to fetch the next task while proving the current one
+    pub fn should_prefetch(&self) -> bool {
+        let allowed = self.queue_size >= PREFETCH_IN_FLIGHT
+            && self
+                .max_in_flight
+                .is_none_or(|max| max >= PREFETCH_IN_FLIGHT);
+        allowed
+            && self
+                .average_proving_time
+                .is_some_and(|average| average < self.fetch_interval)
+    }
+
+    /// Most tasks to hold fetched ahead of the one being proved; zero when not prefetching
+    pub fn max_ahead(&self) -> usize {
+        if !self.should_prefetch() {
+            return 0;
+        }
+        let in_flight = self
+            .max_in_flight
+            .map_or(self.queue_size, |max| max.min(self.queue_size));
+        in_flight.saturating_sub(1) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_prefetch_before_first_proof() {
+        let controller = PrefetchController::new(Duration::from_secs(120), 2);
+        assert!(!controller.should_prefetch());
+    }
+
+    #[test]
+    fn test_prefetch_follows_proving_time() {
+        let mut controller = PrefetchController::new(Duration::from_secs(120), 2);
+        controller.record_proving_time(Duration::from_secs(30));
+        assert!(controller.should_prefetch());
+
+        // Proofs slow down past the fetch interval
+        for _ in 0..10 {
+            controller.record_proving_time(Duration::from_secs(600));
+        }
+        assert!(!controller.should_prefetch());
+    }
+
+    #[test]
+    fn test_queue_size_bounds_tasks_ahead() {
+        let mut controller = PrefetchController::new(Duration::from_secs(120), 4);
+        assert_eq!(controller.max_ahead(), 0);
+        controller.record_proving_time(Duration::from_secs(30));
+        assert_eq!(controller.max_ahead(), 3);
+
+        // The server limit wins when it is tighter
+        controller.set_max_in_flight(Some(2));
+        assert_eq!(controller.max_ahead(), 1);
+
+        // A queue of one never prefetches
+        let mut controller = PrefetchController::new(Duration::from_secs(120), 1);
+        controller.record_proving_time(Duration::from_secs(30));
+        assert!(!controller.should_prefetch());
+        assert_eq!(controller.max_ahead(), 0);
+    }
+
+    #[test]
+    fn test_server_limit_bounds_prefetch() {
+        let mut controller = PrefetchController::new(Duration::from_secs(120), 2);
+        controller.record_proving_time(Duration::from_secs(30));
+
+        controller.set_max_in_flight(Some(1));
+        assert!(!controller.should_prefetch());
+        controller.set_max_in_flight(Some(4));
+        assert!(controller.should_prefetch());
+        // No hint: back to local limits
+        controller.set_max_in_flight(None);
+        assert!(controller.should_prefetch());
+    }
+}
@@ -0,0 +1,255 @@
+//! Cache of proofs by task content
+//!
+//! The orchestrator can assign the same program and inputs again under a new task ID.
+//! Proofs are deterministic, so the proofs and hashes computed the first time can be
+//! submitted again without re-proving. Hashes are always kept; the proofs themselves are
+//! kept, postcard-encoded, within a memory budget, and dropped from the least recently used
+//! entries first. An entry without proofs only serves tasks submitted without them.
+
+use crate::nexus_orchestrator::TaskType;
+use crate::task::Task;
+use nexus_sdk::stwo::seq::Proof;
+use sha3::{Digest, Keccak256};
+use std::collections::{HashMap, VecDeque};
+
+/// Proof hashes, and possibly the proofs, computed for a task
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedProofHash {
+    pub combined_hash: String,
+    pub individual_proof_hashes: Vec<String>,
+    /// Postcard-encoded proofs, or `None` if they weren't kept
+    pub proofs: Option<Vec<Vec<u8>>>,
+}
+
+impl CachedProofHash {
+    /// Encode `proofs` for caching; `None` if any fails to encode
+    pub fn encode_proofs(proofs: &[Proof]) -> Option<Vec<Vec<u8>>> {
+        proofs
+            .iter()
+            .map(|proof| postcard::to_allocvec(proof).ok())
+            .collect()
+    }
+
+    /// The cached proofs, or `None` if they weren't kept or fail to decode
+    pub fn decode_proofs(&self) -> Option<Vec<Proof>> {
+        self.proofs
+            .as_ref()?
+            .iter()
+            .map(|bytes| postcard::from_bytes(bytes).ok())
+            .collect()
+    }
+
+    /// Bytes held by the encoded proofs
+    fn proof_bytes(&self) -> u64 {
+        self.proofs
+            .iter()
+            .flatten()
+            .map(|proof| proof.len() as u64)
+            .sum()
+    }
+}
+
+/// Least-recently-used cache of proofs keyed by [`content_key`]
+#[derive(Debug)]
+pub struct ProofHashCache {
+    capacity: usize,
+    /// Most bytes of encoded proofs held at once
+    max_proof_bytes: u64,
+    /// Bytes of encoded proofs currently held
+    proof_bytes: u64,
+    entries: HashMap<String, CachedProofHash>,
+    /// Keys from least to most recently used
+    order: VecDeque<String>,
+}
+
+/// Hash of a task's program and inputs, independent of its task ID
+pub fn content_key(task: &Task) -> String {
+    let mut hasher = Keccak256::new();
+    hasher.update(task.program_id.as_bytes());
+    for inputs in &task.public_inputs_list {
+        // Length-prefix each input so different splits of the same bytes differ
+        hasher.update((inputs.len() as u64).to_le_bytes());
+        hasher.update(inputs);
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+/// Whether a task can be submitted with a cached hash alone, i.e. without its proofs
+pub fn can_use_cached_hash(task: &Task, always_attach_proof: bool) -> bool {
+    match task.task_type {
+        TaskType::ProofHash => !always_attach_proof,
+        TaskType::AllProofHashes => true,
+        _ => false,
+    }
+}
+
+impl ProofHashCache {
+    /// Create a cache holding at most `capacity` entries and `max_proof_bytes` of encoded
+    /// proofs; `None` when `capacity` is zero
+    pub fn new(capacity: usize, max_proof_bytes: u64) -> Option<Self> {
+        (capacity > 0).then(|| Self {
+            capacity,
+            max_proof_bytes,
+            proof_bytes: 0,
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+        })
+    }
+
+    /// Whether proofs of `bytes` bytes could be kept at all
+    fn fits_proofs(&self, bytes: u64) -> bool {
+        bytes <= self.max_proof_bytes
+    }
+
+    /// Look up `key`, marking it as recently used
+    pub fn get(&mut self, key: &str) -> Option<CachedProofHash> {
+        let entry = self.entries.get(key)?.clone();
+        self.touch(key);
+        Some(entry)
+    }
+
+    /// Store `value` under `key`, evicting the least recently used entry if full and
+    /// dropping the least recently used proofs beyond the memory budget
+    pub fn insert(&mut self, key: String, mut value: CachedProofHash) {
+        if !self.fits_proofs(value.proof_bytes()) {
+            value.proofs = None;
+        }
+        self.proof_bytes += value.proof_bytes();
+        match self.entries.insert(key.clone(), value) {
+            Some(previous) => {
+                self.proof_bytes -= previous.proof_bytes();
+                self.touch(&key);
+            }
+            None => {
+                self.order.push_back(key);
+                if self.order.len() > self.capacity {
+                    if let Some(oldest) = self.order.pop_front() {
+                        if let Some(evicted) = self.entries.remove(&oldest) {
+                            self.proof_bytes -= evicted.proof_bytes();
+                        }
+                    }
+                }
+            }
+        }
+
+        // The newest entry fits on its own, so this stops before reaching it
+        for key in &self.order {
+            if self.proof_bytes <= self.max_proof_bytes {
+                break;
+            }
+            if let Some(entry) = self.entries.get_mut(key) {
+                let bytes = entry.proof_bytes();
+                entry.proofs = None;
+                self.proof_bytes -= bytes;
+            }
+        }
+    }
+
+    fn touch(&mut self, key: &str) {
+        if let Some(position) = self.order.iter().position(|k| k == key) {
+            if let Some(key) = self.order.remove(position) {
+                self.order.push_back(key);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nexus_orchestrator::TaskDifficulty;
+
+    fn task(task_id: &str, inputs: Vec<u8>) -> Task {
+        Task::new(
+            task_id.to_string(),
+            "fib_input_initial".to_string(),
+            inputs,
+            TaskType::ProofHash,
+            TaskDifficulty::Small,
+        )
+    }
+
+    fn hash(value: &str) -> CachedProofHash {
+        CachedProofHash {
+            combined_hash: value.to_string(),
+            individual_proof_hashes: vec![],
+            proofs: None,
+        }
+    }
+
+    fn with_proof(value: &str, proof_len: usize) -> CachedProofHash {
+        CachedProofHash {
+            proofs: Some(vec![vec![0; proof_len]]),
+            ..hash(value)
+        }
+    }
+
+    #[test]
+    fn test_content_key_ignores_task_id() {
+        assert_eq!(
+            content_key(&task("a", vec![1, 2, 3])),
+            content_key(&task("b", vec![1, 2, 3]))
+        );
+        assert_ne!(
+            content_key(&task("a", vec![1, 2, 3])),
+            content_key(&task("a", vec![1, 2, 4]))
+        );
+    }
+
+    #[test]
+    fn test_least_recently_used_is_evicted() {
+        let mut cache = ProofHashCache::new(2, 0).unwrap();
+        cache.insert("a".to_string(), hash("1"));
+        cache.insert("b".to_string(), hash("2"));
+        // Using "a" makes "b" the eviction candidate
+        assert_eq!(cache.get("a"), Some(hash("1")));
+        cache.insert("c".to_string(), hash("3"));
+
+        assert!(cache.get("b").is_none());
+        assert_eq!(cache.get("a"), Some(hash("1")));
+        assert_eq!(cache.get("c"), Some(hash("3")));
+    }
+
+    #[test]
+    fn test_zero_capacity_disables_cache() {
+        assert!(ProofHashCache::new(0, 100).is_none());
+    }
+
+    #[test]
+    fn test_proofs_beyond_budget_are_dropped_oldest_first() {
+        let mut cache = ProofHashCache::new(4, 100).unwrap();
+        cache.insert("a".to_string(), with_proof("1", 60));
+        cache.insert("b".to_string(), with_proof("2", 30));
+        // Over budget: "a" loses its proofs but keeps its hash
+        cache.insert("c".to_string(), with_proof("3", 40));
+
+        assert_eq!(cache.get("a"), Some(hash("1")));
+        assert_eq!(cache.get("b"), Some(with_proof("2", 30)));
+        assert_eq!(cache.get("c"), Some(with_proof("3", 40)));
+
+        // Proofs larger than the whole budget are never kept
+        cache.insert("d".to_string(), with_proof("4", 101));
+        assert_eq!(cache.get("d"), Some(hash("4")));
+        assert_eq!(cache.proof_bytes, 70);
+    }
+
+    #[test]
+    fn test_evicted_entries_release_their_proofs() {
+        let mut cache = ProofHashCache::new(1, 100).unwrap();
+        cache.insert("a".to_string(), with_proof("1", 60));
+        cache.insert("b".to_string(), with_proof("2", 60));
+
+        assert!(cache.get("a").is_none());
+        assert_eq!(cache.get("b"), Some(with_proof("2", 60)));
+        assert_eq!(cache.proof_bytes, 60);
+    }
+
+    #[test]
+    fn test_only_hash_submissions_use_cache() {
+        let mut proof_hash = task("a", vec![1]);
+        assert!(can_use_cached_hash(&proof_hash, false));
+        assert!(!can_use_cached_hash(&proof_hash, true));
+        proof_hash.task_type = TaskType::ProofRequired;
+        assert!(!can_use_cached_hash(&proof_hash, false));
+    }
+}
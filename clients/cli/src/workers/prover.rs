@@ -1,52 +1,246 @@
 //! Proof generation using existing prover module
 
 use super::core::{EventSender, WorkerConfig};
-use crate::analytics::track_authenticated_proof_analytics;
-use crate::events::EventType;
+use super::proof_cache::{CachedProofHash, ProofHashCache, can_use_cached_hash, content_key};
+use crate::analytics::{track_authenticated_proof_analytics, track_task_unprovable};
+use crate::consts::cli_consts::MAX_TRACKED_PROOF_ATTEMPTS;
+use crate::events::{Event, EventType};
 use crate::logging::LogLevel;
+use crate::prover::engine::ProvingEngine;
+use crate::prover::format::save_proof_result;
 use crate::prover::{ProverError, ProverResult, authenticated_proving};
+use crate::storage::describe_write_error;
+use crate::system::process_tree_memory_bytes;
 use crate::task::Task;
+use ed25519_dalek::SigningKey;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum ProveError {
     #[error("Proof generation failed: {0}")]
     Generation(#[from] ProverError),
+
+    #[error("Task unprovable after {attempts} attempts: {source}")]
+    Unprovable {
+        attempts: u32,
+        #[source]
+        source: ProverError,
+    },
+}
+
+impl ProveError {
+    /// Whether proving failed because the workers themselves died (a crashed or killed
+    /// subprocess, or a panicked proving task) rather than because of the task
+    pub fn is_worker_death(&self) -> bool {
+        let source = match self {
+            ProveError::Generation(source) => source,
+            ProveError::Unprovable { source, .. } => source,
+        };
+        matches!(
+            source,
+            ProverError::Subprocess(_) | ProverError::OutOfMemory(_) | ProverError::JoinError(_)
+        )
+    }
+}
+
+/// Counts failed proving attempts per task ID
+#[derive(Debug, Default)]
+struct ProofAttempts {
+    failures: HashMap<String, u32>,
+    /// Tracked task IDs, oldest first
+    order: VecDeque<String>,
+}
+
+impl ProofAttempts {
+    /// Record a failed attempt and return the number of failures so far for this task
+    fn record_failure(&mut self, task_id: &str) -> u32 {
+        if !self.failures.contains_key(task_id) {
+            // Keep memory bounded by forgetting the task tracked longest ago
+            if self.order.len() >= MAX_TRACKED_PROOF_ATTEMPTS {
+                if let Some(oldest) = self.order.pop_front() {
+                    self.failures.remove(&oldest);
+                }
+            }
+            self.order.push_back(task_id.to_string());
+        }
+        let count = self.failures.entry(task_id.to_string()).or_insert(0);
+        *count += 1;
+        *count
+    }
+
+    /// Forget the attempts for a task
+    fn clear(&mut self, task_id: &str) {
+        if self.failures.remove(task_id).is_some() {
+            self.order.retain(|id| id != task_id);
+        }
+    }
 }
 
 /// Task prover that generates proofs using the existing prover module
 pub struct TaskProver {
     event_sender: EventSender,
     config: WorkerConfig,
+    attempts: ProofAttempts,
+    proof_cache: Option<ProofHashCache>,
+    /// Session key tasks are fetched with, saved with `--save-proofs` proofs
+    signing_key: Option<SigningKey>,
 }
 
 impl TaskProver {
     pub fn new(event_sender: EventSender, config: WorkerConfig) -> Self {
+        let proof_cache = config
+            .proof_cache_size
+            .and_then(|size| ProofHashCache::new(size, config.proof_cache_max_bytes));
         Self {
             event_sender,
             config,
+            attempts: ProofAttempts::default(),
+            proof_cache,
+            signing_key: None,
         }
     }
 
+    /// Save `signing_key` with proofs saved to `--save-proofs`, so `submit-saved` can sign
+    /// them the way the orchestrator expects
+    pub fn with_signing_key(mut self, signing_key: SigningKey) -> Self {
+        self.signing_key = Some(signing_key);
+        self
+    }
+
     /// Generate proof for a task with proper logging
-    pub async fn prove_task(&self, task: &Task) -> Result<ProverResult, ProveError> {
+    pub async fn prove_task(&mut self, task: &Task) -> Result<ProverResult, ProveError> {
+        // Identical program and inputs prove identically; reuse the result if we have it.
+        // Tasks submitted with their proofs need the cached proofs, not just the hashes.
+        let needs_proofs = !can_use_cached_hash(task, self.config.always_attach_proof);
+        let cache_key = self.proof_cache.as_ref().map(|_| content_key(task));
+        let cached = cache_key
+            .as_deref()
+            .and_then(|key| self.proof_cache.as_mut()?.get(key))
+            .and_then(|cached| {
+                let proofs = if needs_proofs {
+                    cached.decode_proofs()?
+                } else {
+                    Vec::new()
+                };
+                Some((cached, proofs))
+            });
+        if let Some((cached, proofs)) = cached {
+            let reused = if needs_proofs { "proof" } else { "hash" };
+            self.event_sender
+                .send_event(Event::prover_with_level(
+                    0,
+                    format!(
+                        "Step 3 of 4: Proof generated for task {} (reused the {} of an identical earlier task)",
+                        task.task_id, reused
+                    ),
+                    EventType::Success,
+                    LogLevel::Info,
+                ).with_task_id(&task.task_id))
+                .await;
+            return Ok(ProverResult {
+                proofs,
+                combined_hash: cached.combined_hash,
+                individual_proof_hashes: cached.individual_proof_hashes,
+            });
+        }
+
+        // Sample memory while proving so the worker count can be reduced if the budget is exceeded
+        let peak_memory = Arc::new(AtomicU64::new(0));
+        let sampler = self
+            .config
+            .memory_budget_bytes
+            .map(|_| tokio::spawn(sample_peak_memory(Arc::clone(&peak_memory))));
+
         // Use existing prover module for proof generation
-        match authenticated_proving(
+        let result = authenticated_proving(
             task,
             &self.config.environment,
             &self.config.client_id,
             self.config.num_workers,
+            self.config.skip_local_verify,
         )
-        .await
-        {
+        .await;
+
+        if let Some(sampler) = sampler {
+            sampler.abort();
+            self.enforce_memory_budget(peak_memory.load(Ordering::Relaxed))
+                .await;
+        }
+
+        if let Some(notice) = ProvingEngine::take_subprocess_fallback_notice() {
+            self.event_sender
+                .send_prover_event(0, notice, EventType::Error, LogLevel::Warn)
+                .await;
+        }
+
+        match result {
             Ok((proofs, combined_hash, individual_proof_hashes)) => {
+                self.attempts.clear(&task.task_id);
+
+                if let (Some(cache), Some(key)) = (self.proof_cache.as_mut(), cache_key) {
+                    let encoded = (self.config.proof_cache_max_bytes > 0)
+                        .then(|| CachedProofHash::encode_proofs(&proofs))
+                        .flatten();
+                    cache.insert(
+                        key,
+                        CachedProofHash {
+                            combined_hash: combined_hash.clone(),
+                            individual_proof_hashes: individual_proof_hashes.clone(),
+                            proofs: encoded,
+                        },
+                    );
+                }
+
+                let proof_result = ProverResult {
+                    proofs,
+                    combined_hash,
+                    individual_proof_hashes,
+                };
+
+                if let Some(dir) = &self.config.save_proofs_dir {
+                    let saved = save_proof_result(
+                        dir,
+                        task,
+                        self.config.proof_format,
+                        &proof_result,
+                        self.signing_key.as_ref(),
+                    );
+                    if let Err(e) = saved {
+                        let message = match &e {
+                            ProverError::Io(io_error) => describe_write_error(
+                                &format!("the proof for task {}", task.task_id),
+                                io_error,
+                            ),
+                            _ => format!("Failed to save proof for task {}: {}", task.task_id, e),
+                        };
+                        self.event_sender
+                            .send_event(
+                                Event::prover_with_level(
+                                    0,
+                                    message,
+                                    EventType::Error,
+                                    LogLevel::Warn,
+                                )
+                                .with_task_id(&task.task_id),
+                            )
+                            .await;
+                    }
+                }
+
                 // Log successful proof generation
                 self.event_sender
-                    .send_prover_event(
-                        0, // Single-threaded prover for now
-                        format!("Step 3 of 4: Proof generated for task {}", task.task_id),
-                        EventType::Success,
-                        LogLevel::Info,
+                    .send_event(
+                        Event::prover_with_level(
+                            0, // Single-threaded prover for now
+                            format!("Step 3 of 4: Proof generated for task {}", task.task_id),
+                            EventType::Success,
+                            LogLevel::Info,
+                        )
+                        .with_task_id(&task.task_id),
                     )
                     .await;
 
@@ -56,24 +250,267 @@ impl TaskProver {
                     self.config.client_id.clone(),
                 ));
 
-                Ok(ProverResult {
-                    proofs,
-                    combined_hash,
-                    individual_proof_hashes,
-                })
+                Ok(proof_result)
             }
             Err(e) => {
+                let attempts = self.attempts.record_failure(&task.task_id);
+                if attempts >= self.config.max_proof_attempts {
+                    // Deterministic failure: surface it as a bad task, not a transient error
+                    self.attempts.clear(&task.task_id);
+                    self.event_sender
+                        .send_event(
+                            Event::prover_with_level(
+                                0,
+                                format!(
+                                    "Task {} is unprovable after {} attempts, giving up: {}",
+                                    task.task_id, attempts, e
+                                ),
+                                EventType::Error,
+                                LogLevel::Error,
+                            )
+                            .with_task_id(&task.task_id),
+                        )
+                        .await;
+
+                    tokio::spawn(track_task_unprovable(
+                        task.clone(),
+                        attempts,
+                        e.to_string(),
+                        self.config.environment.clone(),
+                        self.config.client_id.clone(),
+                    ));
+
+                    return Err(ProveError::Unprovable {
+                        attempts,
+                        source: e,
+                    });
+                }
+
                 // Log proof generation failure
                 self.event_sender
-                    .send_prover_event(
-                        0, // Single-threaded prover for now
-                        format!("Proof generation failed for task {}: {}", task.task_id, e),
-                        EventType::Error,
-                        LogLevel::Error,
+                    .send_event(
+                        Event::prover_with_level(
+                            0, // Single-threaded prover for now
+                            format!(
+                                "Proof generation failed for task {} (attempt {}/{}): {}",
+                                task.task_id, attempts, self.config.max_proof_attempts, e
+                            ),
+                            EventType::Error,
+                            LogLevel::Error,
+                        )
+                        .with_task_id(&task.task_id),
                     )
                     .await;
                 Err(ProveError::Generation(e))
             }
         }
     }
+
+    /// Reduce the number of proving workers if the last task went over the memory budget
+    async fn enforce_memory_budget(&mut self, peak_bytes: u64) {
+        let Some(budget_bytes) = self.config.memory_budget_bytes else {
+            return;
+        };
+        let current = self.config.num_workers;
+        let reduced = scaled_workers(current, peak_bytes, budget_bytes);
+        if reduced < current {
+            self.config.num_workers = reduced;
+            self.event_sender
+                .send_prover_event(
+                    0,
+                    format!(
+                        "Memory usage {} MB exceeded budget of {} MB, reducing proving threads from {} to {}",
+                        peak_bytes / (1024 * 1024),
+                        budget_bytes / (1024 * 1024),
+                        current,
+                        reduced
+                    ),
+                    EventType::Refresh,
+                    LogLevel::Warn,
+                )
+                .await;
+        }
+    }
+}
+
+/// Record the peak memory of this process and its prover subprocesses until aborted
+async fn sample_peak_memory(peak: Arc<AtomicU64>) {
+    let mut interval = tokio::time::interval(Duration::from_secs(1));
+    loop {
+        interval.tick().await;
+        let used = tokio::task::spawn_blocking(process_tree_memory_bytes)
+            .await
+            .unwrap_or(0);
+        peak.fetch_max(used, Ordering::Relaxed);
+    }
+}
+
+/// Worker count scaled down in proportion to how far `peak` overshot `budget`, at least 1
+fn scaled_workers(num_workers: usize, peak: u64, budget: u64) -> usize {
+    if peak <= budget || peak == 0 {
+        return num_workers;
+    }
+    ((num_workers as u64 * budget / peak) as usize).clamp(1, num_workers.saturating_sub(1).max(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consts::cli_consts::EVENT_QUEUE_SIZE;
+    use crate::environment::Environment;
+    use crate::nexus_orchestrator::{TaskDifficulty, TaskType};
+    use crate::orchestrator::MockOrchestrator;
+    use crate::workers::submitter::ProofSubmitter;
+    use tokio::sync::mpsc;
+
+    #[tokio::test]
+    async fn test_cache_hit_skips_proving_and_submits() {
+        let (sender, _receiver) = mpsc::channel(EVENT_QUEUE_SIZE);
+        let event_sender = EventSender::new(sender);
+        let mut config = WorkerConfig::new(Environment::Production, "test".to_string());
+        config.proof_cache_size = Some(4);
+
+        // Inputs too short to prove, so only a cache hit can succeed
+        let task = Task::new(
+            "repeat".to_string(),
+            "fib_input_initial".to_string(),
+            vec![1],
+            TaskType::ProofHash,
+            TaskDifficulty::Small,
+        );
+        let mut prover = TaskProver::new(event_sender.clone(), config.clone());
+        prover.proof_cache.as_mut().unwrap().insert(
+            content_key(&task),
+            CachedProofHash {
+                combined_hash: "cached-hash".to_string(),
+                individual_proof_hashes: vec![],
+                proofs: None,
+            },
+        );
+
+        let result = prover.prove_task(&task).await.unwrap();
+        assert_eq!(result.combined_hash, "cached-hash");
+        assert!(result.proofs.is_empty());
+
+        let mut orchestrator = MockOrchestrator::new();
+        orchestrator
+            .expect_submit_proof()
+            .withf(|task_id, proof_hash, _, proofs, _, _, _, _| {
+                task_id == "repeat" && proof_hash == "cached-hash" && proofs.is_empty()
+            })
+            .times(1)
+            .returning(|_, _, _, _, _, _, _, _| Ok(()));
+        let mut submitter = ProofSubmitter::new(
+            0,
+            SigningKey::generate(&mut rand_core::OsRng),
+            Box::new(orchestrator),
+            event_sender,
+            &config,
+        );
+        assert!(submitter.submit_proof(&task, &result).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_cached_proofs_serve_tasks_submitted_with_proofs() {
+        let (sender, _receiver) = mpsc::channel(EVENT_QUEUE_SIZE);
+        let mut config = WorkerConfig::new(Environment::Production, "test".to_string());
+        config.proof_cache_size = Some(4);
+
+        // Inputs too short to prove, so only a cache hit can succeed
+        let task = Task::new(
+            "repeat".to_string(),
+            "fib_input_initial".to_string(),
+            vec![1],
+            TaskType::ProofRequired,
+            TaskDifficulty::Small,
+        );
+        let mut prover = TaskProver::new(EventSender::new(sender), config);
+        let hash_only = CachedProofHash {
+            combined_hash: "cached-hash".to_string(),
+            individual_proof_hashes: vec!["cached-hash".to_string()],
+            proofs: None,
+        };
+        let cache = prover.proof_cache.as_mut().unwrap();
+        cache.insert(content_key(&task), hash_only.clone());
+
+        // A hash alone can't serve a task that must carry its proofs
+        assert!(prover.prove_task(&task).await.is_err());
+
+        let cache = prover.proof_cache.as_mut().unwrap();
+        cache.insert(
+            content_key(&task),
+            CachedProofHash {
+                proofs: Some(vec![]),
+                ..hash_only
+            },
+        );
+        let result = prover.prove_task(&task).await.unwrap();
+        assert_eq!(result.combined_hash, "cached-hash");
+        assert_eq!(
+            result.individual_proof_hashes,
+            vec!["cached-hash".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_record_failure_counts_per_task() {
+        let mut attempts = ProofAttempts::default();
+        assert_eq!(attempts.record_failure("a"), 1);
+        assert_eq!(attempts.record_failure("a"), 2);
+        assert_eq!(attempts.record_failure("b"), 1);
+        assert_eq!(attempts.record_failure("a"), 3);
+    }
+
+    #[test]
+    fn test_is_worker_death() {
+        let crashed = ProverError::Subprocess("killed by signal 4".to_string());
+        assert!(ProveError::Generation(crashed).is_worker_death());
+
+        let malformed = ProverError::MalformedTask("too short".to_string());
+        assert!(!ProveError::Generation(malformed).is_worker_death());
+
+        let unprovable = ProveError::Unprovable {
+            attempts: 3,
+            source: ProverError::Subprocess("exit status: 137".to_string()),
+        };
+        assert!(unprovable.is_worker_death());
+    }
+
+    #[test]
+    fn test_clear_resets_attempts() {
+        let mut attempts = ProofAttempts::default();
+        attempts.record_failure("a");
+        attempts.record_failure("a");
+        attempts.clear("a");
+        assert_eq!(attempts.record_failure("a"), 1);
+    }
+
+    #[test]
+    fn test_tracking_is_bounded() {
+        let mut attempts = ProofAttempts::default();
+        for i in 0..MAX_TRACKED_PROOF_ATTEMPTS {
+            attempts.record_failure(&i.to_string());
+        }
+        attempts.record_failure("1");
+        attempts.record_failure("new");
+        assert_eq!(attempts.failures.len(), MAX_TRACKED_PROOF_ATTEMPTS);
+        // Only the oldest task is forgotten
+        assert!(!attempts.failures.contains_key("0"));
+        assert_eq!(attempts.record_failure("1"), 3);
+        // Existing entries keep counting without triggering eviction
+        assert_eq!(attempts.record_failure("new"), 2);
+        assert_eq!(attempts.failures.len(), MAX_TRACKED_PROOF_ATTEMPTS);
+    }
+
+    #[test]
+    fn test_scaled_workers() {
+        // Within budget: unchanged
+        assert_eq!(scaled_workers(4, 6, 8), 4);
+        // Twice the budget: halve
+        assert_eq!(scaled_workers(4, 16, 8), 2);
+        // Slightly over: still drop at least one worker
+        assert_eq!(scaled_workers(4, 9, 8), 3);
+        // Never below one
+        assert_eq!(scaled_workers(1, 100, 8), 1);
+    }
 }
@@ -0,0 +1,151 @@
+//! Log of proof submissions rejected by the orchestrator
+//!
+//! Each rejection is appended to ~/.nexus/rejections.jsonl as one JSON object per line,
+//! so patterns (e.g. every `LARGE` task being rejected) can be found after the fact.
+//! When the file grows past [`MAX_REJECTIONS_FILE_BYTES`] it is rotated to
+//! `rejections.jsonl.1`, replacing any previous rotation.
+
+use crate::storage::{rotate_file, rotated_path};
+use crate::task::Task;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Size at which the rejections file is rotated
+pub const MAX_REJECTIONS_FILE_BYTES: u64 = 1024 * 1024;
+
+/// Rejection files kept: the one being written and a single rotation
+const REJECTIONS_FILE_COUNT: usize = 2;
+
+/// Get the path to the rejections file, typically ~/.nexus/rejections.jsonl.
+pub fn get_rejections_path() -> Result<PathBuf, std::io::Error> {
+    let home_path = home::home_dir().ok_or(std::io::Error::new(
+        std::io::ErrorKind::NotFound,
+        "Home directory not found",
+    ))?;
+    Ok(home_path.join(".nexus").join("rejections.jsonl"))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Rejection {
+    pub task_id: String,
+    /// Task type, e.g. "PROOF_HASH"
+    pub task_type: String,
+    /// Task difficulty, e.g. "LARGE"
+    pub difficulty: String,
+    /// HTTP status returned by the orchestrator
+    pub status: u16,
+    /// Message returned by the orchestrator
+    pub message: String,
+    /// When the rejection was received (RFC 3339)
+    pub timestamp: String,
+}
+
+impl Rejection {
+    pub fn new(task: &Task, status: u16, message: String) -> Self {
+        Self {
+            task_id: task.task_id.clone(),
+            task_type: task.task_type.as_str_name().to_string(),
+            difficulty: task.difficulty.as_str_name().to_string(),
+            status,
+            message,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+
+    /// Appends the rejection to the file at `path`, rotating it first if it is full.
+    pub fn append(&self, path: &Path) -> Result<(), std::io::Error> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        if fs::metadata(path).is_ok_and(|metadata| metadata.len() >= MAX_REJECTIONS_FILE_BYTES) {
+            rotate_file(path, REJECTIONS_FILE_COUNT)?;
+        }
+
+        let mut line = serde_json::to_string(self)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        line.push('\n');
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?
+            .write_all(line.as_bytes())
+    }
+
+    /// Loads all rejections from the file at `path`, oldest first, including the rotated
+    /// file. Lines that can't be parsed are skipped.
+    pub fn load_all(path: &Path) -> Result<Vec<Self>, std::io::Error> {
+        let mut rejections = Vec::new();
+        for file in [rotated_path(path, 1), path.to_path_buf()] {
+            let contents = match fs::read_to_string(&file) {
+                Ok(contents) => contents,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e),
+            };
+            rejections.extend(
+                contents
+                    .lines()
+                    .filter_map(|line| serde_json::from_str::<Self>(line).ok()),
+            );
+        }
+        Ok(rejections)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nexus_orchestrator::{TaskDifficulty, TaskType};
+    use tempfile::tempdir;
+
+    fn rejection(task_id: &str) -> Rejection {
+        let task = Task::new(
+            task_id.to_string(),
+            "fib_input_initial".to_string(),
+            vec![],
+            TaskType::ProofHash,
+            TaskDifficulty::Large,
+        );
+        Rejection::new(&task, 400, "invalid proof".to_string())
+    }
+
+    #[test]
+    fn test_append_and_load() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("rejections.jsonl");
+
+        rejection("a").append(&path).unwrap();
+        rejection("b").append(&path).unwrap();
+
+        let loaded = Rejection::load_all(&path).unwrap();
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[0].task_id, "a");
+        assert_eq!(loaded[1].difficulty, "LARGE");
+        assert_eq!(loaded[1].status, 400);
+    }
+
+    #[test]
+    fn test_full_file_is_rotated() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("rejections.jsonl");
+        fs::write(&path, vec![b' '; MAX_REJECTIONS_FILE_BYTES as usize]).unwrap();
+
+        rejection("new").append(&path).unwrap();
+
+        assert_eq!(
+            fs::metadata(rotated_path(&path, 1)).unwrap().len(),
+            MAX_REJECTIONS_FILE_BYTES
+        );
+        let loaded = Rejection::load_all(&path).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].task_id, "new");
+    }
+
+    #[test]
+    fn test_missing_file_loads_empty() {
+        let dir = tempdir().unwrap();
+        let loaded = Rejection::load_all(&dir.path().join("rejections.jsonl")).unwrap();
+        assert!(loaded.is_empty());
+    }
+}
@@ -0,0 +1,136 @@
+//! Local-time window during which the worker fetches new tasks
+
+use chrono::{NaiveTime, Timelike};
+use std::fmt::{self, Display};
+use std::str::FromStr;
+use std::time::Duration;
+
+const SECS_PER_DAY: u32 = 24 * 60 * 60;
+
+/// Daily proving window in local time, e.g. `22:00-06:00`.
+///
+/// Windows may cross midnight. A window whose start equals its end (or an
+/// empty schedule string) is always active.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProvingSchedule {
+    start: NaiveTime,
+    end: NaiveTime,
+}
+
+impl ProvingSchedule {
+    /// A schedule that is always active
+    pub fn always() -> Self {
+        Self {
+            start: NaiveTime::MIN,
+            end: NaiveTime::MIN,
+        }
+    }
+
+    /// Whether the given local time falls inside the window
+    pub fn is_active_at(&self, time: NaiveTime) -> bool {
+        if self.start == self.end {
+            true
+        } else if self.start < self.end {
+            self.start <= time && time < self.end
+        } else {
+            // Window crosses midnight
+            time >= self.start || time < self.end
+        }
+    }
+
+    /// Time from `time` until the window next opens (zero if already active)
+    pub fn time_until_active(&self, time: NaiveTime) -> Duration {
+        if self.is_active_at(time) {
+            return Duration::ZERO;
+        }
+        let now = time.num_seconds_from_midnight();
+        let start = self.start.num_seconds_from_midnight();
+        let secs = (start + SECS_PER_DAY - now) % SECS_PER_DAY;
+        Duration::from_secs(secs as u64)
+    }
+}
+
+impl FromStr for ProvingSchedule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Ok(Self::always());
+        }
+        let (start, end) = s
+            .split_once('-')
+            .ok_or_else(|| format!("expected HH:MM-HH:MM, got '{}'", s))?;
+        let parse = |t: &str| {
+            NaiveTime::parse_from_str(t.trim(), "%H:%M")
+                .map_err(|e| format!("invalid time '{}': {}", t.trim(), e))
+        };
+        Ok(Self {
+            start: parse(start)?,
+            end: parse(end)?,
+        })
+    }
+}
+
+impl Display for ProvingSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}-{}",
+            self.start.format("%H:%M"),
+            self.end.format("%H:%M")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    #[test]
+    fn test_same_day_window() {
+        let schedule: ProvingSchedule = "09:00-17:30".parse().unwrap();
+        assert!(!schedule.is_active_at(at(8, 59)));
+        assert!(schedule.is_active_at(at(9, 0)));
+        assert!(schedule.is_active_at(at(17, 29)));
+        assert!(!schedule.is_active_at(at(17, 30)));
+        assert_eq!(
+            schedule.time_until_active(at(8, 0)),
+            Duration::from_secs(3600)
+        );
+    }
+
+    #[test]
+    fn test_window_crossing_midnight() {
+        let schedule: ProvingSchedule = "22:00-06:00".parse().unwrap();
+        assert!(schedule.is_active_at(at(23, 0)));
+        assert!(schedule.is_active_at(at(0, 0)));
+        assert!(schedule.is_active_at(at(5, 59)));
+        assert!(!schedule.is_active_at(at(6, 0)));
+        assert!(!schedule.is_active_at(at(12, 0)));
+        assert_eq!(
+            schedule.time_until_active(at(21, 0)),
+            Duration::from_secs(3600)
+        );
+        assert_eq!(schedule.time_until_active(at(23, 0)), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_empty_schedule_is_always_on() {
+        let schedule: ProvingSchedule = "".parse().unwrap();
+        assert_eq!(schedule, ProvingSchedule::always());
+        assert!(schedule.is_active_at(at(3, 0)));
+        assert!(schedule.is_active_at(at(15, 0)));
+    }
+
+    #[test]
+    fn test_invalid_schedule() {
+        assert!("22:00".parse::<ProvingSchedule>().is_err());
+        assert!("25:00-06:00".parse::<ProvingSchedule>().is_err());
+        assert!("ten-six".parse::<ProvingSchedule>().is_err());
+    }
+}
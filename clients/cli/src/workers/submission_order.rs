@@ -0,0 +1,122 @@
+//! Order in which a backlog of proofs is submitted
+//!
+//! Proofs are submitted first in, first out by default. With `difficulty-priority`, a small
+//! reorder buffer lets harder (higher-value) proofs jump ahead of easier ones. No proof is
+//! passed over more than [`MAX_REORDER_DELAY`] times, so easy proofs can't starve.
+
+use crate::difficulty::difficulty_rank;
+use crate::nexus_orchestrator::TaskDifficulty;
+use std::cmp::Reverse;
+
+/// Number of pending proofs considered when picking the next one to submit
+pub const REORDER_BUFFER_SIZE: usize = 8;
+
+/// Submissions after which a proof is sent regardless of its difficulty
+pub const MAX_REORDER_DELAY: usize = 16;
+
+/// How a backlog of proofs is ordered for submission
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum SubmissionOrder {
+    /// In the order the proofs were queued
+    #[default]
+    Fifo,
+    /// Harder proofs first, within a bounded reorder buffer
+    DifficultyPriority,
+}
+
+/// Reorder `items`, given in queue order, for submission. `difficulty` returns each item's
+/// task difficulty, if known.
+pub fn order_for_submission<T>(
+    items: Vec<T>,
+    order: SubmissionOrder,
+    difficulty: impl Fn(&T) -> Option<TaskDifficulty>,
+) -> Vec<T> {
+    if order == SubmissionOrder::Fifo {
+        return items;
+    }
+
+    let mut ordered = Vec::with_capacity(items.len());
+    let mut queue = items.into_iter().enumerate();
+    // Pending items with their queue position, oldest first
+    let mut buffer: Vec<(usize, T)> = Vec::with_capacity(REORDER_BUFFER_SIZE);
+    loop {
+        while buffer.len() < REORDER_BUFFER_SIZE {
+            match queue.next() {
+                Some(item) => buffer.push(item),
+                None => break,
+            }
+        }
+        if buffer.is_empty() {
+            break;
+        }
+
+        let oldest_overdue = buffer[0].0 + MAX_REORDER_DELAY <= ordered.len();
+        let next = if oldest_overdue {
+            0
+        } else {
+            // Hardest first; the oldest wins ties
+            (0..buffer.len())
+                .max_by_key(|&i| {
+                    (
+                        difficulty(&buffer[i].1).and_then(difficulty_rank),
+                        Reverse(i),
+                    )
+                })
+                .unwrap_or(0)
+        };
+        ordered.push(buffer.remove(next).1);
+    }
+    ordered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order(items: Vec<TaskDifficulty>, order: SubmissionOrder) -> Vec<TaskDifficulty> {
+        order_for_submission(items, order, |difficulty| Some(*difficulty))
+    }
+
+    #[test]
+    fn test_fifo_keeps_queue_order() {
+        let items = vec![
+            TaskDifficulty::Small,
+            TaskDifficulty::Large,
+            TaskDifficulty::Medium,
+        ];
+        assert_eq!(order(items.clone(), SubmissionOrder::Fifo), items);
+    }
+
+    #[test]
+    fn test_difficulty_priority_submits_harder_first() {
+        let items = vec![
+            TaskDifficulty::Small,
+            TaskDifficulty::Large,
+            TaskDifficulty::Medium,
+            TaskDifficulty::Large,
+        ];
+        assert_eq!(
+            order(items, SubmissionOrder::DifficultyPriority),
+            vec![
+                TaskDifficulty::Large,
+                TaskDifficulty::Large,
+                TaskDifficulty::Medium,
+                TaskDifficulty::Small,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_easy_proofs_are_not_starved() {
+        // One easy proof queued ahead of a long run of hard ones
+        let mut items = vec![(0, TaskDifficulty::Small)];
+        items.extend((1..100).map(|i| (i, TaskDifficulty::Large)));
+
+        let ordered = order_for_submission(items, SubmissionOrder::DifficultyPriority, |item| {
+            Some(item.1)
+        });
+        let position = ordered.iter().position(|item| item.0 == 0).unwrap();
+        assert!(position <= MAX_REORDER_DELAY);
+        assert_eq!(ordered.len(), 100);
+    }
+}
@@ -1,15 +1,18 @@
 //! Proof submission with network retry logic
 
 use super::core::{EventSender, WorkerConfig};
+use super::rejections::Rejection;
 use crate::analytics::{
     track_proof_accepted, track_proof_submission_error, track_proof_submission_success,
 };
 use crate::consts::cli_consts::{proof_submission, rate_limiting};
-use crate::events::EventType;
+use crate::events::{Event, EventType};
 use crate::logging::LogLevel;
 use crate::network::{NetworkClient, ProofSubmission, RequestTimer, RequestTimerConfig};
 use crate::orchestrator::Orchestrator;
+use crate::orchestrator::error::OrchestratorError;
 use crate::prover::ProverResult;
+use crate::prover::format::SavedProofMetadata;
 use crate::task::Task;
 use ed25519_dalek::SigningKey;
 use thiserror::Error;
@@ -24,15 +27,19 @@ pub enum SubmitError {
 
 /// Proof submitter with built-in retry and error handling
 pub struct ProofSubmitter {
+    node_id: u64,
     signing_key: SigningKey,
     orchestrator: Box<dyn Orchestrator>,
     network_client: NetworkClient,
     event_sender: EventSender,
     config: WorkerConfig,
+    /// Whether a proof has been accepted yet, for the first-proof milestone
+    first_proof_submitted: bool,
 }
 
 impl ProofSubmitter {
     pub fn new(
+        node_id: u64,
         signing_key: SigningKey,
         orchestrator: Box<dyn Orchestrator>,
         event_sender: EventSender,
@@ -44,6 +51,12 @@ impl ProofSubmitter {
             rate_limiting::SUBMISSION_MAX_REQUESTS_PER_WINDOW,
             rate_limiting::submission_window(),
             proof_submission::initial_backoff(), // Use as default retry delay
+        )
+        .with_state_path(
+            config
+                .request_timer_state_dir
+                .as_ref()
+                .map(|dir| dir.join("submit.json")),
         );
         let request_timer = RequestTimer::new(timer_config);
 
@@ -51,14 +64,22 @@ impl ProofSubmitter {
         let network_client = NetworkClient::new(request_timer, proof_submission::MAX_RETRIES);
 
         Self {
+            node_id,
             signing_key,
             orchestrator,
             network_client,
             event_sender,
             config: config.clone(),
+            first_proof_submitted: false,
         }
     }
 
+    /// Sign later submissions with `signing_key`, e.g. the key a saved proof's task was
+    /// fetched with
+    pub fn set_signing_key(&mut self, signing_key: SigningKey) {
+        self.signing_key = signing_key;
+    }
+
     /// Submit proof with automatic retry and proper logging
     pub async fn submit_proof(
         &mut self,
@@ -67,10 +88,13 @@ impl ProofSubmitter {
     ) -> Result<(), SubmitError> {
         // Log start of submission
         self.event_sender
-            .send_proof_event(
-                format!("Step 3 of 4: Submitting proof for task {}...", task.task_id),
-                EventType::StateChange,
-                LogLevel::Info,
+            .send_event(
+                Event::proof_submitter_with_level(
+                    format!("Step 3 of 4: Submitting proof for task {}...", task.task_id),
+                    EventType::StateChange,
+                    LogLevel::Info,
+                )
+                .with_task_id(&task.task_id),
             )
             .await;
 
@@ -96,8 +120,12 @@ impl ProofSubmitter {
                 submission.with_individual_hashes(proof_result.individual_proof_hashes.clone());
         }
 
-        // Populate proofs for PROOF_REQUIRED; leave empty otherwise
-        if task.task_type == crate::nexus_orchestrator::TaskType::ProofRequired {
+        // Populate proofs for PROOF_REQUIRED (and ProofHash when diagnosing hash mismatches);
+        // leave empty otherwise
+        if task.task_type == crate::nexus_orchestrator::TaskType::ProofRequired
+            || (task.task_type == crate::nexus_orchestrator::TaskType::ProofHash
+                && self.config.always_attach_proof)
+        {
             submission = submission.with_proofs(proofs_bytes);
         }
 
@@ -119,17 +147,43 @@ impl ProofSubmitter {
                     format!(" (after {} attempts)", attempts)
                 };
 
+                // End-to-end latency from fetch to accepted submission
+                let latency = task.fetched_at.elapsed();
                 self.event_sender
-                    .send_proof_event(
-                        format!(
-                            "Step 4 of 4: Proof submitted successfully for task {}{}\n",
-                            task.task_id, attempt_text
-                        ),
-                        EventType::Success,
-                        LogLevel::Info,
+                    .send_event(
+                        Event::proof_submitter_with_level(
+                            format!(
+                                "Step 4 of 4: Proof submitted successfully for task {}{} ({:.1}s since fetch)\n",
+                                task.task_id,
+                                attempt_text,
+                                latency.as_secs_f64()
+                            ),
+                            EventType::Success,
+                            LogLevel::Info,
+                        )
+                        .with_latency(latency)
+                        .with_task_id(&task.task_id),
                     )
                     .await;
 
+                // Reassure new users that the node works end to end
+                if !self.first_proof_submitted {
+                    self.first_proof_submitted = true;
+                    self.event_sender
+                        .send_event(Event::proof_submitter_with_level(
+                            "🎉 First proof submitted! Your node is proving and earning points."
+                                .to_string(),
+                            EventType::Milestone,
+                            LogLevel::Info,
+                        ))
+                        .await;
+                }
+
+                // Don't let `submit-saved` submit this proof again
+                if let Some(dir) = &self.config.save_proofs_dir {
+                    let _ = SavedProofMetadata::mark_submitted(dir, &task.task_id);
+                }
+
                 // Track analytics for successful submission
                 self.track_successful_submission(task).await;
 
@@ -141,16 +195,32 @@ impl ProofSubmitter {
                 // Log submission failure with attempt count and appropriate level
                 let log_level = self.network_client.classify_error(&e);
                 self.event_sender
-                    .send_proof_event(
-                        format!(
-                            "Failed to submit proof for task {} after {} attempts: {}",
-                            task.task_id, attempts, e
-                        ),
-                        EventType::Error,
-                        log_level,
+                    .send_event(
+                        Event::proof_submitter_with_level(
+                            format!(
+                                "Failed to submit proof for task {} after {} attempts: {}",
+                                task.task_id, attempts, e
+                            ),
+                            EventType::Error,
+                            log_level,
+                        )
+                        .with_task_id(&task.task_id),
                     )
                     .await;
 
+                // Keep server rejections (not rate limits or outages) for later investigation
+                if let (
+                    Some(path),
+                    OrchestratorError::Http {
+                        status, message, ..
+                    },
+                ) = (&self.config.rejections_path, &e)
+                {
+                    if (400..500).contains(status) && *status != 429 {
+                        let _ = Rejection::new(task, *status, message.clone()).append(path);
+                    }
+                }
+
                 // Track analytics for submission error
                 tokio::spawn(track_proof_submission_error(
                     task.clone(),
@@ -170,12 +240,14 @@ impl ProofSubmitter {
         if task.task_type == crate::nexus_orchestrator::TaskType::ProofHash {
             tokio::spawn(track_proof_accepted(
                 task.clone(),
+                self.node_id,
                 self.config.environment.clone(),
                 self.config.client_id.clone(),
             ));
         } else {
             tokio::spawn(track_proof_submission_success(
                 task.clone(),
+                self.node_id,
                 self.config.environment.clone(),
                 self.config.client_id.clone(),
             ));